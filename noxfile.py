@@ -53,6 +53,9 @@ def test_rust(session: nox.Session):
         _run_cargo_test(session, features="full")
         _run_cargo_test(session, features="full gil-refs")
         _run_cargo_test(session, features="abi3 full")
+        if _get_rust_version()[:2] >= (1, 62):
+            # experimental-stubs feature not supported before 1.62
+            _run_cargo_test(session, features="full experimental-stubs")
 
 
 @nox.session(name="test-py", venv_backend="none")
@@ -674,6 +677,7 @@ def check_feature_powerset(session: nox.Session):
         "auto-initialize",
         "generate-import-lib",
         "multiple-pymethods",  # TODO add this after MSRV 1.62
+        "experimental-stubs",  # TODO add this after MSRV 1.62
     }
 
     features = cargo_toml["features"]
@@ -691,7 +695,9 @@ def check_feature_powerset(session: nox.Session):
         )
 
     experimental_features = {
-        feature for feature in features if feature.startswith("experimental-")
+        feature
+        for feature in features
+        if feature.startswith("experimental-") and feature not in EXCLUDED_FROM_FULL
     }
     full_without_experimental = full_feature - experimental_features
 
@@ -775,15 +781,15 @@ def _get_feature_sets() -> Tuple[Tuple[str, ...], ...]:
     rust_version = _get_rust_version()
     cargo_target = os.getenv("CARGO_BUILD_TARGET", "")
     if rust_version[:2] >= (1, 62) and "wasm32-wasi" not in cargo_target:
-        # multiple-pymethods feature not supported before 1.62 or on WASI
+        # multiple-pymethods and experimental-stubs features not supported before 1.62 or on WASI
         return (
             ("--no-default-features",),
             (
                 "--no-default-features",
                 "--features=abi3",
             ),
-            ("--features=full gil-refs multiple-pymethods",),
-            ("--features=abi3 full gil-refs multiple-pymethods",),
+            ("--features=full gil-refs multiple-pymethods experimental-stubs",),
+            ("--features=abi3 full gil-refs multiple-pymethods experimental-stubs",),
         )
     else:
         return (
@@ -46,9 +46,32 @@ class Foo:
     })
 }
 
+fn bench_call_bound_method_0(b: &mut Bencher<'_>) {
+    Python::with_gil(|py| {
+        let module = test_module!(
+            py,
+            "
+class Foo:
+    def foo(self):
+        pass
+"
+        );
+
+        let foo_module = &module.getattr("Foo").unwrap().call0().unwrap();
+        let foo_method = &foo_module.get_bound_method("foo").unwrap();
+
+        b.iter(|| {
+            for _ in 0..1000 {
+                black_box(foo_method).call0().unwrap();
+            }
+        });
+    })
+}
+
 fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("call_0", bench_call_0);
     c.bench_function("call_method_0", bench_call_method_0);
+    c.bench_function("call_bound_method_0", bench_call_bound_method_0);
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -22,9 +22,23 @@ fn getattr_intern(b: &mut Bencher<'_>) {
     });
 }
 
+fn import_direct(b: &mut Bencher<'_>) {
+    Python::with_gil(|py| {
+        b.iter(|| py.import_bound(black_box("json")).unwrap());
+    });
+}
+
+fn import_cached(b: &mut Bencher<'_>) {
+    Python::with_gil(|py| {
+        b.iter(|| py.import_bound_cached(black_box("json")).unwrap());
+    });
+}
+
 fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("getattr_direct", getattr_direct);
     c.bench_function("getattr_intern", getattr_intern);
+    c.bench_function("import_direct", import_direct);
+    c.bench_function("import_cached", import_cached);
 }
 
 criterion_group!(benches, criterion_benchmark);
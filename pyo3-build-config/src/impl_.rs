@@ -1002,6 +1002,7 @@ pub enum BuildFlag {
     Py_REF_DEBUG,
     Py_TRACE_REFS,
     COUNT_ALLOCS,
+    Py_GIL_DISABLED,
     Other(String),
 }
 
@@ -1022,6 +1023,7 @@ impl FromStr for BuildFlag {
             "Py_REF_DEBUG" => Ok(BuildFlag::Py_REF_DEBUG),
             "Py_TRACE_REFS" => Ok(BuildFlag::Py_TRACE_REFS),
             "COUNT_ALLOCS" => Ok(BuildFlag::COUNT_ALLOCS),
+            "Py_GIL_DISABLED" => Ok(BuildFlag::Py_GIL_DISABLED),
             other => Ok(BuildFlag::Other(other.to_owned())),
         }
     }
@@ -1044,11 +1046,12 @@ impl FromStr for BuildFlag {
 pub struct BuildFlags(pub HashSet<BuildFlag>);
 
 impl BuildFlags {
-    const ALL: [BuildFlag; 4] = [
+    const ALL: [BuildFlag; 5] = [
         BuildFlag::Py_DEBUG,
         BuildFlag::Py_REF_DEBUG,
         BuildFlag::Py_TRACE_REFS,
         BuildFlag::COUNT_ALLOCS,
+        BuildFlag::Py_GIL_DISABLED,
     ];
 
     pub fn new() -> Self {
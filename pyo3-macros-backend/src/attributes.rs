@@ -1,39 +1,72 @@
 use proc_macro2::TokenStream;
 use quote::ToTokens;
 use syn::{
+    ext::IdentExt,
     parse::{Parse, ParseStream},
     punctuated::Punctuated,
     spanned::Spanned,
     token::Comma,
-    Attribute, Expr, ExprPath, Ident, LitStr, Path, Result, Token,
+    Attribute, Expr, ExprPath, Ident, LitBool, LitInt, LitStr, Path, Result, Token, Type,
 };
 
 pub mod kw {
+    syn::custom_keyword!(allow_threads);
     syn::custom_keyword!(annotation);
     syn::custom_keyword!(attribute);
+    syn::custom_keyword!(buffer);
+    syn::custom_keyword!(cache);
     syn::custom_keyword!(cancel_handle);
+    syn::custom_keyword!(clone);
+    syn::custom_keyword!(compare_with);
+    syn::custom_keyword!(context_manager);
     syn::custom_keyword!(dict);
+    syn::custom_keyword!(doc);
+    syn::custom_keyword!(eq);
+    syn::custom_keyword!(eq_hash);
     syn::custom_keyword!(extends);
     syn::custom_keyword!(freelist);
     syn::custom_keyword!(from_py_with);
     syn::custom_keyword!(frozen);
     syn::custom_keyword!(get);
     syn::custom_keyword!(get_all);
+    syn::custom_keyword!(getnewargs);
+    syn::custom_keyword!(gil_used);
+    syn::custom_keyword!(hash);
     syn::custom_keyword!(item);
+    syn::custom_keyword!(iter);
+    syn::custom_keyword!(kwargs_struct);
     syn::custom_keyword!(from_item_all);
+    syn::custom_keyword!(lazy);
     syn::custom_keyword!(mapping);
+    syn::custom_keyword!(match_args);
+    syn::custom_keyword!(max_args);
+    syn::custom_keyword!(min_args);
     syn::custom_keyword!(module);
     syn::custom_keyword!(name);
+    syn::custom_keyword!(on_first_init);
+    syn::custom_keyword!(ord);
     syn::custom_keyword!(pass_module);
     syn::custom_keyword!(rename_all);
+    syn::custom_keyword!(replace);
+    syn::custom_keyword!(repr);
     syn::custom_keyword!(sequence);
     syn::custom_keyword!(set);
     syn::custom_keyword!(set_all);
     syn::custom_keyword!(signature);
+    syn::custom_keyword!(signature_object);
+    syn::custom_keyword!(str);
+    syn::custom_keyword!(strict);
     syn::custom_keyword!(subclass);
+    syn::custom_keyword!(subscriptable);
+    syn::custom_keyword!(submodules);
+    syn::custom_keyword!(tag);
+    syn::custom_keyword!(tag_value);
     syn::custom_keyword!(text_signature);
+    syn::custom_keyword!(track_instances);
     syn::custom_keyword!(transparent);
+    syn::custom_keyword!(unpack);
     syn::custom_keyword!(unsendable);
+    syn::custom_keyword!(validate);
     syn::custom_keyword!(weakref);
 }
 
@@ -62,14 +95,17 @@ impl<T: ToTokens> ToTokens for LitStrValue<T> {
 }
 
 /// A helper type which parses a name via a literal string
+///
+/// Rust keywords are accepted (with or without an `r#` prefix), because they are valid Python
+/// names; the parsed identifier never has the `r#` prefix.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct NameLitStr(pub Ident);
 
 impl Parse for NameLitStr {
     fn parse(input: ParseStream<'_>) -> Result<Self> {
         let string_literal: LitStr = input.parse()?;
-        if let Ok(ident) = string_literal.parse() {
-            Ok(NameLitStr(ident))
+        if let Ok(ident) = string_literal.parse_with(Ident::parse_any) {
+            Ok(NameLitStr(ident.unraw()))
         } else {
             bail_spanned!(string_literal.span() => "expected a single identifier in double quotes")
         }
@@ -166,11 +202,135 @@ impl ToTokens for TextSignatureAttributeValue {
     }
 }
 
-pub type ExtendsAttribute = KeywordAttribute<kw::extends, Path>;
+/// The value of `extends`: either a single base class, or a parenthesized list of the base class
+/// followed by stateless mixin classes, e.g. `extends = (Base, MixinA, MixinB)`.
+#[derive(Clone, Debug)]
+pub struct ExtendsValue {
+    pub base: Path,
+    pub mixins: Vec<Path>,
+}
+
+impl Parse for ExtendsValue {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        if !input.peek(syn::token::Paren) {
+            return Ok(ExtendsValue {
+                base: input.parse()?,
+                mixins: Vec::new(),
+            });
+        }
+        let content;
+        let paren = syn::parenthesized!(content in input);
+        let mut bases = Punctuated::<Path, Comma>::parse_terminated(&content)?.into_iter();
+        match bases.next() {
+            Some(base) => Ok(ExtendsValue {
+                base,
+                mixins: bases.collect(),
+            }),
+            None => bail_spanned!(paren.span.join() => "expected at least one base class"),
+        }
+    }
+}
+
+impl ToTokens for ExtendsValue {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        if self.mixins.is_empty() {
+            self.base.to_tokens(tokens)
+        } else {
+            let base = &self.base;
+            let mixins = &self.mixins;
+            tokens.extend(quote::quote! { (#base, #(#mixins),*) })
+        }
+    }
+}
+
+/// `str` on its own, or `str = "format string"`.
+#[derive(Clone, Debug)]
+pub struct StrFormatterAttribute {
+    pub kw: kw::str,
+    pub value: Option<LitStr>,
+}
+
+impl Parse for StrFormatterAttribute {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let kw: kw::str = input.parse()?;
+        let value = if input.peek(Token![=]) {
+            let _: Token![=] = input.parse()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        Ok(StrFormatterAttribute { kw, value })
+    }
+}
+
+impl ToTokens for StrFormatterAttribute {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.kw.to_tokens(tokens);
+        if let Some(value) = &self.value {
+            Token![=](self.kw.span).to_tokens(tokens);
+            value.to_tokens(tokens);
+        }
+    }
+}
+
+/// `#[pyo3(set)]` on a field, optionally with a validator as in `#[pyo3(set = validate_age)]`.
+pub struct SetterAttribute {
+    pub kw: kw::set,
+    pub validator: Option<ExprPath>,
+}
+
+impl Parse for SetterAttribute {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let kw: kw::set = input.parse()?;
+        let validator = if input.peek(Token![=]) {
+            let _: Token![=] = input.parse()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        Ok(SetterAttribute { kw, validator })
+    }
+}
+
+/// `submodules(a, b::c)`: the `#[pymodule]` functions added as submodules of a `#[pymodule]`.
+#[derive(Clone, Debug)]
+pub struct SubmodulesAttribute {
+    pub kw: kw::submodules,
+    pub modules: Punctuated<Path, Comma>,
+}
+
+impl Parse for SubmodulesAttribute {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let kw = input.parse()?;
+        let content;
+        syn::parenthesized!(content in input);
+        Ok(SubmodulesAttribute {
+            kw,
+            modules: Punctuated::parse_terminated(&content)?,
+        })
+    }
+}
+
+impl ToTokens for SubmodulesAttribute {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.kw.to_tokens(tokens);
+        let modules = &self.modules;
+        tokens.extend(quote::quote! { (#modules) });
+    }
+}
+
+pub type CompareWithAttribute = KeywordAttribute<kw::compare_with, Type>;
+pub type DocAttribute = KeywordAttribute<kw::doc, Expr>;
+pub type ExtendsAttribute = KeywordAttribute<kw::extends, ExtendsValue>;
 pub type FreelistAttribute = KeywordAttribute<kw::freelist, Box<Expr>>;
+pub type GILUsedAttribute = KeywordAttribute<kw::gil_used, LitBool>;
+pub type MaxArgsAttribute = KeywordAttribute<kw::max_args, LitInt>;
+pub type MinArgsAttribute = KeywordAttribute<kw::min_args, LitInt>;
 pub type ModuleAttribute = KeywordAttribute<kw::module, LitStr>;
 pub type NameAttribute = KeywordAttribute<kw::name, NameLitStr>;
+pub type OnFirstInitAttribute = KeywordAttribute<kw::on_first_init, ExprPath>;
 pub type RenameAllAttribute = KeywordAttribute<kw::rename_all, RenamingRuleLitStr>;
+pub type ValidateAttribute = KeywordAttribute<kw::validate, ExprPath>;
 pub type TextSignatureAttribute = KeywordAttribute<kw::text_signature, TextSignatureAttributeValue>;
 
 impl<K: Parse + std::fmt::Debug, V: Parse> Parse for KeywordAttribute<K, V> {
@@ -0,0 +1,125 @@
+//! Implementation of `pyo3::extract_args!`, which extracts call arguments with the signature
+//! grammar of `#[pyfunction]`.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    ext::IdentExt,
+    parse::{Parse, ParseStream},
+    Result, Token,
+};
+
+use crate::{
+    attributes::take_pyo3_options,
+    deprecations::Deprecations,
+    method::{get_return_info, CallingConvention, FnArg, FnSpec, FnType},
+    params::{impl_arg_params, Holders},
+    pyfunction::{FunctionSignature, PyFunctionOptions},
+    pymethod::check_generic,
+    utils::Ctx,
+};
+
+/// The input of `extract_args!(args, kwargs, fn name(a: A, b: B))`.
+pub struct ExtractArgs {
+    args: syn::Expr,
+    kwargs: syn::Expr,
+    attrs: Vec<syn::Attribute>,
+    sig: syn::Signature,
+}
+
+impl Parse for ExtractArgs {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let args = input.parse()?;
+        let _: Token![,] = input.parse()?;
+        let kwargs = input.parse()?;
+        let _: Token![,] = input.parse()?;
+        let attrs = input.call(syn::Attribute::parse_outer)?;
+        let sig = input.parse()?;
+        let _: Option<Token![;]> = input.parse()?;
+        Ok(Self {
+            args,
+            kwargs,
+            attrs,
+            sig,
+        })
+    }
+}
+
+/// Generates an expression extracting the arguments of `sig` from a `&Bound<'py, PyTuple>` and an
+/// `Option<&Bound<'py, PyDict>>`, evaluating to a `PyResult` of a tuple of the extracted values.
+pub fn build_extract_args(input: ExtractArgs) -> Result<TokenStream> {
+    let ExtractArgs {
+        args,
+        kwargs,
+        mut attrs,
+        mut sig,
+    } = input;
+    check_generic(&sig)?;
+    if let Some(receiver) = sig.receiver() {
+        bail_spanned!(receiver.self_token.span => "`extract_args!` does not take a receiver");
+    }
+
+    let mut options = PyFunctionOptions::default();
+    options.add_attributes(take_pyo3_options(&mut attrs)?)?;
+    let PyFunctionOptions {
+        signature,
+        min_args,
+        max_args,
+        krate,
+        ..
+    } = options;
+    let ctx = &Ctx::new(&krate);
+    let Ctx { pyo3_path } = ctx;
+
+    let arguments = sig
+        .inputs
+        .iter_mut()
+        .map(FnArg::parse)
+        .collect::<Result<Vec<_>>>()?;
+    let mut signature = if let Some(signature) = signature {
+        FunctionSignature::from_arguments_and_attribute(arguments, signature)?
+    } else {
+        FunctionSignature::from_arguments(arguments)?
+    };
+    signature.set_varargs_arity(min_args, max_args)?;
+
+    let spec = FnSpec {
+        tp: FnType::FnStatic,
+        name: &sig.ident,
+        convention: CallingConvention::Varargs,
+        python_name: sig.ident.unraw(),
+        signature,
+        output: get_return_info(&sig.output),
+        text_signature: None,
+        asyncness: None,
+        unsafety: None,
+        allow_threads: None,
+        unpack: None,
+        deprecations: Deprecations::new(ctx),
+    };
+
+    let mut holders = Holders::new();
+    let (arg_convert, values) = impl_arg_params(&spec, None, false, &mut holders, ctx)?;
+    let init_holders = holders.init_holders(ctx);
+    let check_gil_refs = holders.check_gil_refs();
+    let types: Vec<_> = spec.signature.arguments.iter().map(|arg| arg.ty).collect();
+
+    Ok(quote! {{
+        fn __pyo3_extract_args<'py>(
+            args: &#pyo3_path::Bound<'py, #pyo3_path::types::PyTuple>,
+            kwargs: ::std::option::Option<&#pyo3_path::Bound<'py, #pyo3_path::types::PyDict>>,
+        ) -> #pyo3_path::PyResult<(#(#types,)*)> {
+            let py = args.py();
+            let _args = args.as_ptr();
+            let _kwargs = kwargs.map_or(::std::ptr::null_mut(), |kwargs| kwargs.as_ptr());
+            unsafe {
+                #arg_convert
+                #init_holders
+                let result: (#(#types,)*) = (#(#values,)*);
+                #check_gil_refs
+                ::std::result::Result::Ok(result)
+            }
+        }
+        __pyo3_extract_args(#args, #kwargs)
+    }})
+}
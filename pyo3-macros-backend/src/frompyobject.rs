@@ -1,5 +1,5 @@
 use crate::attributes::{self, get_pyo3_options, CrateAttribute, FromPyWithAttribute};
-use crate::utils::Ctx;
+use crate::utils::{option_type_argument, Ctx};
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote, quote_spanned};
 use syn::{
@@ -15,6 +15,8 @@ use syn::{
 struct Enum<'a> {
     enum_ident: &'a Ident,
     variants: Vec<Container<'a>>,
+    /// The `#[pyo3(tag = "...")]` key and the `tag_value` of each variant, in order.
+    tag: Option<(LitStr, Vec<LitStr>)>,
 }
 
 impl<'a> Enum<'a> {
@@ -22,16 +24,37 @@ impl<'a> Enum<'a> {
     ///
     /// `data_enum` is the `syn` representation of the input enum, `ident` is the
     /// `Identifier` of the enum.
-    fn new(data_enum: &'a DataEnum, ident: &'a Ident) -> Result<Self> {
+    fn new(data_enum: &'a DataEnum, ident: &'a Ident, tag: Option<LitStr>) -> Result<Self> {
         ensure_spanned!(
             !data_enum.variants.is_empty(),
             ident.span() => "cannot derive FromPyObject for empty enum"
         );
+        let mut tag_values: Vec<LitStr> = Vec::new();
         let variants = data_enum
             .variants
             .iter()
             .map(|variant| {
-                let attrs = ContainerOptions::from_attrs(&variant.attrs)?;
+                let mut attrs = ContainerOptions::from_attrs(&variant.attrs)?;
+                ensure_spanned!(
+                    attrs.tag.is_none(),
+                    attrs.tag.unwrap().span() => "`tag` is only supported at top level for enums, use `tag_value` on variants"
+                );
+                match (&tag, attrs.tag_value.take()) {
+                    (Some(_), Some(tag_value)) => {
+                        ensure_spanned!(
+                            tag_values.iter().all(|other| other.value() != tag_value.value()),
+                            tag_value.span() => "`tag_value` must be unique among the variants"
+                        );
+                        tag_values.push(tag_value);
+                    }
+                    (Some(_), None) => bail_spanned!(
+                        variant.span() => "every variant of an enum with `tag` needs a `tag_value`"
+                    ),
+                    (None, Some(tag_value)) => bail_spanned!(
+                        tag_value.span() => "`tag_value` requires the enum to have a `#[pyo3(tag = \"...\")]`"
+                    ),
+                    (None, None) => {}
+                }
                 let var_ident = &variant.ident;
                 Container::new(&variant.fields, parse_quote!(#ident::#var_ident), attrs)
             })
@@ -40,11 +63,51 @@ impl<'a> Enum<'a> {
         Ok(Enum {
             enum_ident: ident,
             variants,
+            tag: tag.map(|tag| (tag, tag_values)),
         })
     }
 
+    /// Build derivation body for enums with `#[pyo3(tag = "...")]`, which extract only the
+    /// variant matching the tag.
+    fn build_tagged(
+        &self,
+        tag: &LitStr,
+        tag_values: &[LitStr],
+        ctx: &Ctx,
+    ) -> (TokenStream, TokenStream) {
+        let Ctx { pyo3_path } = ctx;
+        let ty_name = self.enum_ident.to_string();
+        let mut arms = Vec::new();
+        let mut deprecations = TokenStream::new();
+        for (var, tag_value) in self.variants.iter().zip(tag_values) {
+            let (struct_derive, dep) = var.build(ctx);
+            deprecations.extend(dep);
+            arms.push(quote!(#tag_value => { #struct_derive }));
+        }
+        (
+            quote!(
+                let tag = #pyo3_path::impl_::frompyobject::extract_enum_tag(obj, #ty_name, #tag)?;
+                match tag.as_str() {
+                    #(#arms,)*
+                    other => ::std::result::Result::Err(
+                        #pyo3_path::impl_::frompyobject::unknown_enum_tag(
+                            #ty_name,
+                            #tag,
+                            other,
+                            &[#(#tag_values),*],
+                        )
+                    ),
+                }
+            ),
+            deprecations,
+        )
+    }
+
     /// Build derivation body for enums.
     fn build(&self, ctx: &Ctx) -> (TokenStream, TokenStream) {
+        if let Some((tag, tag_values)) = &self.tag {
+            return self.build_tagged(tag, tag_values, ctx);
+        }
         let Ctx { pyo3_path } = ctx;
         let mut var_extracts = Vec::new();
         let mut variant_names = Vec::new();
@@ -92,6 +155,7 @@ impl<'a> Enum<'a> {
 
 struct NamedStructField<'a> {
     ident: &'a syn::Ident,
+    ty: &'a syn::Type,
     getter: Option<FieldGetter>,
     from_py_with: Option<FromPyWithAttribute>,
 }
@@ -195,6 +259,7 @@ impl<'a> Container<'a> {
 
                         Ok(NamedStructField {
                             ident,
+                            ty: &field.ty,
                             getter: attrs.getter,
                             from_py_with: attrs.from_py_with,
                         })
@@ -243,6 +308,26 @@ impl<'a> Container<'a> {
         value
     }
 
+    /// The `(key, required)` pairs of a struct whose fields are all extracted by string key, as
+    /// used by `#[pyo3(kwargs_struct)]` arguments.
+    fn kwargs_struct_fields(&self) -> Option<Vec<(String, bool)>> {
+        let struct_fields = match &self.ty {
+            ContainerType::Struct(struct_fields) => struct_fields,
+            _ => return None,
+        };
+        struct_fields
+            .iter()
+            .map(|field| {
+                let key = match field.getter.as_ref()? {
+                    FieldGetter::GetItem(None) => field.ident.to_string(),
+                    FieldGetter::GetItem(Some(syn::Lit::Str(key))) => key.value(),
+                    _ => return None,
+                };
+                Some((key, option_type_argument(field.ty).is_none()))
+            })
+            .collect()
+    }
+
     /// Build derivation body for a struct.
     fn build(&self, ctx: &Ctx) -> (TokenStream, TokenStream) {
         match &self.ty {
@@ -455,10 +540,14 @@ struct ContainerOptions {
     annotation: Option<syn::LitStr>,
     /// Change the path for the pyo3 crate
     krate: Option<CrateAttribute>,
+    /// Dispatch on the value of this item instead of trying each enum variant in turn.
+    tag: Option<LitStr>,
+    /// The value of the enum's `tag` selecting this variant.
+    tag_value: Option<LitStr>,
 }
 
 /// Attributes for deriving FromPyObject scoped on containers.
-enum ContainerPyO3Attribute {
+pub(crate) enum ContainerPyO3Attribute {
     /// Treat the Container as a Wrapper, directly extract its fields from the input object.
     Transparent(attributes::kw::transparent),
     /// Force every field to be extracted from item of source Python object.
@@ -467,6 +556,10 @@ enum ContainerPyO3Attribute {
     ErrorAnnotation(LitStr),
     /// Change the path for the pyo3 crate
     Crate(CrateAttribute),
+    /// Dispatch on the value of this item instead of trying each enum variant in turn.
+    Tag(LitStr),
+    /// The value of the enum's `tag` selecting this variant.
+    TagValue(LitStr),
 }
 
 impl Parse for ContainerPyO3Attribute {
@@ -484,6 +577,14 @@ impl Parse for ContainerPyO3Attribute {
             input.parse().map(ContainerPyO3Attribute::ErrorAnnotation)
         } else if lookahead.peek(Token![crate]) {
             input.parse().map(ContainerPyO3Attribute::Crate)
+        } else if lookahead.peek(attributes::kw::tag) {
+            let _: attributes::kw::tag = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            input.parse().map(ContainerPyO3Attribute::Tag)
+        } else if lookahead.peek(attributes::kw::tag_value) {
+            let _: attributes::kw::tag_value = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            input.parse().map(ContainerPyO3Attribute::TagValue)
         } else {
             Err(lookahead.error())
         }
@@ -526,6 +627,20 @@ impl ContainerOptions {
                             );
                             options.krate = Some(path);
                         }
+                        ContainerPyO3Attribute::Tag(lit_str) => {
+                            ensure_spanned!(
+                                options.tag.is_none(),
+                                lit_str.span() => "`tag` may only be provided once"
+                            );
+                            options.tag = Some(lit_str);
+                        }
+                        ContainerPyO3Attribute::TagValue(lit_str) => {
+                            ensure_spanned!(
+                                options.tag_value.is_none(),
+                                lit_str.span() => "`tag_value` may only be provided once"
+                            );
+                            options.tag_value = Some(lit_str);
+                        }
                     }
                 }
             }
@@ -673,21 +788,40 @@ pub fn build_derive_from_pyobject(tokens: &DeriveInput) -> Result<TokenStream> {
     let ctx = &Ctx::new(&options.krate);
     let Ctx { pyo3_path } = &ctx;
 
+    let mut kwargs_struct_fields = None;
     let (derives, from_py_with_deprecations) = match &tokens.data {
         syn::Data::Enum(en) => {
             if options.transparent || options.annotation.is_some() {
                 bail_spanned!(tokens.span() => "`transparent` or `annotation` is not supported \
                                                 at top level for enums");
             }
-            let en = Enum::new(en, &tokens.ident)?;
+            if let Some(lit_str) = &options.tag_value {
+                bail_spanned!(lit_str.span() => "`tag_value` is only supported on enum variants");
+            }
+            let en = Enum::new(en, &tokens.ident, options.tag)?;
             en.build(ctx)
         }
         syn::Data::Struct(st) => {
             if let Some(lit_str) = &options.annotation {
                 bail_spanned!(lit_str.span() => "`annotation` is unsupported for structs");
             }
+            if let Some(lit_str) = options.tag.as_ref().or(options.tag_value.as_ref()) {
+                bail_spanned!(lit_str.span() => "`tag` and `tag_value` are only supported for enums");
+            }
             let ident = &tokens.ident;
             let st = Container::new(&st.fields, parse_quote!(#ident), options)?;
+            if let Some(fields) = st.kwargs_struct_fields() {
+                let (impl_generics, ty_generics, where_clause) = tokens.generics.split_for_impl();
+                let fields = fields
+                    .iter()
+                    .map(|(key, required)| quote!((#key, #required)));
+                kwargs_struct_fields = Some(quote! {
+                    #[automatically_derived]
+                    impl #impl_generics #pyo3_path::impl_::extract_argument::KwargsStructFields for #ident #ty_generics #where_clause {
+                        const FIELDS: &'static [(&'static str, bool)] = &[#(#fields),*];
+                    }
+                });
+            }
             st.build(ctx)
         }
         syn::Data::Union(_) => bail_spanned!(
@@ -705,5 +839,6 @@ pub fn build_derive_from_pyobject(tokens: &DeriveInput) -> Result<TokenStream> {
         }
 
         #from_py_with_deprecations
+        #kwargs_struct_fields
     ))
 }
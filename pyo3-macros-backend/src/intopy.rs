@@ -0,0 +1,157 @@
+//! Implementation of `#[derive(IntoPy)]`.
+
+use crate::attributes::{get_pyo3_options, CrateAttribute};
+use crate::frompyobject::ContainerPyO3Attribute;
+use crate::utils::Ctx;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_quote, spanned::Spanned, Attribute, DeriveInput, Fields, Result};
+
+/// Reads `#[pyo3(crate = "...")]`, the only option of `#[derive(IntoPy)]`.
+///
+/// The other options of `#[derive(FromPyObject)]` describe how to read a Python object, and have
+/// no counterpart in the conversion.
+fn crate_option(attrs: &[Attribute]) -> Result<Option<CrateAttribute>> {
+    let mut krate = None;
+    for attr in attrs {
+        if let Some(pyo3_attrs) = get_pyo3_options(attr)? {
+            for pyo3_attr in pyo3_attrs {
+                let (span, option) = match pyo3_attr {
+                    ContainerPyO3Attribute::Crate(path) => {
+                        ensure_spanned!(
+                            krate.is_none(),
+                            path.span() => "`crate` may only be provided once"
+                        );
+                        krate = Some(path);
+                        continue;
+                    }
+                    ContainerPyO3Attribute::Transparent(kw) => (kw.span(), "transparent"),
+                    ContainerPyO3Attribute::ItemAll(kw) => (kw.span(), "from_item_all"),
+                    ContainerPyO3Attribute::ErrorAnnotation(lit) => (lit.span(), "annotation"),
+                    ContainerPyO3Attribute::Tag(lit) => (lit.span(), "tag"),
+                    ContainerPyO3Attribute::TagValue(lit) => (lit.span(), "tag_value"),
+                };
+                bail_spanned!(span => format!("`{}` is not supported by `#[derive(IntoPy)]`", option));
+            }
+        }
+    }
+    Ok(krate)
+}
+
+/// Rejects `#[pyo3(...)]` attributes on enum variants and fields, e.g. `item` or `attribute`,
+/// since their conversion is fixed by their shape.
+fn ensure_no_pyo3_attributes(attrs: &[Attribute], on: &str) -> Result<()> {
+    if let Some(attr) = attrs.iter().find(|attr| attr.path().is_ident("pyo3")) {
+        let options = attr.meta.require_list()?;
+        bail_spanned!(
+            options.tokens.span() => format!("`#[pyo3]` attributes on {} are not supported by `#[derive(IntoPy)]`", on)
+        );
+    }
+    Ok(())
+}
+
+/// Builds the conversion of the fields of a struct or an enum variant matched by `path`.
+///
+/// Newtypes convert to their field, tuples to a `tuple`, named fields to a `dict` keyed by the
+/// field names and unit structs or variants to `None`.
+fn build_arm(path: TokenStream, fields: &Fields, ctx: &Ctx) -> Result<TokenStream> {
+    let Ctx { pyo3_path } = ctx;
+    let into_py = quote!(#pyo3_path::IntoPy::<#pyo3_path::PyObject>::into_py);
+    for field in fields {
+        ensure_no_pyo3_attributes(&field.attrs, "fields")?;
+    }
+    Ok(match fields {
+        Fields::Unnamed(unnamed) if unnamed.unnamed.len() == 1 => quote! {
+            #path(field) => #into_py(field, py)
+        },
+        Fields::Unnamed(unnamed) => {
+            let idents: Vec<_> = (0..unnamed.unnamed.len())
+                .map(|i| format_ident!("field{}", i))
+                .collect();
+            quote! {
+                #path(#(#idents),*) => #pyo3_path::types::PyTuple::new_bound(
+                    py,
+                    [#(#into_py(#idents, py)),*],
+                )
+                .into_any()
+                .unbind()
+            }
+        }
+        Fields::Named(named) => {
+            let idents: Vec<_> = named
+                .named
+                .iter()
+                .map(|field| field.ident.as_ref().unwrap())
+                .collect();
+            let keys = idents.iter().map(|ident| ident.to_string());
+            quote! {
+                #path { #(#idents),* } => {
+                    let dict = #pyo3_path::types::PyDict::new_bound(py);
+                    #(
+                        #pyo3_path::types::PyDictMethods::set_item(&dict, #keys, #into_py(#idents, py))
+                            .expect("failed to set item on dict");
+                    )*
+                    dict.into_any().unbind()
+                }
+            }
+        }
+        Fields::Unit => quote! {
+            #path => py.None()
+        },
+    })
+}
+
+/// Derive `IntoPy<PyObject>` for enums and structs.
+///
+///   * Each enum variant is converted on its own, so variants may convert to different Python types.
+///   * Fields must implement `IntoPy<PyObject>`.
+///   * Derivation for structs with generic fields like `struct<T> Foo(T)`
+///     adds `T: IntoPy<PyObject>` on the derived implementation.
+pub fn build_derive_into_py(tokens: &DeriveInput) -> Result<TokenStream> {
+    let krate = crate_option(&tokens.attrs)?;
+    let ctx = &Ctx::new(&krate);
+    let Ctx { pyo3_path } = ctx;
+
+    let ident = &tokens.ident;
+    let arms = match &tokens.data {
+        syn::Data::Enum(en) => {
+            ensure_spanned!(
+                !en.variants.is_empty(),
+                ident.span() => "cannot derive IntoPy for empty enum"
+            );
+            en.variants
+                .iter()
+                .map(|variant| {
+                    ensure_no_pyo3_attributes(&variant.attrs, "enum variants")?;
+                    let var_ident = &variant.ident;
+                    build_arm(quote!(#ident::#var_ident), &variant.fields, ctx)
+                })
+                .collect::<Result<Vec<_>>>()?
+        }
+        syn::Data::Struct(st) => vec![build_arm(quote!(#ident), &st.fields, ctx)?],
+        syn::Data::Union(_) => bail_spanned!(
+            tokens.span() => "#[derive(IntoPy)] is not supported for unions"
+        ),
+    };
+
+    let mut generics = tokens.generics.clone();
+    let where_clause = generics.make_where_clause();
+    for param in tokens.generics.type_params() {
+        let gen_ident = &param.ident;
+        where_clause
+            .predicates
+            .push(parse_quote!(#gen_ident: #pyo3_path::IntoPy<#pyo3_path::PyObject>));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote!(
+        #[automatically_derived]
+        impl #impl_generics #pyo3_path::IntoPy<#pyo3_path::PyObject> for #ident #ty_generics #where_clause {
+            fn into_py(self, py: #pyo3_path::Python<'_>) -> #pyo3_path::PyObject {
+                match self {
+                    #(#arms,)*
+                }
+            }
+        }
+    ))
+}
@@ -10,7 +10,9 @@ mod utils;
 
 mod attributes;
 mod deprecations;
+mod extract_args;
 mod frompyobject;
+mod intopy;
 mod konst;
 mod method;
 mod module;
@@ -20,10 +22,17 @@ mod pyfunction;
 mod pyimpl;
 mod pymethod;
 mod quotes;
+mod shared_signature;
+mod stub;
 
+pub use extract_args::{build_extract_args, ExtractArgs};
 pub use frompyobject::build_derive_from_pyobject;
+pub use intopy::build_derive_into_py;
 pub use module::{pymodule_function_impl, pymodule_module_impl, PyModuleOptions};
 pub use pyclass::{build_py_class, build_py_enum, PyClassArgs};
 pub use pyfunction::{build_py_function, PyFunctionOptions};
 pub use pyimpl::{build_py_methods, PyClassMethodsType};
+pub use shared_signature::{
+    build_resolve_shared_signature, defer_to_shared_signature, ResolveSharedSignature,
+};
 pub use utils::get_doc;
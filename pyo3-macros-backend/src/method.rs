@@ -1,19 +1,19 @@
 use std::fmt::Display;
 
 use proc_macro2::{Span, TokenStream};
-use quote::{quote, quote_spanned, ToTokens};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
 use syn::{ext::IdentExt, spanned::Spanned, Ident, Result};
 
 use crate::utils::Ctx;
 use crate::{
-    attributes::{TextSignatureAttribute, TextSignatureAttributeValue},
+    attributes::{kw, TextSignatureAttribute, TextSignatureAttributeValue},
     deprecations::{Deprecation, Deprecations},
     params::{impl_arg_params, Holders},
     pyfunction::{
         FunctionSignature, PyFunctionArgPyO3Attributes, PyFunctionOptions, SignatureAttribute,
     },
     quotes,
-    utils::{self, is_abi3, PythonDoc},
+    utils::{self, is_abi3, unwrap_ty_group, PythonDoc},
 };
 
 #[derive(Clone, Debug)]
@@ -48,6 +48,7 @@ impl<'a> FnArg<'a> {
                 };
 
                 let is_cancel_handle = arg_attrs.cancel_handle.is_some();
+                let is_kwargs = arg_attrs.kwargs_struct.is_some();
 
                 Ok(FnArg {
                     name: ident,
@@ -57,7 +58,7 @@ impl<'a> FnArg<'a> {
                     py: utils::is_python(&cap.ty),
                     attrs: arg_attrs,
                     is_varargs: false,
-                    is_kwargs: false,
+                    is_kwargs,
                     is_cancel_handle,
                 })
             }
@@ -88,7 +89,10 @@ pub enum FnType {
     FnClass(Span),
     FnStatic,
     FnModule(Span),
-    ClassAttribute,
+    /// `lazy` attributes are computed on first access rather than when the type is created.
+    ClassAttribute {
+        lazy: bool,
+    },
 }
 
 impl FnType {
@@ -100,7 +104,7 @@ impl FnType {
             | FnType::FnClass(_)
             | FnType::FnNewClass(_)
             | FnType::FnModule(_) => true,
-            FnType::FnNew | FnType::FnStatic | FnType::ClassAttribute => false,
+            FnType::FnNew | FnType::FnStatic | FnType::ClassAttribute { .. } => false,
         }
     }
 
@@ -123,7 +127,7 @@ impl FnType {
                 syn::Token![,](Span::call_site()).to_tokens(&mut receiver);
                 receiver
             }
-            FnType::FnNew | FnType::FnStatic | FnType::ClassAttribute => {
+            FnType::FnNew | FnType::FnStatic | FnType::ClassAttribute { .. } => {
                 quote!()
             }
             FnType::FnClass(span) | FnType::FnNewClass(span) => {
@@ -142,13 +146,16 @@ impl FnType {
                 let py = syn::Ident::new("py", Span::call_site());
                 let slf: Ident = syn::Ident::new("_slf_ref", Span::call_site());
                 let pyo3_path = pyo3_path.to_tokens_spanned(*span);
-                quote_spanned! { *span =>
-                    #[allow(clippy::useless_conversion)]
-                    ::std::convert::Into::into(
-                        #pyo3_path::impl_::pymethods::BoundRef::ref_from_ptr(#py, &*(#slf as *const _ as *const *mut _))
-                            .downcast_unchecked::<#pyo3_path::types::PyModule>()
-                    ),
-                }
+                let module = error_mode.handle_error(
+                    quote_spanned! { *span =>
+                        #pyo3_path::impl_::pymethods::extract_module_argument(
+                            #pyo3_path::impl_::pymethods::BoundRef::ref_from_ptr(#py, &*(#slf as *const _ as *const *mut _))
+                                .downcast_unchecked::<#pyo3_path::types::PyModule>()
+                        )
+                    },
+                    ctx,
+                );
+                quote! { #module, }
             }
         }
     }
@@ -274,6 +281,10 @@ pub struct FnSpec<'a> {
     pub text_signature: Option<TextSignatureAttribute>,
     pub asyncness: Option<syn::Token![async]>,
     pub unsafety: Option<syn::Token![unsafe]>,
+    /// `#[pyo3(allow_threads)]`: the function body runs with the GIL released.
+    pub allow_threads: Option<kw::allow_threads>,
+    /// `#[pyo3(unpack)]`: the arguments are passed as a single tuple or dict.
+    pub unpack: Option<kw::unpack>,
     pub deprecations: Deprecations<'a>,
 }
 
@@ -284,6 +295,47 @@ pub fn get_return_info(output: &syn::ReturnType) -> syn::Type {
     }
 }
 
+/// How a function returning `impl Iterator` should have its return value wrapped.
+enum IteratorReturn {
+    /// `impl Iterator<Item = T>`
+    Bare,
+    /// `PyResult<impl Iterator<Item = T>>` or `Result<impl Iterator<Item = T>, E>`
+    InResult,
+}
+
+fn iterator_return(output: &syn::Type) -> Option<IteratorReturn> {
+    fn is_impl_iterator(ty: &syn::Type) -> bool {
+        match unwrap_ty_group(ty) {
+            syn::Type::ImplTrait(impl_trait) => impl_trait.bounds.iter().any(|bound| match bound {
+                syn::TypeParamBound::Trait(bound) => matches!(
+                    bound.path.segments.last(),
+                    Some(segment) if segment.ident == "Iterator"
+                ),
+                _ => false,
+            }),
+            _ => false,
+        }
+    }
+
+    if is_impl_iterator(output) {
+        return Some(IteratorReturn::Bare);
+    }
+    if let syn::Type::Path(syn::TypePath { qself: None, path }) = unwrap_ty_group(output) {
+        let segment = path.segments.last()?;
+        if segment.ident != "PyResult" && segment.ident != "Result" {
+            return None;
+        }
+        if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+            if let Some(syn::GenericArgument::Type(ty)) = args.args.first() {
+                if is_impl_iterator(ty) {
+                    return Some(IteratorReturn::InResult);
+                }
+            }
+        }
+    }
+    None
+}
+
 pub fn parse_method_receiver(arg: &syn::FnArg) -> Result<SelfType> {
     match arg {
         syn::FnArg::Receiver(
@@ -319,9 +371,20 @@ impl<'a> FnSpec<'a> {
             text_signature,
             name,
             signature,
+            min_args,
+            max_args,
+            allow_threads,
+            unpack,
             ..
         } = options;
 
+        if let Some(allow_threads) = allow_threads {
+            bail_spanned!(allow_threads.span() => "`allow_threads` is only supported on `#[pyfunction]`");
+        }
+        if let Some(unpack) = unpack {
+            bail_spanned!(unpack.span() => "`unpack` is only supported on `#[pyfunction]`");
+        }
+
         let mut python_name = name.map(|name| name.value.0);
         let mut deprecations = Deprecations::new(ctx);
 
@@ -343,11 +406,12 @@ impl<'a> FnSpec<'a> {
             .map(FnArg::parse)
             .collect::<Result<_>>()?;
 
-        let signature = if let Some(signature) = signature {
+        let mut signature = if let Some(signature) = signature {
             FunctionSignature::from_arguments_and_attribute(arguments, signature)?
         } else {
             FunctionSignature::from_arguments(arguments)?
         };
+        signature.set_varargs_arity(min_args, max_args)?;
 
         let convention = if matches!(fn_type, FnType::FnNew | FnType::FnNewClass(_)) {
             CallingConvention::TpNew
@@ -365,6 +429,8 @@ impl<'a> FnSpec<'a> {
             text_signature,
             asyncness: sig.asyncness,
             unsafety: sig.unsafety,
+            allow_threads: None,
+            unpack: None,
             deprecations,
         })
     }
@@ -411,7 +477,9 @@ impl<'a> FnSpec<'a> {
                 "static method needs #[staticmethod] attribute",
             )?),
             [MethodTypeAttribute::StaticMethod(_)] => FnType::FnStatic,
-            [MethodTypeAttribute::ClassAttribute(_)] => FnType::ClassAttribute,
+            [MethodTypeAttribute::ClassAttribute(_, lazy)] => {
+                FnType::ClassAttribute { lazy: *lazy }
+            }
             [MethodTypeAttribute::New(_)] => {
                 set_name_to_new()?;
                 FnType::FnNew
@@ -514,6 +582,22 @@ impl<'a> FnSpec<'a> {
             );
         }
 
+        if let Some(allow_threads) = &self.allow_threads {
+            ensure_spanned!(
+                self.asyncness.is_none(),
+                allow_threads.span() => "`allow_threads` cannot be used with `async fn`"
+            );
+            ensure_spanned!(
+                matches!(self.tp, FnType::FnStatic),
+                allow_threads.span() => "`allow_threads` cannot be used with `pass_module`"
+            );
+            if let Some(py_arg) = self.signature.arguments.iter().find(|arg| arg.py) {
+                bail_spanned!(
+                    py_arg.ty.span() => "`Python` arguments cannot be used with `allow_threads`, because the function is called without holding the GIL"
+                );
+            }
+        }
+
         let rust_call = |args: Vec<TokenStream>, holders: &mut Holders| {
             let mut self_arg = || self.tp.self_arg(cls, ExtractErrorMode::Raise, holders, ctx);
 
@@ -528,17 +612,26 @@ impl<'a> FnSpec<'a> {
                     Some(cls) => quote!(Some(<#cls as #pyo3_path::PyTypeInfo>::NAME)),
                     None => quote!(None),
                 };
+                // The arguments are extracted before the `async move` block, so that only the
+                // extracted (`Send`) values are moved into the future.
+                let arg_names: Vec<_> = (0..args.len())
+                    .map(|i| format_ident!("__async_arg{}", i))
+                    .collect();
                 let future = match self.tp {
                     FnType::Fn(SelfType::Receiver { mutable: false, .. }) => {
                         quote! {{
                             let __guard = #pyo3_path::impl_::coroutine::RefGuard::<#cls>::new(&#pyo3_path::impl_::pymethods::BoundRef::ref_from_ptr(py, &_slf))?;
-                            async move { function(&__guard, #(#args),*).await }
+                            match (#(#args,)*) {
+                                (#(#arg_names,)*) => async move { function(&__guard, #(#arg_names),*).await },
+                            }
                         }}
                     }
                     FnType::Fn(SelfType::Receiver { mutable: true, .. }) => {
                         quote! {{
                             let mut __guard = #pyo3_path::impl_::coroutine::RefMutGuard::<#cls>::new(&#pyo3_path::impl_::pymethods::BoundRef::ref_from_ptr(py, &_slf))?;
-                            async move { function(&mut __guard, #(#args),*).await }
+                            match (#(#args,)*) {
+                                (#(#arg_names,)*) => async move { function(&mut __guard, #(#arg_names),*).await },
+                            }
                         }}
                     }
                     _ => {
@@ -557,13 +650,14 @@ impl<'a> FnSpec<'a> {
                         }
                     }
                 };
+                let ok_wrap = quotes::ok_wrap(quote!(future.await), ctx);
                 let mut call = quote! {{
                     let future = #future;
                     #pyo3_path::impl_::coroutine::new_coroutine(
                         #pyo3_path::intern!(py, stringify!(#python_name)),
                         #qualname_prefix,
                         #throw_callback,
-                        async move { #pyo3_path::impl_::wrap::OkWrap::wrap(future.await) },
+                        async move { #ok_wrap },
                     )
                 }};
                 if cancel_handle.is_some() {
@@ -574,6 +668,18 @@ impl<'a> FnSpec<'a> {
                     }};
                 }
                 call
+            } else if self.allow_threads.is_some() {
+                // Arguments are extracted while the GIL is still held; only the call itself
+                // happens inside `allow_threads`, which requires the arguments to be `Ungil`.
+                let arg_names: Vec<_> = (0..args.len())
+                    .map(|i| format_ident!("__allow_threads_arg{}", i))
+                    .collect();
+                // A `match` keeps the temporaries of the extraction expressions alive for the call.
+                quote! {
+                    match (#(#args,)*) {
+                        (#(#arg_names,)*) => py.allow_threads(move || function(#(#arg_names),*)),
+                    }
+                }
             } else {
                 let self_arg = self_arg();
                 if self_arg.is_empty() {
@@ -589,6 +695,15 @@ impl<'a> FnSpec<'a> {
                     }
                 }
             };
+            let call = match (&self.asyncness, iterator_return(&self.output)) {
+                (None, Some(IteratorReturn::Bare)) => {
+                    quote! { #pyo3_path::impl_::iterator::RustIterator::new(#call) }
+                }
+                (None, Some(IteratorReturn::InResult)) => {
+                    quote! { (#call).map(#pyo3_path::impl_::iterator::RustIterator::new) }
+                }
+                _ => call,
+            };
             quotes::map_result_into_ptr(quotes::ok_wrap(call, ctx), ctx)
         };
 
@@ -803,7 +918,7 @@ impl<'a> FnSpec<'a> {
     pub fn text_signature_call_signature(&self) -> Option<String> {
         let self_argument = match &self.tp {
             // Getters / Setters / ClassAttribute are not callables on the Python side
-            FnType::Getter(_) | FnType::Setter(_) | FnType::ClassAttribute => return None,
+            FnType::Getter(_) | FnType::Setter(_) | FnType::ClassAttribute { .. } => return None,
             FnType::Fn(_) => Some("self"),
             FnType::FnModule(_) => Some("module"),
             FnType::FnClass(_) | FnType::FnNewClass(_) => Some("cls"),
@@ -812,6 +927,11 @@ impl<'a> FnSpec<'a> {
 
         match self.text_signature.as_ref().map(|attr| &attr.value) {
             Some(TextSignatureAttributeValue::Str(s)) => Some(s.value()),
+            // `#[pyo3(unpack)]` functions only take the packed arguments
+            None if self.unpack.is_some() => Some(match self_argument {
+                Some(self_argument) => format!("(${}, packed, /)", self_argument),
+                None => "(packed, /)".to_owned(),
+            }),
             None => Some(self.signature.text_signature(self_argument)),
             Some(TextSignatureAttributeValue::Disabled(_)) => None,
         }
@@ -824,7 +944,8 @@ enum MethodTypeAttribute {
     StaticMethod(Span),
     Getter(Span, Option<Ident>),
     Setter(Span, Option<Ident>),
-    ClassAttribute(Span),
+    /// The flag is set for `#[classattr(lazy)]`.
+    ClassAttribute(Span, bool),
 }
 
 impl MethodTypeAttribute {
@@ -835,7 +956,7 @@ impl MethodTypeAttribute {
             | MethodTypeAttribute::StaticMethod(span)
             | MethodTypeAttribute::Getter(span, _)
             | MethodTypeAttribute::Setter(span, _)
-            | MethodTypeAttribute::ClassAttribute(span) => *span,
+            | MethodTypeAttribute::ClassAttribute(span, _) => *span,
         }
     }
 
@@ -903,8 +1024,14 @@ impl MethodTypeAttribute {
             ensure_no_arguments(meta, "staticmethod")?;
             Ok(Some(MethodTypeAttribute::StaticMethod(path.span())))
         } else if path.is_ident("classattr") {
-            ensure_no_arguments(meta, "classattr")?;
-            Ok(Some(MethodTypeAttribute::ClassAttribute(path.span())))
+            let lazy = match meta {
+                syn::Meta::List(l) if l.parse_args::<kw::lazy>().is_ok() => true,
+                _ => {
+                    ensure_no_arguments(meta, "classattr")?;
+                    false
+                }
+            };
+            Ok(Some(MethodTypeAttribute::ClassAttribute(path.span(), lazy)))
         } else if path.is_ident("getter") {
             let name = extract_name(meta, "getter")?;
             Ok(Some(MethodTypeAttribute::Getter(path.span(), name)))
@@ -925,7 +1052,7 @@ impl Display for MethodTypeAttribute {
             MethodTypeAttribute::StaticMethod(_) => "#[staticmethod]".fmt(f),
             MethodTypeAttribute::Getter(_, _) => "#[getter]".fmt(f),
             MethodTypeAttribute::Setter(_, _) => "#[setter]".fmt(f),
-            MethodTypeAttribute::ClassAttribute(_) => "#[classattr]".fmt(f),
+            MethodTypeAttribute::ClassAttribute(_, _) => "#[classattr]".fmt(f),
         }
     }
 }
@@ -967,7 +1094,7 @@ fn ensure_signatures_on_valid_method(
             FnType::Setter(_) => {
                 bail_spanned!(signature.kw.span() => "`signature` not allowed with `setter`")
             }
-            FnType::ClassAttribute => {
+            FnType::ClassAttribute { .. } => {
                 bail_spanned!(signature.kw.span() => "`signature` not allowed with `classattr`")
             }
             _ => {}
@@ -981,7 +1108,7 @@ fn ensure_signatures_on_valid_method(
             FnType::Setter(_) => {
                 bail_spanned!(text_signature.kw.span() => "`text_signature` not allowed with `setter`")
             }
-            FnType::ClassAttribute => {
+            FnType::ClassAttribute { .. } => {
                 bail_spanned!(text_signature.kw.span() => "`text_signature` not allowed with `classattr`")
             }
             _ => {}
@@ -2,7 +2,10 @@
 
 use crate::utils::Ctx;
 use crate::{
-    attributes::{self, take_attributes, take_pyo3_options, CrateAttribute, NameAttribute},
+    attributes::{
+        self, take_attributes, take_pyo3_options, CrateAttribute, GILUsedAttribute, NameAttribute,
+        SubmodulesAttribute,
+    },
     get_doc,
     pyfunction::{impl_wrap_pyfunction, PyFunctionOptions},
 };
@@ -21,6 +24,8 @@ use syn::{
 pub struct PyModuleOptions {
     krate: Option<CrateAttribute>,
     name: Option<syn::Ident>,
+    gil_used: Option<GILUsedAttribute>,
+    submodules: Option<SubmodulesAttribute>,
 }
 
 impl PyModuleOptions {
@@ -31,6 +36,8 @@ impl PyModuleOptions {
             match option {
                 PyModulePyO3Option::Name(name) => options.set_name(name.value.0)?,
                 PyModulePyO3Option::Crate(path) => options.set_crate(path)?,
+                PyModulePyO3Option::GILUsed(gil_used) => options.set_gil_used(gil_used)?,
+                PyModulePyO3Option::Submodules(submodules) => options.set_submodules(submodules)?,
             }
         }
 
@@ -56,6 +63,33 @@ impl PyModuleOptions {
         self.krate = Some(path);
         Ok(())
     }
+
+    fn set_gil_used(&mut self, gil_used: GILUsedAttribute) -> Result<()> {
+        ensure_spanned!(
+            self.gil_used.is_none(),
+            gil_used.span() => "`gil_used` may only be specified once"
+        );
+
+        self.gil_used = Some(gil_used);
+        Ok(())
+    }
+
+    fn set_submodules(&mut self, submodules: SubmodulesAttribute) -> Result<()> {
+        ensure_spanned!(
+            self.submodules.is_none(),
+            submodules.span() => "`submodules` may only be specified once"
+        );
+
+        self.submodules = Some(submodules);
+        Ok(())
+    }
+
+    fn gil_used(&self) -> bool {
+        self.gil_used
+            .as_ref()
+            .map(|gil_used| gil_used.value.value)
+            .unwrap_or(true)
+    }
 }
 
 pub fn pymodule_module_impl(mut module: syn::ItemMod) -> Result<TokenStream> {
@@ -74,9 +108,13 @@ pub fn pymodule_module_impl(mut module: syn::ItemMod) -> Result<TokenStream> {
         bail_spanned!(module.span() => "`#[pymodule]` can only be used on inline modules")
     };
     let options = PyModuleOptions::from_attrs(attrs)?;
+    if let Some(submodules) = &options.submodules {
+        bail_spanned!(submodules.span() => "`submodules` is only supported on `#[pymodule]` functions; declare the submodules as nested `#[pymodule]` modules instead");
+    }
     let ctx = &Ctx::new(&options.krate);
     let Ctx { pyo3_path } = ctx;
     let doc = get_doc(attrs, None);
+    let gil_used = options.gil_used();
 
     let mut module_items = Vec::new();
     let mut module_items_cfg_attrs = Vec::new();
@@ -259,6 +297,7 @@ pub fn pymodule_module_impl(mut module: syn::ItemMod) -> Result<TokenStream> {
                             #doc,
                             INITIALIZER
                         )
+                        .gil_used(#gil_used)
                     }
                 }
             }
@@ -287,6 +326,12 @@ pub fn pymodule_function_impl(mut function: syn::ItemFn) -> Result<TokenStream>
     let ident = &function.sig.ident;
     let vis = &function.vis;
     let doc = get_doc(&function.attrs, None);
+    let gil_used = options.gil_used();
+    let submodules: Vec<_> = options
+        .submodules
+        .iter()
+        .flat_map(|submodules| submodules.modules.iter().cloned())
+        .collect();
 
     let initialization = module_initialization(options, ident);
 
@@ -335,6 +380,7 @@ pub fn pymodule_function_impl(mut function: syn::ItemFn) -> Result<TokenStream>
         impl #ident::MakeDef {
             const fn make_def() -> #pyo3_path::impl_::pymodule::ModuleDef {
                 fn __pyo3_pymodule(module: &#pyo3_path::Bound<'_, #pyo3_path::types::PyModule>) -> #pyo3_path::PyResult<()> {
+                    #(#submodules::add_to_module(module)?;)*
                     #ident(#(#module_args),*)
                 }
 
@@ -345,6 +391,7 @@ pub fn pymodule_function_impl(mut function: syn::ItemFn) -> Result<TokenStream>
                         #doc,
                         INITIALIZER
                     )
+                    .gil_used(#gil_used)
                 }
             }
         }
@@ -394,7 +441,7 @@ fn process_functions_in_module(options: &PyModuleOptions, func: &mut syn::ItemFn
                 let name = &func.sig.ident;
                 let statements: Vec<syn::Stmt> = syn::parse_quote! {
                     #wrapped_function
-                    #module_name.as_borrowed().add_function(#pyo3_path::wrap_pyfunction!(#name, #module_name.as_borrowed())?)?;
+                    #name::add_to_module(&#module_name.as_borrowed())?;
                 };
                 stmts.extend(statements);
             }
@@ -487,6 +534,8 @@ fn has_attribute(attrs: &[syn::Attribute], ident: &str) -> bool {
 enum PyModulePyO3Option {
     Crate(CrateAttribute),
     Name(NameAttribute),
+    GILUsed(GILUsedAttribute),
+    Submodules(SubmodulesAttribute),
 }
 
 impl Parse for PyModulePyO3Option {
@@ -496,6 +545,10 @@ impl Parse for PyModulePyO3Option {
             input.parse().map(PyModulePyO3Option::Name)
         } else if lookahead.peek(syn::Token![crate]) {
             input.parse().map(PyModulePyO3Option::Crate)
+        } else if lookahead.peek(attributes::kw::gil_used) {
+            input.parse().map(PyModulePyO3Option::GILUsed)
+        } else if lookahead.peek(attributes::kw::submodules) {
+            input.parse().map(PyModulePyO3Option::Submodules)
         } else {
             Err(lookahead.error())
         }
@@ -1,11 +1,11 @@
-use crate::utils::Ctx;
+use crate::utils::{erase_lifetimes, Ctx};
 use crate::{
     method::{FnArg, FnSpec},
     pyfunction::FunctionSignature,
     quotes::some_wrap,
 };
 use proc_macro2::{Span, TokenStream};
-use quote::{quote, quote_spanned};
+use quote::{quote, quote_spanned, ToTokens};
 use syn::spanned::Spanned;
 use syn::Result;
 
@@ -58,19 +58,21 @@ impl Holders {
 
 /// Return true if the argument list is simply (*args, **kwds).
 pub fn is_forwarded_args(signature: &FunctionSignature<'_>) -> bool {
-    matches!(
-        signature.arguments.as_slice(),
-        [
-            FnArg {
-                is_varargs: true,
-                ..
-            },
-            FnArg {
-                is_kwargs: true,
-                ..
-            },
-        ]
-    )
+    signature.python_signature.min_varargs.is_none()
+        && signature.python_signature.max_varargs.is_none()
+        && matches!(
+            signature.arguments.as_slice(),
+            [
+                FnArg {
+                    is_varargs: true,
+                    ..
+                },
+                FnArg {
+                    is_kwargs: true,
+                    ..
+                },
+            ]
+        )
 }
 
 fn check_arg_for_gil_refs(
@@ -111,7 +113,7 @@ pub fn impl_arg_params(
         })
         .collect::<TokenStream>();
 
-    if !fastcall && is_forwarded_args(&spec.signature) {
+    if !fastcall && spec.unpack.is_none() && is_forwarded_args(&spec.signature) {
         // In the varargs convention, we can just pass though if the signature
         // is (*args, **kwds).
         let arg_convert = spec
@@ -192,7 +194,38 @@ pub fn impl_arg_params(
     };
     let python_name = &spec.python_name;
 
-    let extract_expression = if fastcall {
+    let min_varargs = &spec.signature.python_signature.min_varargs;
+    let max_varargs = &spec.signature.python_signature.max_varargs;
+    let check_varargs_arity = if min_varargs.is_some() || max_varargs.is_some() {
+        let option_tokens = |value: &Option<usize>| match value {
+            Some(value) => quote! { ::std::option::Option::Some(#value) },
+            None => quote! { ::std::option::Option::None },
+        };
+        let min_varargs = option_tokens(min_varargs);
+        let max_varargs = option_tokens(max_varargs);
+        quote! {
+            DESCRIPTION.check_varargs_arity(&_args, #min_varargs, #max_varargs)?;
+        }
+    } else {
+        quote! {}
+    };
+
+    let packed_kwargs_holder = if spec.unpack.is_some() {
+        quote! { let mut packed_kwargs_holder = ::std::option::Option::None; }
+    } else {
+        quote! {}
+    };
+    let extract_expression = if spec.unpack.is_some() {
+        quote! {
+            DESCRIPTION.extract_arguments_unpacked::<#args_handler, #kwargs_handler>(
+                py,
+                _args,
+                _kwargs,
+                &mut #args_array,
+                &mut packed_kwargs_holder
+            )?
+        }
+    } else if fastcall {
         quote! {
             DESCRIPTION.extract_arguments_fastcall::<#args_handler, #kwargs_handler>(
                 py,
@@ -225,7 +258,9 @@ pub fn impl_arg_params(
                     keyword_only_parameters: &[#(#keyword_only_parameters),*],
                 };
                 let mut #args_array = [::std::option::Option::None; #num_params];
+                #packed_kwargs_holder
                 let (_args, _kwargs) = #extract_expression;
+                #check_varargs_arity
                 #from_py_with
         },
         param_conversion,
@@ -241,6 +276,31 @@ fn impl_arg_param(
     args_array: &syn::Ident,
     holders: &mut Holders,
     ctx: &Ctx,
+) -> Result<TokenStream> {
+    let tokens = impl_arg_extraction(arg, pos, option_pos, args_array, holders, ctx)?;
+    let validate = match &arg.attrs.validate {
+        Some(validate) => &validate.value,
+        None => return Ok(tokens),
+    };
+
+    let Ctx { pyo3_path } = ctx;
+    let name_str = arg.name.to_string();
+    Ok(quote_spanned! { validate.span() =>
+        {
+            let value = #tokens;
+            #pyo3_path::impl_::extract_argument::validate_argument(py, &value, #name_str, #validate)?;
+            value
+        }
+    })
+}
+
+fn impl_arg_extraction(
+    arg: &FnArg<'_>,
+    pos: usize,
+    option_pos: &mut usize,
+    args_array: &syn::Ident,
+    holders: &mut Holders,
+    ctx: &Ctx,
 ) -> Result<TokenStream> {
     let Ctx { pyo3_path } = ctx;
     let pyo3_path = pyo3_path.to_tokens_spanned(arg.ty.span());
@@ -262,11 +322,29 @@ fn impl_arg_param(
     let name = arg.name;
     let name_str = name.to_string();
 
+    if let Some(strict) = arg.attrs.strict {
+        ensure_spanned!(
+            !arg.is_varargs && !arg.is_kwargs,
+            strict.span() => "`strict` cannot be used on `*args` or `**kwargs` arguments"
+        );
+    }
+
     if arg.is_varargs {
         ensure_spanned!(
             arg.optional.is_none(),
             arg.name.span() => "args cannot be optional"
         );
+        if is_vec_type(arg.ty) {
+            // `*option_pos` is the number of positional parameters before `*args`
+            let first_position = *option_pos + 1;
+            return Ok(quote_arg_span! {
+                #pyo3_path::impl_::extract_argument::extract_varargs_vec(
+                    &_args,
+                    #name_str,
+                    #first_position
+                )?
+            });
+        }
         let holder = holders.push_holder(arg.ty.span());
         return Ok(quote_arg_span! {
             #pyo3_path::impl_::extract_argument::extract_argument(
@@ -275,6 +353,24 @@ fn impl_arg_param(
                 #name_str
             )?
         });
+    } else if arg.is_kwargs && arg.attrs.kwargs_struct.is_some() {
+        let ty = erase_lifetimes(arg.ty.to_token_stream());
+        return Ok(quote_arg_span! {
+            #pyo3_path::impl_::extract_argument::extract_kwargs_struct(
+                py,
+                &DESCRIPTION,
+                _kwargs.as_deref(),
+                {
+                    #[allow(unused_imports)]
+                    use #pyo3_path::impl_::extract_argument::{
+                        ProbeKwargsStructFields, ProbeNoKwargsStructFields,
+                    };
+                    (&#pyo3_path::impl_::extract_argument::KwargsStructProbe::<#ty>::new())
+                        .kwargs_struct_fields()
+                },
+                #name_str
+            )?
+        });
     } else if arg.is_kwargs {
         ensure_spanned!(
             arg.optional.is_some(),
@@ -311,8 +407,14 @@ fn impl_arg_param(
         .as_ref()
         .map(|attr| &attr.value)
         .is_some()
+        || arg.attrs.strict.is_some()
     {
-        let from_py_with = syn::Ident::new(&format!("from_py_with_{}", pos), Span::call_site());
+        let from_py_with = if arg.attrs.strict.is_some() {
+            let ty = arg.ty;
+            quote_arg_span! { <#ty as #pyo3_path::impl_::extract_argument::StrictArgument>::extract_strict }
+        } else {
+            syn::Ident::new(&format!("from_py_with_{}", pos), Span::call_site()).into_token_stream()
+        };
         if let Some(default) = default {
             quote_arg_span! {
                 #pyo3_path::impl_::extract_argument::from_py_with_with_default(
@@ -373,3 +475,14 @@ fn impl_arg_param(
 
     Ok(tokens)
 }
+
+/// Whether `ty` is spelled `Vec<_>`, in which case `*args` are extracted element by element.
+fn is_vec_type(ty: &syn::Type) -> bool {
+    if let syn::Type::Path(syn::TypePath { qself: None, path }) = ty {
+        if let Some(seg) = path.segments.last() {
+            return seg.ident == "Vec"
+                && matches!(seg.arguments, syn::PathArguments::AngleBracketed(_));
+        }
+    }
+    false
+}
@@ -2,22 +2,26 @@ use std::borrow::Cow;
 
 use crate::attributes::kw::frozen;
 use crate::attributes::{
-    self, kw, take_pyo3_options, CrateAttribute, ExtendsAttribute, FreelistAttribute,
-    ModuleAttribute, NameAttribute, NameLitStr, RenameAllAttribute,
+    self, kw, take_pyo3_options, CompareWithAttribute, CrateAttribute, DocAttribute,
+    ExtendsAttribute, FreelistAttribute, ModuleAttribute, NameAttribute, NameLitStr,
+    OnFirstInitAttribute, RenameAllAttribute, SetterAttribute, StrFormatterAttribute,
 };
 use crate::deprecations::Deprecations;
 use crate::konst::{ConstAttributes, ConstSpec};
 use crate::method::{FnArg, FnSpec};
+use crate::pyfunction::stub_return_annotation;
 use crate::pyimpl::{gen_py_const, PyClassMethodsType};
 use crate::pymethod::{
-    impl_py_getter_def, impl_py_setter_def, MethodAndMethodDef, MethodAndSlotDef, PropertyType,
-    SlotDef, __INT__, __REPR__, __RICHCMP__,
+    impl_py_getter_def, impl_py_method_def, impl_py_setter_def, MethodAndMethodDef,
+    MethodAndSlotDef, PropertyType, SlotDef, __GETBUFFER__, __HASH__, __INT__, __ITER__, __NEXT__,
+    __RELEASEBUFFER__, __REPR__, __RICHCMP__, __STR__,
 };
+use crate::stub::{submit_stub, StubClass};
 use crate::utils::Ctx;
 use crate::utils::{self, apply_renaming_rule, PythonDoc};
 use crate::PyFunctionOptions;
 use proc_macro2::{Ident, Span, TokenStream};
-use quote::{format_ident, quote};
+use quote::{format_ident, quote, quote_spanned};
 use syn::ext::IdentExt;
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
@@ -57,36 +61,68 @@ impl PyClassArgs {
 #[derive(Clone, Default)]
 pub struct PyClassPyO3Options {
     pub krate: Option<CrateAttribute>,
+    pub buffer: Option<kw::buffer>,
+    pub clone: Option<kw::clone>,
+    pub compare_with: Option<CompareWithAttribute>,
+    pub context_manager: Option<kw::context_manager>,
     pub dict: Option<kw::dict>,
+    pub doc: Option<DocAttribute>,
+    pub eq: Option<kw::eq>,
     pub extends: Option<ExtendsAttribute>,
     pub get_all: Option<kw::get_all>,
+    pub getnewargs: Option<kw::getnewargs>,
+    pub hash: Option<kw::hash>,
     pub freelist: Option<FreelistAttribute>,
     pub frozen: Option<kw::frozen>,
     pub mapping: Option<kw::mapping>,
+    pub match_args: Option<kw::match_args>,
     pub module: Option<ModuleAttribute>,
     pub name: Option<NameAttribute>,
+    pub on_first_init: Option<OnFirstInitAttribute>,
+    pub ord: Option<kw::ord>,
     pub rename_all: Option<RenameAllAttribute>,
+    pub replace: Option<kw::replace>,
+    pub repr: Option<kw::repr>,
     pub sequence: Option<kw::sequence>,
     pub set_all: Option<kw::set_all>,
+    pub str: Option<StrFormatterAttribute>,
     pub subclass: Option<kw::subclass>,
+    pub subscriptable: Option<kw::subscriptable>,
+    pub track_instances: Option<kw::track_instances>,
     pub unsendable: Option<kw::unsendable>,
     pub weakref: Option<kw::weakref>,
 }
 
 enum PyClassPyO3Option {
     Crate(CrateAttribute),
+    Buffer(kw::buffer),
+    Clone(kw::clone),
+    CompareWith(CompareWithAttribute),
+    ContextManager(kw::context_manager),
     Dict(kw::dict),
+    Doc(DocAttribute),
+    Eq(kw::eq),
     Extends(ExtendsAttribute),
     Freelist(FreelistAttribute),
     Frozen(kw::frozen),
     GetAll(kw::get_all),
+    Getnewargs(kw::getnewargs),
+    Hash(kw::hash),
     Mapping(kw::mapping),
+    MatchArgs(kw::match_args),
     Module(ModuleAttribute),
     Name(NameAttribute),
+    OnFirstInit(OnFirstInitAttribute),
+    Ord(kw::ord),
     RenameAll(RenameAllAttribute),
+    Replace(kw::replace),
+    Repr(kw::repr),
     Sequence(kw::sequence),
     SetAll(kw::set_all),
+    Str(StrFormatterAttribute),
     Subclass(kw::subclass),
+    Subscriptable(kw::subscriptable),
+    TrackInstances(kw::track_instances),
     Unsendable(kw::unsendable),
     Weakref(kw::weakref),
 }
@@ -96,8 +132,20 @@ impl Parse for PyClassPyO3Option {
         let lookahead = input.lookahead1();
         if lookahead.peek(Token![crate]) {
             input.parse().map(PyClassPyO3Option::Crate)
+        } else if lookahead.peek(attributes::kw::buffer) {
+            input.parse().map(PyClassPyO3Option::Buffer)
+        } else if lookahead.peek(kw::clone) {
+            input.parse().map(PyClassPyO3Option::Clone)
+        } else if lookahead.peek(attributes::kw::compare_with) {
+            input.parse().map(PyClassPyO3Option::CompareWith)
+        } else if lookahead.peek(attributes::kw::context_manager) {
+            input.parse().map(PyClassPyO3Option::ContextManager)
         } else if lookahead.peek(kw::dict) {
             input.parse().map(PyClassPyO3Option::Dict)
+        } else if lookahead.peek(kw::doc) {
+            input.parse().map(PyClassPyO3Option::Doc)
+        } else if lookahead.peek(attributes::kw::eq) {
+            input.parse().map(PyClassPyO3Option::Eq)
         } else if lookahead.peek(kw::extends) {
             input.parse().map(PyClassPyO3Option::Extends)
         } else if lookahead.peek(attributes::kw::freelist) {
@@ -106,20 +154,40 @@ impl Parse for PyClassPyO3Option {
             input.parse().map(PyClassPyO3Option::Frozen)
         } else if lookahead.peek(attributes::kw::get_all) {
             input.parse().map(PyClassPyO3Option::GetAll)
+        } else if lookahead.peek(attributes::kw::getnewargs) {
+            input.parse().map(PyClassPyO3Option::Getnewargs)
+        } else if lookahead.peek(attributes::kw::hash) {
+            input.parse().map(PyClassPyO3Option::Hash)
         } else if lookahead.peek(attributes::kw::mapping) {
             input.parse().map(PyClassPyO3Option::Mapping)
+        } else if lookahead.peek(attributes::kw::match_args) {
+            input.parse().map(PyClassPyO3Option::MatchArgs)
         } else if lookahead.peek(attributes::kw::module) {
             input.parse().map(PyClassPyO3Option::Module)
         } else if lookahead.peek(kw::name) {
             input.parse().map(PyClassPyO3Option::Name)
+        } else if lookahead.peek(attributes::kw::on_first_init) {
+            input.parse().map(PyClassPyO3Option::OnFirstInit)
+        } else if lookahead.peek(attributes::kw::ord) {
+            input.parse().map(PyClassPyO3Option::Ord)
         } else if lookahead.peek(kw::rename_all) {
             input.parse().map(PyClassPyO3Option::RenameAll)
+        } else if lookahead.peek(attributes::kw::replace) {
+            input.parse().map(PyClassPyO3Option::Replace)
+        } else if lookahead.peek(attributes::kw::repr) {
+            input.parse().map(PyClassPyO3Option::Repr)
         } else if lookahead.peek(attributes::kw::sequence) {
             input.parse().map(PyClassPyO3Option::Sequence)
         } else if lookahead.peek(attributes::kw::set_all) {
             input.parse().map(PyClassPyO3Option::SetAll)
+        } else if lookahead.peek(attributes::kw::str) {
+            input.parse().map(PyClassPyO3Option::Str)
         } else if lookahead.peek(attributes::kw::subclass) {
             input.parse().map(PyClassPyO3Option::Subclass)
+        } else if lookahead.peek(attributes::kw::subscriptable) {
+            input.parse().map(PyClassPyO3Option::Subscriptable)
+        } else if lookahead.peek(attributes::kw::track_instances) {
+            input.parse().map(PyClassPyO3Option::TrackInstances)
         } else if lookahead.peek(attributes::kw::unsendable) {
             input.parse().map(PyClassPyO3Option::Unsendable)
         } else if lookahead.peek(attributes::kw::weakref) {
@@ -164,18 +232,34 @@ impl PyClassPyO3Options {
 
         match option {
             PyClassPyO3Option::Crate(krate) => set_option!(krate),
+            PyClassPyO3Option::Buffer(buffer) => set_option!(buffer),
+            PyClassPyO3Option::Clone(clone) => set_option!(clone),
+            PyClassPyO3Option::CompareWith(compare_with) => set_option!(compare_with),
+            PyClassPyO3Option::ContextManager(context_manager) => set_option!(context_manager),
             PyClassPyO3Option::Dict(dict) => set_option!(dict),
+            PyClassPyO3Option::Doc(doc) => set_option!(doc),
+            PyClassPyO3Option::Eq(eq) => set_option!(eq),
             PyClassPyO3Option::Extends(extends) => set_option!(extends),
             PyClassPyO3Option::Freelist(freelist) => set_option!(freelist),
             PyClassPyO3Option::Frozen(frozen) => set_option!(frozen),
             PyClassPyO3Option::GetAll(get_all) => set_option!(get_all),
+            PyClassPyO3Option::Getnewargs(getnewargs) => set_option!(getnewargs),
+            PyClassPyO3Option::Hash(hash) => set_option!(hash),
             PyClassPyO3Option::Mapping(mapping) => set_option!(mapping),
+            PyClassPyO3Option::MatchArgs(match_args) => set_option!(match_args),
             PyClassPyO3Option::Module(module) => set_option!(module),
             PyClassPyO3Option::Name(name) => set_option!(name),
+            PyClassPyO3Option::OnFirstInit(on_first_init) => set_option!(on_first_init),
+            PyClassPyO3Option::Ord(ord) => set_option!(ord),
             PyClassPyO3Option::RenameAll(rename_all) => set_option!(rename_all),
+            PyClassPyO3Option::Replace(replace) => set_option!(replace),
+            PyClassPyO3Option::Repr(repr) => set_option!(repr),
             PyClassPyO3Option::Sequence(sequence) => set_option!(sequence),
             PyClassPyO3Option::SetAll(set_all) => set_option!(set_all),
+            PyClassPyO3Option::Str(str) => set_option!(str),
             PyClassPyO3Option::Subclass(subclass) => set_option!(subclass),
+            PyClassPyO3Option::Subscriptable(subscriptable) => set_option!(subscriptable),
+            PyClassPyO3Option::TrackInstances(track_instances) => set_option!(track_instances),
             PyClassPyO3Option::Unsendable(unsendable) => set_option!(unsendable),
             PyClassPyO3Option::Weakref(weakref) => set_option!(weakref),
         }
@@ -183,13 +267,21 @@ impl PyClassPyO3Options {
     }
 }
 
+/// `#[pyo3(doc = ...)]` takes precedence over the Rust doc comments.
+fn get_class_doc(attrs: &[syn::Attribute], options: &PyClassPyO3Options) -> PythonDoc {
+    match &options.doc {
+        Some(doc) => utils::get_doc_from_expr(&doc.value),
+        None => utils::get_doc(attrs, None),
+    }
+}
+
 pub fn build_py_class(
     class: &mut syn::ItemStruct,
     mut args: PyClassArgs,
     methods_type: PyClassMethodsType,
 ) -> syn::Result<TokenStream> {
     args.options.take_pyo3_options(&mut class.attrs)?;
-    let doc = utils::get_doc(&class.attrs, None);
+    let doc = get_class_doc(&class.attrs, &args.options);
 
     let ctx = &Ctx::new(&args.options.krate);
 
@@ -246,8 +338,12 @@ pub fn build_py_class(
     }
 
     if let Some(attr) = args.options.set_all {
-        for (_, FieldPyO3Options { set, .. }) in &mut field_options {
-            if let Some(old_set) = set.replace(Annotated::Struct(attr)) {
+        for (_, options) in &mut field_options {
+            if options.set_validator.is_some() {
+                // `set = validator` adds to `set_all` rather than repeating it
+                continue;
+            }
+            if let Some(old_set) = options.set.replace(Annotated::Struct(attr)) {
                 return Err(syn::Error::new(old_set.span(), DUPE_SET));
             }
         }
@@ -274,20 +370,37 @@ impl<X: Spanned, Y: Spanned> Annotated<X, Y> {
 struct FieldPyO3Options {
     get: Option<Annotated<kw::get, kw::get_all>>,
     set: Option<Annotated<kw::set, kw::set_all>>,
+    set_validator: Option<syn::ExprPath>,
     name: Option<NameAttribute>,
+    cache: Option<kw::cache>,
+    eq_hash: Option<kw::eq_hash>,
+    iter: Option<kw::iter>,
+    repr: Option<kw::repr>,
 }
 
 enum FieldPyO3Option {
+    Cache(attributes::kw::cache),
+    EqHash(attributes::kw::eq_hash),
     Get(attributes::kw::get),
-    Set(attributes::kw::set),
+    Iter(attributes::kw::iter),
+    Repr(attributes::kw::repr),
+    Set(SetterAttribute),
     Name(NameAttribute),
 }
 
 impl Parse for FieldPyO3Option {
     fn parse(input: ParseStream<'_>) -> Result<Self> {
         let lookahead = input.lookahead1();
-        if lookahead.peek(attributes::kw::get) {
+        if lookahead.peek(attributes::kw::cache) {
+            input.parse().map(FieldPyO3Option::Cache)
+        } else if lookahead.peek(attributes::kw::eq_hash) {
+            input.parse().map(FieldPyO3Option::EqHash)
+        } else if lookahead.peek(attributes::kw::get) {
             input.parse().map(FieldPyO3Option::Get)
+        } else if lookahead.peek(attributes::kw::iter) {
+            input.parse().map(FieldPyO3Option::Iter)
+        } else if lookahead.peek(attributes::kw::repr) {
+            input.parse().map(FieldPyO3Option::Repr)
         } else if lookahead.peek(attributes::kw::set) {
             input.parse().map(FieldPyO3Option::Set)
         } else if lookahead.peek(attributes::kw::name) {
@@ -303,20 +416,46 @@ impl FieldPyO3Options {
         let mut options = FieldPyO3Options {
             get: None,
             set: None,
+            set_validator: None,
             name: None,
+            cache: None,
+            eq_hash: None,
+            iter: None,
+            repr: None,
         };
 
         for option in take_pyo3_options(attrs)? {
             match option {
+                FieldPyO3Option::Cache(kw) => {
+                    if options.cache.replace(kw).is_some() {
+                        return Err(syn::Error::new(kw.span(), UNIQUE_CACHE));
+                    }
+                }
+                FieldPyO3Option::EqHash(kw) => {
+                    if options.eq_hash.replace(kw).is_some() {
+                        return Err(syn::Error::new(kw.span(), UNIQUE_EQ_HASH));
+                    }
+                }
                 FieldPyO3Option::Get(kw) => {
                     if options.get.replace(Annotated::Field(kw)).is_some() {
                         return Err(syn::Error::new(kw.span(), UNIQUE_GET));
                     }
                 }
-                FieldPyO3Option::Set(kw) => {
+                FieldPyO3Option::Iter(kw) => {
+                    if options.iter.replace(kw).is_some() {
+                        return Err(syn::Error::new(kw.span(), UNIQUE_ITER));
+                    }
+                }
+                FieldPyO3Option::Repr(kw) => {
+                    if options.repr.replace(kw).is_some() {
+                        return Err(syn::Error::new(kw.span(), UNIQUE_REPR));
+                    }
+                }
+                FieldPyO3Option::Set(SetterAttribute { kw, validator }) => {
                     if options.set.replace(Annotated::Field(kw)).is_some() {
                         return Err(syn::Error::new(kw.span(), UNIQUE_SET));
                     }
+                    options.set_validator = validator;
                 }
                 FieldPyO3Option::Name(name) => {
                     if options.name.replace(name).is_some() {
@@ -325,53 +464,953 @@ impl FieldPyO3Options {
                 }
             }
         }
-
-        Ok(options)
+
+        Ok(options)
+    }
+}
+
+fn get_class_python_name<'a>(cls: &'a syn::Ident, args: &'a PyClassArgs) -> Cow<'a, syn::Ident> {
+    args.options
+        .name
+        .as_ref()
+        .map(|name_attr| Cow::Borrowed(&name_attr.value.0))
+        .unwrap_or_else(|| Cow::Owned(cls.unraw()))
+}
+
+fn impl_class(
+    cls: &syn::Ident,
+    args: &PyClassArgs,
+    doc: PythonDoc,
+    field_options: Vec<(&syn::Field, FieldPyO3Options)>,
+    methods_type: PyClassMethodsType,
+    ctx: &Ctx,
+) -> syn::Result<TokenStream> {
+    let Ctx { pyo3_path } = ctx;
+    let pytypeinfo_impl = impl_pytypeinfo(cls, args, None, ctx);
+
+    let getter_cache_size = field_options
+        .iter()
+        .filter(|(_, options)| options.cache.is_some())
+        .count();
+    let (eq_hash_impl, mut default_slots) = generate_eq_hash_slots(cls, args, &field_options, ctx)?;
+    let iter_impl = generate_iter_slots(cls, args, &field_options, &mut default_slots, ctx)?;
+    let richcmp_impl = generate_richcmp_slot(cls, args, &field_options, &mut default_slots, ctx)?;
+    let hash_impl = generate_hash_slot(cls, args, &mut default_slots, ctx)?;
+    let buffer_impl = generate_buffer_slots(cls, args, &mut default_slots, ctx)?;
+    let fields: Vec<&syn::Field> = field_options.iter().map(|(field, _)| *field).collect();
+    let str_impl = generate_str_slot(cls, args, Some(&fields), &mut default_slots, ctx)?;
+    let repr_impl = generate_repr_slot(cls, args, &field_options, &mut default_slots, ctx)?;
+    let stubs = class_stubs(cls, args, &field_options, ctx);
+    let replace_fields = match args.options.replace {
+        Some(_) => replace_fields(args, &field_options)?,
+        None => Vec::new(),
+    };
+    let mut default_methods = descriptors_to_items(
+        cls,
+        args.options.rename_all.as_ref(),
+        args.options.frozen,
+        field_options,
+        ctx,
+    )?;
+
+    let copy_impl = if let Some(clone) = args.options.clone {
+        ensure_spanned!(
+            args.options.extends.is_none(),
+            clone.span() => "`clone` cannot be used together with `extends`"
+        );
+        let (copy_impl, copy_defs) = generate_default_copy_methods(cls, ctx)?;
+        default_methods.extend(copy_defs);
+        Some(copy_impl)
+    } else {
+        None
+    };
+
+    let replace_impl = if let Some(replace) = args.options.replace {
+        ensure_spanned!(
+            args.options.extends.is_none(),
+            replace.span() => "`replace` cannot be used together with `extends`"
+        );
+        let (replace_impl, replace_def) =
+            generate_default_replace_method(cls, args, &replace_fields, ctx)?;
+        default_methods.push(replace_def);
+        Some(replace_impl)
+    } else {
+        None
+    };
+
+    let context_manager_impl = if args.options.context_manager.is_some() {
+        let (context_manager_impl, context_manager_defs) =
+            generate_default_context_manager_methods(cls, ctx)?;
+        default_methods.extend(context_manager_defs);
+        Some(context_manager_impl)
+    } else {
+        None
+    };
+
+    if args.options.match_args.is_some() {
+        default_methods.push(generate_match_args_attr(cls, ctx));
+    }
+
+    let getnewargs_impl = if args.options.getnewargs.is_some() {
+        let (getnewargs_impl, getnewargs_def) = generate_default_getnewargs_method(cls, ctx)?;
+        default_methods.push(getnewargs_def);
+        Some(getnewargs_impl)
+    } else {
+        None
+    };
+
+    let class_getitem_impl = if args.options.subscriptable.is_some() {
+        let (class_getitem_impl, class_getitem_def) =
+            generate_default_class_getitem_method(cls, ctx)?;
+        default_methods.push(class_getitem_def);
+        Some(class_getitem_impl)
+    } else {
+        None
+    };
+
+    let py_class_impl =
+        PyClassImplsBuilder::new(cls, args, methods_type, default_methods, default_slots)
+            .doc(doc)
+            .getter_cache_size(getter_cache_size)
+            .impl_all(ctx)?;
+
+    Ok(quote! {
+        impl #pyo3_path::types::DerefToPyAny for #cls {}
+
+        #pytypeinfo_impl
+
+        #py_class_impl
+
+        #copy_impl
+
+        #replace_impl
+
+        #context_manager_impl
+
+        #getnewargs_impl
+
+        #class_getitem_impl
+
+        #eq_hash_impl
+
+        #richcmp_impl
+
+        #hash_impl
+
+        #buffer_impl
+
+        #iter_impl
+
+        #str_impl
+
+        #repr_impl
+
+        #stubs
+    })
+}
+
+/// The Python name of a field, as used by its `#[pyo3(get, set)]` descriptors.
+///
+/// `None` for tuple struct fields without `#[pyo3(name = ...)]`.
+fn field_python_name(
+    field: &syn::Field,
+    options: &FieldPyO3Options,
+    args: &PyClassArgs,
+) -> Option<String> {
+    match (&options.name, &field.ident) {
+        (Some(name), _) => Some(name.value.0.to_string()),
+        (None, Some(ident)) => {
+            let name = ident.unraw().to_string();
+            Some(match &args.options.rename_all {
+                Some(rename_all) => apply_renaming_rule(rename_all.value.rule, &name),
+                None => name,
+            })
+        }
+        (None, None) => None,
+    }
+}
+
+/// The Python keyword of every field with a getter, for `#[pyclass(replace)]`, and whether the
+/// field also has a setter.
+fn replace_fields(
+    args: &PyClassArgs,
+    field_options: &[(&syn::Field, FieldPyO3Options)],
+) -> syn::Result<Vec<(String, bool)>> {
+    field_options
+        .iter()
+        .filter(|(_, options)| options.get.is_some())
+        .map(|(field, options)| {
+            let name = field_python_name(field, options, args).ok_or_else(|| {
+                err_spanned!(field.span() => "`replace` with tuple struct fields requires `#[pyo3(name = ...)]`")
+            })?;
+            Ok((name, options.set.is_some()))
+        })
+        .collect()
+}
+
+/// Generates `__replace__` for `#[pyclass(replace)]`, which is used by `copy.replace` on
+/// Python 3.13 and up. The new object is created by calling the type of `self` with the values of
+/// all fields with getters as keyword arguments. Changes to fields with setters are then assigned
+/// through the setters, the other changes are passed to the constructor.
+fn generate_default_replace_method(
+    cls: &syn::Ident,
+    args: &PyClassArgs,
+    fields: &[(String, bool)],
+    ctx: &Ctx,
+) -> syn::Result<(TokenStream, MethodAndMethodDef)> {
+    let Ctx { pyo3_path } = ctx;
+    let class_name = get_class_python_name(cls, args).to_string();
+    let unexpected_keyword = format!(
+        "{}.__replace__() got an unexpected keyword argument '{{}}'",
+        class_name
+    );
+    let names: Vec<_> = fields.iter().map(|(name, _)| name).collect();
+    let (settable, constructed): (Vec<_>, Vec<_>) = fields.iter().partition(|(_, set)| *set);
+    let mut arms = Vec::new();
+    let (declare_assigned, assign) = if settable.is_empty() {
+        (quote!(), quote!())
+    } else {
+        let settable = settable.iter().map(|(name, _)| name);
+        arms.push(quote! {
+            #(#settable)|* => assigned.push((::std::clone::Clone::clone(&key), value)),
+        });
+        (
+            quote! {
+                let mut assigned = ::std::vec::Vec::new();
+            },
+            quote! {
+                for (key, value) in assigned {
+                    #pyo3_path::types::PyAnyMethods::setattr(&replaced, key, value)?;
+                }
+            },
+        )
+    };
+    if !constructed.is_empty() {
+        let constructed = constructed.iter().map(|(name, _)| name);
+        arms.push(quote! {
+            #(#constructed)|* => #pyo3_path::types::PyDictMethods::set_item(&kwargs, &key, value)?,
+        });
+    }
+    let mut replace_impl: syn::ImplItemFn = parse_quote! {
+        fn __pyo3__replace__(
+            slf: &#pyo3_path::Bound<'_, Self>,
+            changes: ::std::option::Option<&#pyo3_path::Bound<'_, #pyo3_path::types::PyDict>>,
+        ) -> #pyo3_path::PyResult<#pyo3_path::PyObject> {
+            let py = #pyo3_path::Bound::py(slf);
+            let kwargs = #pyo3_path::types::PyDict::new_bound(py);
+            #(#pyo3_path::types::PyDictMethods::set_item(
+                &kwargs,
+                #names,
+                #pyo3_path::types::PyAnyMethods::getattr(slf.as_any(), #names)?,
+            )?;)*
+            #declare_assigned
+            if let ::std::option::Option::Some(changes) = changes {
+                for (key, value) in #pyo3_path::types::PyDictMethods::iter(changes) {
+                    let key = #pyo3_path::types::PyAnyMethods::downcast_into::<
+                        #pyo3_path::types::PyString,
+                    >(key)?;
+                    match &*#pyo3_path::types::PyStringMethods::to_cow(&key)? {
+                        #(#arms)*
+                        key => {
+                            return ::std::result::Result::Err(
+                                #pyo3_path::exceptions::PyTypeError::new_err(
+                                    ::std::format!(#unexpected_keyword, key),
+                                ),
+                            );
+                        }
+                    }
+                }
+            }
+            let replaced = #pyo3_path::types::PyAnyMethods::call(
+                #pyo3_path::types::PyAnyMethods::get_type(slf.as_any()).as_any(),
+                (),
+                ::std::option::Option::Some(&kwargs),
+            )?;
+            #assign
+            ::std::result::Result::Ok(#pyo3_path::Bound::unbind(replaced))
+        }
+    };
+    let options = PyFunctionOptions {
+        name: Some(NameAttribute {
+            kw: parse_quote! { name },
+            value: NameLitStr(format_ident!("__replace__")),
+        }),
+        signature: Some(parse_quote! { signature = (**changes) }),
+        ..Default::default()
+    };
+    let spec = FnSpec::parse(&mut replace_impl.sig, &mut Vec::new(), options, ctx)?;
+    let replace_def = impl_py_method_def(
+        &parse_quote!(#cls),
+        &spec,
+        &utils::get_doc(&[], None),
+        None,
+        ctx,
+    )?;
+    let replace_impl = quote! {
+        #[doc(hidden)]
+        #[allow(non_snake_case)]
+        impl #cls {
+            #replace_impl
+        }
+    };
+    Ok((replace_impl, replace_def))
+}
+
+/// Registers the `.pyi` stubs of the class and of its `#[pyo3(get, set)]` fields.
+fn class_stubs(
+    cls: &syn::Ident,
+    args: &PyClassArgs,
+    field_options: &[(&syn::Field, FieldPyO3Options)],
+    ctx: &Ctx,
+) -> TokenStream {
+    if !cfg!(feature = "experimental-stubs") {
+        return TokenStream::new();
+    }
+    let class_name = get_class_python_name(cls, args).to_string();
+    let class = || Some(StubClass::Named(&class_name));
+    let mut stubs = vec![submit_stub(class(), "Class", &class_name, "", None, ctx)];
+    for (field, options) in field_options {
+        let name = match field_python_name(field, options, args) {
+            Some(name) => name,
+            // reported as an error by `descriptors_to_items`
+            None => continue,
+        };
+        let annotation = stub_return_annotation(&field.ty);
+        if options.get.is_some() {
+            stubs.push(submit_stub(
+                class(),
+                "Getter",
+                &name,
+                "(self)",
+                annotation.clone(),
+                ctx,
+            ));
+        }
+        if options.set.is_some() {
+            let parameters = match &annotation {
+                Some(annotation) => format!("(self, value: {})", annotation),
+                None => "(self, value)".to_owned(),
+            };
+            stubs.push(submit_stub(
+                class(),
+                "Setter",
+                &name,
+                &parameters,
+                Some("None".to_owned()),
+                ctx,
+            ));
+        }
+    }
+    quote! {
+        const _: () = {
+            #(#stubs)*
+        };
+    }
+}
+
+/// Generates the `__match_args__` class attribute for `#[pyclass(match_args)]`.
+///
+/// The names of the positional parameters of `#[new]` are only known to `#[pymethods]`, so they
+/// are looked up at runtime through the `PyClassNewMatchArgs` specialization.
+fn generate_match_args_attr(cls: &syn::Ident, ctx: &Ctx) -> MethodAndMethodDef {
+    let Ctx { pyo3_path } = ctx;
+    let associated_method = quote! {
+        fn __pymethod___match_args____(
+            py: #pyo3_path::Python<'_>,
+        ) -> #pyo3_path::PyResult<#pyo3_path::PyObject> {
+            use #pyo3_path::impl_::pyclass::*;
+            let collector = PyClassImplCollector::<Self>::new();
+            ::std::result::Result::Ok(
+                #pyo3_path::types::PyTuple::new_bound(py, collector.new_match_args())
+                    .into_any()
+                    .unbind(),
+            )
+        }
+    };
+    let method_def = quote! {
+        #pyo3_path::class::PyMethodDefType::ClassAttribute({
+            #pyo3_path::class::PyClassAttributeDef::new(
+                "__match_args__\0",
+                #pyo3_path::impl_::pymethods::PyClassAttributeFactory(#cls::__pymethod___match_args____)
+            )
+        })
+    };
+    MethodAndMethodDef {
+        associated_method,
+        method_def,
+    }
+}
+
+/// Generates `__richcmp__` and `__hash__` slots comparing and hashing the fields marked with
+/// `#[pyo3(eq_hash)]`, if there are any.
+fn generate_eq_hash_slots(
+    cls: &syn::Ident,
+    args: &PyClassArgs,
+    field_options: &[(&syn::Field, FieldPyO3Options)],
+    ctx: &Ctx,
+) -> syn::Result<(Option<TokenStream>, Vec<MethodAndSlotDef>)> {
+    let Ctx { pyo3_path } = ctx;
+    let eq_hash = match field_options
+        .iter()
+        .find_map(|(_, options)| options.eq_hash)
+    {
+        Some(eq_hash) => eq_hash,
+        None => return Ok((None, Vec::new())),
+    };
+    ensure_spanned!(
+        args.options.frozen.is_some(),
+        eq_hash.span() => "`eq_hash` requires the class to be `frozen`"
+    );
+
+    let fields: Vec<TokenStream> = field_options
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, options))| options.eq_hash.is_some())
+        .map(|(index, (field, _))| match &field.ident {
+            Some(ident) => quote!(#ident),
+            None => {
+                let index = syn::Index::from(index);
+                quote!(#index)
+            }
+        })
+        .collect();
+
+    let ty: syn::Type = syn::parse_quote!(#cls);
+    let mut richcmp_impl: syn::ImplItemFn = syn::parse_quote! {
+        fn __pyo3__richcmp__(
+            &self,
+            py: #pyo3_path::Python,
+            other: &#pyo3_path::Bound<'_, #pyo3_path::PyAny>,
+            op: #pyo3_path::basic::CompareOp
+        ) -> #pyo3_path::PyResult<#pyo3_path::PyObject> {
+            use #pyo3_path::conversion::ToPyObject;
+            use #pyo3_path::types::PyAnyMethods;
+            let other = match other.extract::<#pyo3_path::PyRef<Self>>() {
+                Ok(other) => other,
+                Err(_) => return Ok(py.NotImplemented()),
+            };
+            let eq = true #(&& self.#fields == other.#fields)*;
+            match op {
+                #pyo3_path::basic::CompareOp::Eq => Ok(eq.to_object(py)),
+                #pyo3_path::basic::CompareOp::Ne => Ok((!eq).to_object(py)),
+                _ => Ok(py.NotImplemented()),
+            }
+        }
+    };
+    let richcmp_slot = generate_default_protocol_slot(&ty, &mut richcmp_impl, &__RICHCMP__, ctx)?;
+
+    let mut hash_impl: syn::ImplItemFn = syn::parse_quote! {
+        fn __pyo3__hash__(&self) -> u64 {
+            use ::std::hash::{Hash, Hasher};
+            let mut hasher = ::std::collections::hash_map::DefaultHasher::new();
+            #(Hash::hash(&self.#fields, &mut hasher);)*
+            hasher.finish()
+        }
+    };
+    let hash_slot = generate_default_protocol_slot(&ty, &mut hash_impl, &__HASH__, ctx)?;
+
+    let eq_hash_impl = quote! {
+        #[doc(hidden)]
+        #[allow(non_snake_case)]
+        impl #cls {
+            #richcmp_impl
+            #hash_impl
+        }
+    };
+    Ok((Some(eq_hash_impl), vec![richcmp_slot, hash_slot]))
+}
+
+/// Generates a `__richcmp__` slot for `#[pyclass(eq)]`, which uses the `PartialEq` implementation
+/// of the type for `==` and `!=`, or `#[pyclass(ord)]`, which also uses `PartialOrd` for the
+/// ordering comparisons.
+///
+/// With `#[pyclass(compare_with = T)]`, an operand which is not an instance of the class is
+/// extracted as `T` and compared through `PartialEq<T>` and `PartialOrd<T>` instead.
+fn generate_richcmp_slot(
+    cls: &syn::Ident,
+    args: &PyClassArgs,
+    field_options: &[(&syn::Field, FieldPyO3Options)],
+    default_slots: &mut Vec<MethodAndSlotDef>,
+    ctx: &Ctx,
+) -> syn::Result<Option<TokenStream>> {
+    let Ctx { pyo3_path } = ctx;
+    let span = match (&args.options.eq, &args.options.ord) {
+        (_, Some(ord)) => ord.span(),
+        (Some(eq), None) => eq.span(),
+        (None, None) => {
+            if let Some(compare_with) = &args.options.compare_with {
+                bail_spanned!(compare_with.span() => "`compare_with` requires `eq` or `ord`");
+            }
+            return Ok(None);
+        }
+    };
+    if field_options
+        .iter()
+        .any(|(_, options)| options.eq_hash.is_some())
+    {
+        bail_spanned!(span => "`eq` and `ord` cannot be combined with `#[pyo3(eq_hash)]`");
+    }
+
+    let comparisons = |other: TokenStream| {
+        let ordering = if args.options.ord.is_some() {
+            quote_spanned! { span =>
+                #pyo3_path::basic::CompareOp::Lt => *self < #other,
+                #pyo3_path::basic::CompareOp::Le => *self <= #other,
+                #pyo3_path::basic::CompareOp::Gt => *self > #other,
+                #pyo3_path::basic::CompareOp::Ge => *self >= #other,
+            }
+        } else {
+            quote! { _ => return ::std::result::Result::Ok(py.NotImplemented()), }
+        };
+        quote_spanned! { span =>
+            match op {
+                #pyo3_path::basic::CompareOp::Eq => *self == #other,
+                #pyo3_path::basic::CompareOp::Ne => *self != #other,
+                #ordering
+            }
+        }
+    };
+    let compare_self = comparisons(quote!(*other));
+    let fallback = match &args.options.compare_with {
+        Some(compare_with) => {
+            let other_ty = &compare_with.value;
+            let compare_other = comparisons(quote!(other));
+            quote! {
+                if let Ok(other) = other.extract::<#other_ty>() {
+                    let result = #compare_other;
+                    return Ok(result.to_object(py));
+                }
+            }
+        }
+        None => quote!(),
+    };
+
+    let ty: syn::Type = syn::parse_quote!(#cls);
+    let mut richcmp_impl: syn::ImplItemFn = syn::parse_quote! {
+        fn __pyo3__richcmp__(
+            &self,
+            py: #pyo3_path::Python,
+            other: &#pyo3_path::Bound<'_, #pyo3_path::PyAny>,
+            op: #pyo3_path::basic::CompareOp
+        ) -> #pyo3_path::PyResult<#pyo3_path::PyObject> {
+            use #pyo3_path::conversion::ToPyObject;
+            use #pyo3_path::types::PyAnyMethods;
+            let other = match other.extract::<#pyo3_path::PyRef<Self>>() {
+                Ok(other) => other,
+                Err(_) => {
+                    #fallback
+                    return Ok(py.NotImplemented());
+                }
+            };
+            let result = #compare_self;
+            Ok(result.to_object(py))
+        }
+    };
+    default_slots.push(generate_default_protocol_slot(
+        &ty,
+        &mut richcmp_impl,
+        &__RICHCMP__,
+        ctx,
+    )?);
+
+    Ok(Some(quote! {
+        #[doc(hidden)]
+        #[allow(non_snake_case)]
+        impl #cls {
+            #richcmp_impl
+        }
+    }))
+}
+
+/// Generates a `__hash__` slot for `#[pyclass(hash)]`, which feeds the object to a
+/// `DefaultHasher` through the `Hash` implementation of the type.
+///
+/// The class must be `frozen`, so the hash cannot change, and use `eq` without `compare_with`, so
+/// that objects which compare equal are also hashed from the same Rust value.
+fn generate_hash_slot(
+    cls: &syn::Ident,
+    args: &PyClassArgs,
+    default_slots: &mut Vec<MethodAndSlotDef>,
+    ctx: &Ctx,
+) -> syn::Result<Option<TokenStream>> {
+    let hash = match args.options.hash {
+        Some(hash) => hash,
+        None => return Ok(None),
+    };
+    ensure_spanned!(
+        args.options.frozen.is_some(),
+        hash.span() => "`hash` requires the class to be `frozen`"
+    );
+    ensure_spanned!(
+        args.options.eq.is_some() || args.options.ord.is_some(),
+        hash.span() => "`hash` requires `eq`, so that equal objects have the same hash"
+    );
+    if let Some(compare_with) = &args.options.compare_with {
+        // e.g. `Meters(5.0) == 5.0`, but the hash of the instance is not `hash(5.0)`
+        bail_spanned!(
+            compare_with.span() => "`hash` cannot be combined with `compare_with`, because values which compare equal would not have the same hash"
+        );
+    }
+
+    let ty: syn::Type = syn::parse_quote!(#cls);
+    let hash_call = quote_spanned! { hash.span() =>
+        ::std::hash::Hash::hash(self, &mut hasher);
+    };
+    let mut hash_impl: syn::ImplItemFn = syn::parse_quote! {
+        fn __pyo3__hash__(&self) -> u64 {
+            use ::std::hash::Hasher;
+            let mut hasher = ::std::collections::hash_map::DefaultHasher::new();
+            #hash_call
+            hasher.finish()
+        }
+    };
+    default_slots.push(generate_default_protocol_slot(
+        &ty,
+        &mut hash_impl,
+        &__HASH__,
+        ctx,
+    )?);
+
+    Ok(Some(quote! {
+        #[doc(hidden)]
+        #[allow(non_snake_case)]
+        impl #cls {
+            #hash_impl
+        }
+    }))
+}
+
+/// Generates `__getbuffer__` and `__releasebuffer__` for `#[pyclass(buffer)]`, exporting the data
+/// of the class's `PyClassBuffer` implementation.
+fn generate_buffer_slots(
+    cls: &syn::Ident,
+    args: &PyClassArgs,
+    default_slots: &mut Vec<MethodAndSlotDef>,
+    ctx: &Ctx,
+) -> syn::Result<Option<TokenStream>> {
+    let Ctx { pyo3_path } = ctx;
+    if args.options.buffer.is_none() {
+        return Ok(None);
+    }
+
+    let ty: syn::Type = syn::parse_quote!(#cls);
+    let mut getbuffer_impl: syn::ImplItemFn = syn::parse_quote! {
+        unsafe fn __pyo3__getbuffer__(
+            slf: #pyo3_path::Bound<'_, Self>,
+            view: *mut #pyo3_path::ffi::Py_buffer,
+            flags: ::std::os::raw::c_int,
+        ) -> #pyo3_path::PyResult<()> {
+            #pyo3_path::impl_::pyclass::class_buffer_get(&slf, view, flags)
+        }
+    };
+    let mut releasebuffer_impl: syn::ImplItemFn = syn::parse_quote! {
+        unsafe fn __pyo3__releasebuffer__(
+            slf: #pyo3_path::Bound<'_, Self>,
+            view: *mut #pyo3_path::ffi::Py_buffer,
+        ) {
+            #pyo3_path::impl_::pyclass::class_buffer_release(&slf, view)
+        }
+    };
+    default_slots.push(generate_default_protocol_slot(
+        &ty,
+        &mut getbuffer_impl,
+        &__GETBUFFER__,
+        ctx,
+    )?);
+    default_slots.push(generate_default_protocol_slot(
+        &ty,
+        &mut releasebuffer_impl,
+        &__RELEASEBUFFER__,
+        ctx,
+    )?);
+
+    Ok(Some(quote! {
+        #[doc(hidden)]
+        #[allow(non_snake_case)]
+        impl #cls {
+            #getbuffer_impl
+            #releasebuffer_impl
+        }
+    }))
+}
+
+/// Generates `__iter__` (returning the object itself) and `__next__` (advancing the field) slots
+/// for the field marked with `#[pyo3(iter)]`, if there is one.
+fn generate_iter_slots(
+    cls: &syn::Ident,
+    args: &PyClassArgs,
+    field_options: &[(&syn::Field, FieldPyO3Options)],
+    default_slots: &mut Vec<MethodAndSlotDef>,
+    ctx: &Ctx,
+) -> syn::Result<Option<TokenStream>> {
+    let Ctx { pyo3_path } = ctx;
+    let mut iter_fields = field_options
+        .iter()
+        .enumerate()
+        .filter_map(|(index, (field, options))| options.iter.map(|iter| (index, field, iter)));
+    let (index, field, iter) = match iter_fields.next() {
+        Some(iter_field) => iter_field,
+        None => return Ok(None),
+    };
+    if let Some((_, _, other)) = iter_fields.next() {
+        bail_spanned!(other.span() => "`iter` may only be specified on one field");
+    }
+    ensure_spanned!(
+        args.options.frozen.is_none(),
+        iter.span() => "`iter` cannot be used with `frozen` classes, advancing the iterator needs mutable access"
+    );
+
+    let member = match &field.ident {
+        Some(ident) => quote!(#ident),
+        None => {
+            let index = syn::Index::from(index);
+            quote!(#index)
+        }
+    };
+    let field_ty = &field.ty;
+
+    let ty: syn::Type = syn::parse_quote!(#cls);
+    let mut iter_impl: syn::ImplItemFn = syn::parse_quote! {
+        fn __pyo3__iter__(slf: #pyo3_path::PyRef<'_, Self>) -> #pyo3_path::PyRef<'_, Self> {
+            slf
+        }
+    };
+    default_slots.push(generate_default_protocol_slot(
+        &ty,
+        &mut iter_impl,
+        &__ITER__,
+        ctx,
+    )?);
+    let mut next_impl: syn::ImplItemFn = syn::parse_quote_spanned! { iter.span() =>
+        fn __pyo3__next__(
+            mut slf: #pyo3_path::PyRefMut<'_, Self>,
+        ) -> ::std::option::Option<<#field_ty as ::std::iter::Iterator>::Item> {
+            ::std::iter::Iterator::next(&mut slf.#member)
+        }
+    };
+    default_slots.push(generate_default_protocol_slot(
+        &ty,
+        &mut next_impl,
+        &__NEXT__,
+        ctx,
+    )?);
+
+    Ok(Some(quote! {
+        #[doc(hidden)]
+        #[allow(non_snake_case)]
+        impl #cls {
+            #iter_impl
+            #next_impl
+        }
+    }))
+}
+
+/// Generates a `__str__` slot for `#[pyclass(str)]`, which uses the `Display` implementation of
+/// the type, or for `#[pyclass(str = "...")]`, which formats the named fields of a struct.
+///
+/// `fields` is `None` for enums, which only support the `Display` form.
+fn generate_str_slot(
+    cls: &syn::Ident,
+    args: &PyClassArgs,
+    fields: Option<&[&syn::Field]>,
+    default_slots: &mut Vec<MethodAndSlotDef>,
+    ctx: &Ctx,
+) -> syn::Result<Option<TokenStream>> {
+    let str = match &args.options.str {
+        Some(str) => str,
+        None => return Ok(None),
+    };
+
+    let to_string = match &str.value {
+        None => quote! { ::std::string::ToString::to_string(self) },
+        Some(fmt) => {
+            let fields = match fields {
+                Some(fields) => fields,
+                None => {
+                    bail_spanned!(fmt.span() => "`str = \"...\"` is not supported for enums, implement `Display` and use `str` instead")
+                }
+            };
+            let placeholders = format_string_placeholder_names(fmt)?;
+            let args = fields
+                .iter()
+                .filter_map(|field| field.ident.as_ref())
+                .filter(|ident| placeholders.contains(&ident.unraw().to_string()))
+                .map(|ident| quote! { #ident = &self.#ident });
+            quote! { ::std::format!(#fmt #(, #args)*) }
+        }
+    };
+
+    let ty: syn::Type = syn::parse_quote!(#cls);
+    let mut str_impl: syn::ImplItemFn = syn::parse_quote! {
+        fn __pyo3__str__(&self) -> ::std::string::String {
+            #to_string
+        }
+    };
+    default_slots.push(generate_default_protocol_slot(
+        &ty,
+        &mut str_impl,
+        &__STR__,
+        ctx,
+    )?);
+
+    Ok(Some(quote! {
+        #[doc(hidden)]
+        #[allow(non_snake_case)]
+        impl #cls {
+            #str_impl
+        }
+    }))
+}
+
+/// Returns the argument names used by the `{name}` and `{name:spec}` placeholders of a format
+/// string, including the `name$` width and precision arguments of the spec.
+///
+/// Positional placeholders such as `{}`, `{0}` or `{:.*}` have no field to refer to, so they are
+/// rejected.
+fn format_string_placeholder_names(fmt: &syn::LitStr) -> syn::Result<Vec<String>> {
+    fn push_name(
+        names: &mut Vec<String>,
+        name: &str,
+        placeholder: &str,
+        fmt: &syn::LitStr,
+    ) -> syn::Result<()> {
+        let is_ident = matches!(name.chars().next(), Some(c) if c == '_' || c.is_alphabetic());
+        ensure_spanned!(
+            is_ident && name != "_",
+            fmt.span() => format!(
+                "positional argument in `{{{}}}` is not supported in `str = \"...\"`, use the name of a field instead",
+                placeholder
+            )
+        );
+        if !names.iter().any(|n| n == name) {
+            names.push(name.to_owned());
+        }
+        Ok(())
+    }
+
+    let mut names = Vec::new();
+    let value = fmt.value();
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '{' {
+            continue;
+        }
+        if chars.peek() == Some(&'{') {
+            // `{{` is an escaped brace
+            chars.next();
+            continue;
+        }
+        let placeholder: String = chars.by_ref().take_while(|&c| c != '}').collect();
+        let (name, spec) = match placeholder.find(':') {
+            Some(index) => (&placeholder[..index], &placeholder[index + 1..]),
+            None => (placeholder.as_str(), ""),
+        };
+        push_name(&mut names, name.trim(), &placeholder, fmt)?;
+        // Skip the fill character, which may be a `$`
+        let spec = match spec.char_indices().nth(1) {
+            Some((index, '<' | '^' | '>')) => &spec[index..],
+            _ => spec,
+        };
+        if spec.contains(".*") {
+            push_name(&mut names, "", &placeholder, fmt)?;
+        }
+        // The width and precision can name an argument as `name$`
+        for (index, _) in spec.match_indices('$') {
+            let start = spec[..index]
+                .rfind(|c: char| !(c == '_' || c.is_alphanumeric()))
+                .map_or(0, |i| i + 1);
+            push_name(&mut names, &spec[start..index], &placeholder, fmt)?;
+        }
     }
+    Ok(names)
 }
 
-fn get_class_python_name<'a>(cls: &'a syn::Ident, args: &'a PyClassArgs) -> Cow<'a, syn::Ident> {
-    args.options
-        .name
-        .as_ref()
-        .map(|name_attr| Cow::Borrowed(&name_attr.value.0))
-        .unwrap_or_else(|| Cow::Owned(cls.unraw()))
-}
-
-fn impl_class(
+/// Generates a `__repr__` slot for `#[pyclass(repr)]`, which formats the fields with a getter or
+/// marked with `#[pyo3(repr)]` as `ClassName(field=value, ...)`.
+fn generate_repr_slot(
     cls: &syn::Ident,
     args: &PyClassArgs,
-    doc: PythonDoc,
-    field_options: Vec<(&syn::Field, FieldPyO3Options)>,
-    methods_type: PyClassMethodsType,
+    field_options: &[(&syn::Field, FieldPyO3Options)],
+    default_slots: &mut Vec<MethodAndSlotDef>,
     ctx: &Ctx,
-) -> syn::Result<TokenStream> {
+) -> syn::Result<Option<TokenStream>> {
     let Ctx { pyo3_path } = ctx;
-    let pytypeinfo_impl = impl_pytypeinfo(cls, args, None, ctx);
-
-    let py_class_impl = PyClassImplsBuilder::new(
-        cls,
-        args,
-        methods_type,
-        descriptors_to_items(
-            cls,
-            args.options.rename_all.as_ref(),
-            args.options.frozen,
-            field_options,
-            ctx,
-        )?,
-        vec![],
-    )
-    .doc(doc)
-    .impl_all(ctx)?;
+    if args.options.repr.is_none() {
+        if let Some(repr) = field_options.iter().find_map(|(_, options)| options.repr) {
+            bail_spanned!(repr.span() => "`#[pyo3(repr)]` on a field requires `#[pyclass(repr)]`");
+        }
+        return Ok(None);
+    }
 
-    Ok(quote! {
-        impl #pyo3_path::types::DerefToPyAny for #cls {}
+    let renaming_rule = args.options.rename_all.as_ref().map(|attr| attr.value.rule);
+    let fields = field_options
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, options))| options.get.is_some() || options.repr.is_some())
+        .map(|(index, (field, options))| {
+            if options.get.is_some() {
+                // read through the getter so that the value matches what Python code sees
+                let name = match (&options.name, &field.ident) {
+                    (Some(name), _) => name.value.0.to_string(),
+                    (None, Some(ident)) => {
+                        let name = ident.unraw().to_string();
+                        match renaming_rule {
+                            Some(rule) => apply_renaming_rule(rule, &name),
+                            None => name,
+                        }
+                    }
+                    (None, None) => {
+                        bail_spanned!(field.span() => "`get` and `set` with tuple struct fields require `name`")
+                    }
+                };
+                Ok(quote! {
+                    (
+                        ::std::option::Option::Some(#name),
+                        #pyo3_path::types::PyAnyMethods::getattr(slf.as_any(), #name)?,
+                    )
+                })
+            } else {
+                let (label, member) = match &field.ident {
+                    Some(ident) => {
+                        let label = ident.unraw().to_string();
+                        (quote!(::std::option::Option::Some(#label)), quote!(#ident))
+                    }
+                    None => {
+                        let index = syn::Index::from(index);
+                        (quote!(::std::option::Option::None), quote!(#index))
+                    }
+                };
+                let value = quote_spanned! { field.ty.span() =>
+                    #pyo3_path::ToPyObject::to_object(&slf.try_borrow()?.#member, slf.py())
+                };
+                Ok(quote! { (#label, #value.into_bound(slf.py())) })
+            }
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
 
-        #pytypeinfo_impl
+    let ty: syn::Type = syn::parse_quote!(#cls);
+    let mut repr_impl: syn::ImplItemFn = syn::parse_quote! {
+        fn __pyo3__repr__(
+            slf: &#pyo3_path::Bound<'_, Self>,
+        ) -> #pyo3_path::PyResult<::std::string::String> {
+            let fields = ::std::vec![#(#fields),*];
+            #pyo3_path::impl_::pyclass::repr_fields(slf.as_any(), &fields)
+        }
+    };
+    default_slots.push(generate_default_protocol_slot(
+        &ty,
+        &mut repr_impl,
+        &__REPR__,
+        ctx,
+    )?);
 
-        #py_class_impl
-    })
+    Ok(Some(quote! {
+        #[doc(hidden)]
+        #[allow(non_snake_case)]
+        impl #cls {
+            #repr_impl
+        }
+    }))
 }
 
 enum PyClassEnum<'a> {
@@ -396,6 +1435,51 @@ impl<'a> PyClassEnum<'a> {
     }
 }
 
+/// Generates the `__class_getitem__` classmethod for `#[pyclass(subscriptable)]`, so that the
+/// class can be parametrized in type annotations like builtin containers.
+fn generate_default_class_getitem_method(
+    cls: &syn::Ident,
+    ctx: &Ctx,
+) -> syn::Result<(TokenStream, MethodAndMethodDef)> {
+    let Ctx { pyo3_path } = ctx;
+    let mut class_getitem_impl: syn::ImplItemFn = parse_quote! {
+        fn __pyo3__class_getitem__<'py>(
+            cls: &#pyo3_path::Bound<'py, #pyo3_path::types::PyType>,
+            item: &#pyo3_path::Bound<'py, #pyo3_path::PyAny>,
+        ) -> #pyo3_path::PyResult<#pyo3_path::Bound<'py, #pyo3_path::PyAny>> {
+            #pyo3_path::impl_::pyclass::class_getitem(cls, item)
+        }
+    };
+    let options = PyFunctionOptions {
+        name: Some(NameAttribute {
+            kw: parse_quote! { name },
+            value: NameLitStr(format_ident!("__class_getitem__")),
+        }),
+        ..Default::default()
+    };
+    let spec = FnSpec::parse(
+        &mut class_getitem_impl.sig,
+        &mut vec![parse_quote!(#[classmethod])],
+        options,
+        ctx,
+    )?;
+    let class_getitem_def = impl_py_method_def(
+        &parse_quote!(#cls),
+        &spec,
+        &utils::get_doc(&[], None),
+        Some(quote!(#pyo3_path::ffi::METH_CLASS)),
+        ctx,
+    )?;
+    let class_getitem_impl = quote! {
+        #[doc(hidden)]
+        #[allow(non_snake_case)]
+        impl #cls {
+            #class_getitem_impl
+        }
+    };
+    Ok((class_getitem_impl, class_getitem_def))
+}
+
 pub fn build_py_enum(
     enum_: &mut syn::ItemEnum,
     mut args: PyClassArgs,
@@ -408,11 +1492,35 @@ pub fn build_py_enum(
         bail_spanned!(extends.span() => "enums can't extend from other classes");
     } else if let Some(subclass) = &args.options.subclass {
         bail_spanned!(subclass.span() => "enums can't be inherited by other classes");
+    } else if let Some(buffer) = &args.options.buffer {
+        bail_spanned!(buffer.span() => "`buffer` is not supported for enums");
+    } else if let Some(clone) = &args.options.clone {
+        bail_spanned!(clone.span() => "`clone` is not supported for enums");
+    } else if let Some(replace) = &args.options.replace {
+        bail_spanned!(replace.span() => "`replace` is not supported for enums");
+    } else if let Some(getnewargs) = &args.options.getnewargs {
+        bail_spanned!(getnewargs.span() => "`getnewargs` is not supported for enums");
+    } else if let Some(subscriptable) = &args.options.subscriptable {
+        bail_spanned!(subscriptable.span() => "`subscriptable` is not supported for enums");
+    } else if let Some(context_manager) = &args.options.context_manager {
+        bail_spanned!(context_manager.span() => "`context_manager` is not supported for enums");
+    } else if let Some(repr) = &args.options.repr {
+        bail_spanned!(repr.span() => "`repr` is not supported for enums");
+    } else if let Some(match_args) = &args.options.match_args {
+        bail_spanned!(match_args.span() => "`match_args` is not supported for enums");
+    } else if let Some(eq) = &args.options.eq {
+        bail_spanned!(eq.span() => "`eq` is not supported for enums, which are already comparable");
+    } else if let Some(ord) = &args.options.ord {
+        bail_spanned!(ord.span() => "`ord` is not supported for enums, which are already comparable");
+    } else if let Some(compare_with) = &args.options.compare_with {
+        bail_spanned!(compare_with.span() => "`compare_with` is not supported for enums");
+    } else if let Some(hash) = &args.options.hash {
+        bail_spanned!(hash.span() => "`hash` is not supported for enums");
     } else if enum_.variants.is_empty() {
         bail_spanned!(enum_.brace_token.span.join() => "#[pyclass] can't be used on enums without any variants");
     }
 
-    let doc = utils::get_doc(&enum_.attrs, None);
+    let doc = get_class_doc(&enum_.attrs, &args.options);
     let enum_ = PyClassEnum::new(enum_)?;
     impl_enum(enum_, &args, doc, method_type, ctx)
 }
@@ -525,12 +1633,21 @@ impl<'a> PyClassComplexEnum<'a> {
                             options,
                         })
                     }
-                    Fields::Unnamed(_) => {
-                        bail_spanned!(variant.span() => format!(
-                        "Tuple variant `{ident}` is not yet supported in a complex enum\n\
-                        = help: change to a struct variant with named fields: `{ident} {{ /* fields */ }}`\n\
-                        = note: the enum is complex because of non-unit variant `{witness}`",
-                        ident=ident, witness=witness))
+                    Fields::Unnamed(fields) => {
+                        let fields = fields
+                            .unnamed
+                            .iter()
+                            .map(|field| PyClassEnumVariantUnnamedField {
+                                ty: &field.ty,
+                                span: field.span(),
+                            })
+                            .collect();
+
+                        PyClassEnumVariant::Tuple(PyClassEnumTupleVariant {
+                            ident,
+                            fields,
+                            options,
+                        })
                     }
                 };
 
@@ -552,7 +1669,7 @@ impl<'a> PyClassComplexEnum<'a> {
 enum PyClassEnumVariant<'a> {
     // TODO(mkovaxx): Unit(PyClassEnumUnitVariant<'a>),
     Struct(PyClassEnumStructVariant<'a>),
-    // TODO(mkovaxx): Tuple(PyClassEnumTupleVariant<'a>),
+    Tuple(PyClassEnumTupleVariant<'a>),
 }
 
 trait EnumVariant {
@@ -580,12 +1697,14 @@ impl<'a> EnumVariant for PyClassEnumVariant<'a> {
     fn get_ident(&self) -> &syn::Ident {
         match self {
             PyClassEnumVariant::Struct(struct_variant) => struct_variant.ident,
+            PyClassEnumVariant::Tuple(tuple_variant) => tuple_variant.ident,
         }
     }
 
     fn get_options(&self) -> &EnumVariantPyO3Options {
         match self {
             PyClassEnumVariant::Struct(struct_variant) => &struct_variant.options,
+            PyClassEnumVariant::Tuple(tuple_variant) => &tuple_variant.options,
         }
     }
 }
@@ -619,6 +1738,27 @@ struct PyClassEnumVariantNamedField<'a> {
     span: Span,
 }
 
+/// A tuple variant has unnamed fields
+struct PyClassEnumTupleVariant<'a> {
+    ident: &'a syn::Ident,
+    fields: Vec<PyClassEnumVariantUnnamedField<'a>>,
+    options: EnumVariantPyO3Options,
+}
+
+struct PyClassEnumVariantUnnamedField<'a> {
+    ty: &'a syn::Type,
+    span: Span,
+}
+
+impl<'a> PyClassEnumTupleVariant<'a> {
+    /// The fields of a tuple variant are exposed to Python as `_0`, `_1`, etc.
+    fn field_names(&self) -> Vec<Ident> {
+        (0..self.fields.len())
+            .map(|index| format_ident!("_{}", index))
+            .collect()
+    }
+}
+
 /// `#[pyo3()]` options for pyclass enum variants
 struct EnumVariantPyO3Options {
     name: Option<NameAttribute>,
@@ -774,7 +1914,8 @@ fn impl_simple_enum(
         (richcmp_impl, richcmp_slot)
     };
 
-    let default_slots = vec![default_repr_slot, default_int_slot, default_richcmp_slot];
+    let mut default_slots = vec![default_repr_slot, default_int_slot, default_richcmp_slot];
+    let str_impl = generate_str_slot(cls, args, None, &mut default_slots, ctx)?;
 
     let pyclass_impls = PyClassImplsBuilder::new(
         cls,
@@ -790,6 +1931,8 @@ fn impl_simple_enum(
     .doc(doc)
     .impl_all(ctx)?;
 
+    let stubs = simple_enum_stubs(cls, args, &variants, ctx);
+
     Ok(quote! {
         #pytypeinfo
 
@@ -802,9 +1945,44 @@ fn impl_simple_enum(
             #default_int
             #default_richcmp
         }
+
+        #str_impl
+
+        #stubs
     })
 }
 
+/// Registers the `.pyi` stubs of the enum class and of its variants, which are class attributes.
+fn simple_enum_stubs(
+    cls: &syn::Ident,
+    args: &PyClassArgs,
+    variants: &[PyClassEnumUnitVariant<'_>],
+    ctx: &Ctx,
+) -> TokenStream {
+    if !cfg!(feature = "experimental-stubs") {
+        return TokenStream::new();
+    }
+    let class_name = get_class_python_name(cls, args).to_string();
+    let class = || Some(StubClass::Named(&class_name));
+    let class_stub = submit_stub(class(), "Class", &class_name, "", None, ctx);
+    let variant_stubs = variants.iter().map(|variant| {
+        submit_stub(
+            class(),
+            "ClassAttribute",
+            &variant.get_python_name(args).to_string(),
+            "",
+            Some(class_name.clone()),
+            ctx,
+        )
+    });
+    quote! {
+        const _: () = {
+            #class_stub
+            #(#variant_stubs)*
+        };
+    }
+}
+
 fn impl_complex_enum(
     complex_enum: PyClassComplexEnum<'_>,
     args: &PyClassArgs,
@@ -829,7 +2007,8 @@ fn impl_complex_enum(
     let variants = complex_enum.variants;
     let pytypeinfo = impl_pytypeinfo(cls, &args, None, ctx);
 
-    let default_slots = vec![];
+    let mut default_slots = vec![];
+    let str_impl = generate_str_slot(cls, &args, None, &mut default_slots, ctx)?;
 
     let impl_builder = PyClassImplsBuilder::new(
         cls,
@@ -899,11 +2078,21 @@ fn impl_complex_enum(
         };
         variant_cls_zsts.push(variant_cls_zst);
 
-        let variant_args = PyClassArgs {
+        let mut variant_args = PyClassArgs {
             class_kind: PyClassKind::Struct,
             // TODO(mkovaxx): propagate variant.options
             options: parse_quote!(extends = #cls, frozen),
         };
+        // a renamed variant's class is named after the variant in Python, e.g.
+        // `Shape.CIRCLE.__name__` is `"CIRCLE"`, and lives in the same module as the enum
+        if variant.get_options().name.is_some() || args.options.rename_all.is_some() {
+            let variant_python_name = variant.get_python_name(&args).into_owned();
+            variant_args.options.name = Some(NameAttribute {
+                kw: parse_quote! { name },
+                value: NameLitStr(variant_python_name),
+            });
+            variant_args.options.module = args.options.module.clone();
+        }
 
         let variant_cls_pytypeinfo = impl_pytypeinfo(&variant_cls, &variant_args, None, ctx);
         variant_cls_pytypeinfos.push(variant_cls_pytypeinfo);
@@ -934,6 +2123,8 @@ fn impl_complex_enum(
         #[allow(non_snake_case)]
         impl #cls {}
 
+        #str_impl
+
         #(#variant_cls_zsts)*
 
         #(#variant_cls_pytypeinfos)*
@@ -953,6 +2144,9 @@ fn impl_complex_enum_variant_cls(
         PyClassEnumVariant::Struct(struct_variant) => {
             impl_complex_enum_struct_variant_cls(enum_name, struct_variant, ctx)
         }
+        PyClassEnumVariant::Tuple(tuple_variant) => {
+            impl_complex_enum_tuple_variant_cls(enum_name, tuple_variant, ctx)
+        }
     }
 }
 
@@ -1014,10 +2208,218 @@ fn impl_complex_enum_struct_variant_cls(
     Ok((cls_impl, field_getters))
 }
 
+fn impl_complex_enum_tuple_variant_cls(
+    enum_name: &syn::Ident,
+    variant: &PyClassEnumTupleVariant<'_>,
+    ctx: &Ctx,
+) -> Result<(TokenStream, Vec<MethodAndMethodDef>)> {
+    let Ctx { pyo3_path } = ctx;
+    let variant_ident = &variant.ident;
+    let variant_cls = gen_complex_enum_variant_class_ident(enum_name, variant.ident);
+    let variant_cls_type = parse_quote!(#variant_cls);
+
+    let field_names = variant.field_names();
+    let mut fields_with_types: Vec<TokenStream> = vec![];
+    let mut field_getters = vec![];
+    let mut field_getter_impls: Vec<TokenStream> = vec![];
+    for (index, (field, field_name)) in variant.fields.iter().zip(&field_names).enumerate() {
+        let field_type = field.ty;
+        let field_with_type = quote! { #field_name: #field_type };
+
+        let field_getter = complex_enum_variant_field_getter(
+            &variant_cls_type,
+            field_name,
+            field_type,
+            field.span,
+            ctx,
+        )?;
+
+        let index = syn::Index::from(index);
+        let field_getter_impl = quote! {
+            fn #field_name(slf: #pyo3_path::PyRef<Self>) -> #pyo3_path::PyResult<#field_type> {
+                match &*slf.into_super() {
+                    #enum_name::#variant_ident { #index: value, .. } => Ok(value.clone()),
+                    _ => unreachable!("Wrong complex enum variant found in variant wrapper PyClass"),
+                }
+            }
+        };
+
+        fields_with_types.push(field_with_type);
+        field_getters.push(field_getter);
+        field_getter_impls.push(field_getter_impl);
+    }
+
+    let cls_impl = quote! {
+        #[doc(hidden)]
+        #[allow(non_snake_case, clippy::used_underscore_binding)]
+        impl #variant_cls {
+            fn __pymethod_constructor__(py: #pyo3_path::Python<'_>, #(#fields_with_types,)*) -> #pyo3_path::PyClassInitializer<#variant_cls> {
+                let base_value = #enum_name::#variant_ident ( #(#field_names,)* );
+                #pyo3_path::PyClassInitializer::from(base_value).add_subclass(#variant_cls)
+            }
+
+            #(#field_getter_impls)*
+        }
+    };
+
+    Ok((cls_impl, field_getters))
+}
+
 fn gen_complex_enum_variant_class_ident(enum_: &syn::Ident, variant: &syn::Ident) -> syn::Ident {
     format_ident!("{}_{}", enum_, variant)
 }
 
+/// Generates `__copy__` and `clone` for `#[pyclass(clone)]`, returning a new object built with
+/// `Clone`.
+fn generate_default_copy_methods(
+    cls: &syn::Ident,
+    ctx: &Ctx,
+) -> syn::Result<(TokenStream, Vec<MethodAndMethodDef>)> {
+    let mut impls = Vec::new();
+    let mut defs = Vec::new();
+    for (python_name, rust_name) in [("__copy__", "__pyo3__copy__"), ("clone", "__pyo3__clone__")] {
+        let rust_name = format_ident!("{}", rust_name);
+        let mut copy_impl: syn::ImplItemFn = parse_quote! {
+            fn #rust_name(&self) -> Self {
+                ::std::clone::Clone::clone(self)
+            }
+        };
+        let options = PyFunctionOptions {
+            name: Some(NameAttribute {
+                kw: parse_quote! { name },
+                value: NameLitStr(format_ident!("{}", python_name)),
+            }),
+            ..Default::default()
+        };
+        let spec = FnSpec::parse(&mut copy_impl.sig, &mut Vec::new(), options, ctx)?;
+        defs.push(impl_py_method_def(
+            &parse_quote!(#cls),
+            &spec,
+            &utils::get_doc(&[], None),
+            None,
+            ctx,
+        )?);
+        impls.push(copy_impl);
+    }
+    let copy_impl = quote! {
+        #[doc(hidden)]
+        #[allow(non_snake_case)]
+        impl #cls {
+            #(#impls)*
+        }
+    };
+    Ok((copy_impl, defs))
+}
+
+/// Generates `__getnewargs__` for `#[pyclass(getnewargs)]`, returning the attributes named after
+/// the positional parameters of `#[new]`, so that `pickle` and `copy` can call `__new__` with them.
+/// `#[new]` fails to compile if it has other parameters, as they would be lost.
+fn generate_default_getnewargs_method(
+    cls: &syn::Ident,
+    ctx: &Ctx,
+) -> syn::Result<(TokenStream, MethodAndMethodDef)> {
+    let Ctx { pyo3_path } = ctx;
+    let mut getnewargs_impl: syn::ImplItemFn = parse_quote! {
+        fn __pyo3__getnewargs__<'py>(
+            slf: &#pyo3_path::Bound<'py, Self>,
+        ) -> #pyo3_path::PyResult<#pyo3_path::Bound<'py, #pyo3_path::types::PyTuple>> {
+            use #pyo3_path::impl_::pyclass::*;
+            use #pyo3_path::types::PyAnyMethods;
+            let collector = PyClassImplCollector::<Self>::new();
+            let args = collector
+                .new_match_args()
+                .iter()
+                .map(|name| slf.as_any().getattr(*name))
+                .collect::<#pyo3_path::PyResult<::std::vec::Vec<_>>>()?;
+            ::std::result::Result::Ok(#pyo3_path::types::PyTuple::new_bound(slf.py(), args))
+        }
+    };
+    let options = PyFunctionOptions {
+        name: Some(NameAttribute {
+            kw: parse_quote! { name },
+            value: NameLitStr(format_ident!("__getnewargs__")),
+        }),
+        ..Default::default()
+    };
+    let spec = FnSpec::parse(&mut getnewargs_impl.sig, &mut Vec::new(), options, ctx)?;
+    let getnewargs_def = impl_py_method_def(
+        &parse_quote!(#cls),
+        &spec,
+        &utils::get_doc(&[], None),
+        None,
+        ctx,
+    )?;
+    let getnewargs_impl = quote! {
+        #[doc(hidden)]
+        #[allow(non_snake_case)]
+        impl #cls {
+            #getnewargs_impl
+        }
+    };
+    Ok((getnewargs_impl, getnewargs_def))
+}
+
+/// Generates `__enter__` and `__exit__` for `#[pyclass(context_manager)]`; `__exit__` is routed
+/// to the class's `PyContextManager` implementation.
+fn generate_default_context_manager_methods(
+    cls: &syn::Ident,
+    ctx: &Ctx,
+) -> syn::Result<(TokenStream, Vec<MethodAndMethodDef>)> {
+    let Ctx { pyo3_path } = ctx;
+    let mut enter_impl: syn::ImplItemFn = parse_quote! {
+        fn __pyo3__enter__(slf: #pyo3_path::Bound<'_, Self>) -> #pyo3_path::Bound<'_, Self> {
+            slf
+        }
+    };
+    let mut exit_impl: syn::ImplItemFn = parse_quote! {
+        fn __pyo3__exit__(
+            slf: &#pyo3_path::Bound<'_, Self>,
+            _exc_type: &#pyo3_path::Bound<'_, #pyo3_path::PyAny>,
+            exc_value: ::std::option::Option<
+                #pyo3_path::Bound<'_, #pyo3_path::exceptions::PyBaseException>,
+            >,
+            _traceback: &#pyo3_path::Bound<'_, #pyo3_path::PyAny>,
+        ) -> #pyo3_path::PyResult<bool> {
+            <Self as #pyo3_path::pyclass::PyContextManager>::exit(slf, exc_value.as_ref())
+        }
+    };
+    let mut defs = Vec::new();
+    for (method, python_name, signature) in [
+        (&mut enter_impl, "__enter__", None),
+        (
+            &mut exit_impl,
+            "__exit__",
+            Some(parse_quote! { signature = (_exc_type, exc_value, _traceback) }),
+        ),
+    ] {
+        let options = PyFunctionOptions {
+            name: Some(NameAttribute {
+                kw: parse_quote! { name },
+                value: NameLitStr(format_ident!("{}", python_name)),
+            }),
+            signature,
+            ..Default::default()
+        };
+        let spec = FnSpec::parse(&mut method.sig, &mut Vec::new(), options, ctx)?;
+        defs.push(impl_py_method_def(
+            &parse_quote!(#cls),
+            &spec,
+            &utils::get_doc(&[], None),
+            None,
+            ctx,
+        )?);
+    }
+    let context_manager_impl = quote! {
+        #[doc(hidden)]
+        #[allow(non_snake_case)]
+        impl #cls {
+            #enter_impl
+            #exit_impl
+        }
+    };
+    Ok((context_manager_impl, defs))
+}
+
 fn generate_default_protocol_slot(
     cls: &syn::Type,
     method: &mut syn::ImplItemFn,
@@ -1129,19 +2531,37 @@ fn complex_enum_variant_new<'a>(
     ctx: &Ctx,
 ) -> Result<MethodAndSlotDef> {
     match variant {
-        PyClassEnumVariant::Struct(struct_variant) => {
-            complex_enum_struct_variant_new(cls, struct_variant, ctx)
+        PyClassEnumVariant::Struct(struct_variant) => complex_enum_variant_new_from_fields(
+            cls,
+            struct_variant.ident,
+            struct_variant
+                .fields
+                .iter()
+                .map(|field| (field.ident, field.ty)),
+            ctx,
+        ),
+        PyClassEnumVariant::Tuple(tuple_variant) => {
+            let field_names = tuple_variant.field_names();
+            complex_enum_variant_new_from_fields(
+                cls,
+                tuple_variant.ident,
+                field_names
+                    .iter()
+                    .zip(tuple_variant.fields.iter().map(|field| field.ty)),
+                ctx,
+            )
         }
     }
 }
 
-fn complex_enum_struct_variant_new<'a>(
-    cls: &'a syn::Ident,
-    variant: &'a PyClassEnumStructVariant<'a>,
+fn complex_enum_variant_new_from_fields<'a>(
+    cls: &syn::Ident,
+    variant_ident: &syn::Ident,
+    fields: impl Iterator<Item = (&'a syn::Ident, &'a syn::Type)>,
     ctx: &Ctx,
 ) -> Result<MethodAndSlotDef> {
     let Ctx { pyo3_path } = ctx;
-    let variant_cls = format_ident!("{}_{}", cls, variant.ident);
+    let variant_cls = format_ident!("{}_{}", cls, variant_ident);
     let variant_cls_type: syn::Type = parse_quote!(#variant_cls);
 
     let arg_py_ident: syn::Ident = parse_quote!(py);
@@ -1166,10 +2586,10 @@ fn complex_enum_struct_variant_new<'a>(
             },
         ];
 
-        for field in &variant.fields {
+        for (name, ty) in fields {
             args.push(FnArg {
-                name: field.ident,
-                ty: field.ty,
+                name,
+                ty,
                 optional: None,
                 default: None,
                 py: false,
@@ -1193,6 +2613,8 @@ fn complex_enum_struct_variant_new<'a>(
         text_signature: None,
         asyncness: None,
         unsafety: None,
+        allow_threads: None,
+        unpack: None,
         deprecations: Deprecations::new(ctx),
     };
 
@@ -1220,6 +2642,8 @@ fn complex_enum_variant_field_getter<'a>(
         text_signature: None,
         asyncness: None,
         unsafety: None,
+        allow_threads: None,
+        unpack: None,
         deprecations: Deprecations::new(ctx),
     };
 
@@ -1242,16 +2666,22 @@ fn descriptors_to_items(
 ) -> syn::Result<Vec<MethodAndMethodDef>> {
     let ty = syn::parse_quote!(#cls);
     let mut items = Vec::new();
+    let mut cache_indices = 0..;
     for (field_index, (field, options)) in field_options.into_iter().enumerate() {
         if let FieldPyO3Options {
             name: Some(name),
             get: None,
             set: None,
+            ..
         } = options
         {
             return Err(syn::Error::new_spanned(name, USELESS_NAME));
         }
 
+        if let Some(cache) = options.cache {
+            ensure_spanned!(options.get.is_some(), cache.span() => USELESS_CACHE);
+        }
+
         if options.get.is_some() {
             let getter = impl_py_getter_def(
                 &ty,
@@ -1260,6 +2690,8 @@ fn descriptors_to_items(
                     field,
                     python_name: options.name.as_ref(),
                     renaming_rule: rename_all.map(|rename_all| rename_all.value.rule),
+                    cache_index: options.cache.and_then(|_| cache_indices.next()),
+                    set_validator: None,
                 },
                 ctx,
             )?;
@@ -1275,6 +2707,8 @@ fn descriptors_to_items(
                     field,
                     python_name: options.name.as_ref(),
                     renaming_rule: rename_all.map(|rename_all| rename_all.value.rule),
+                    cache_index: None,
+                    set_validator: options.set_validator.as_ref(),
                 },
                 ctx,
             )?;
@@ -1334,6 +2768,7 @@ struct PyClassImplsBuilder<'a> {
     default_methods: Vec<MethodAndMethodDef>,
     default_slots: Vec<MethodAndSlotDef>,
     doc: Option<PythonDoc>,
+    getter_cache_size: usize,
 }
 
 impl<'a> PyClassImplsBuilder<'a> {
@@ -1351,6 +2786,7 @@ impl<'a> PyClassImplsBuilder<'a> {
             default_methods,
             default_slots,
             doc: None,
+            getter_cache_size: 0,
         }
     }
 
@@ -1361,6 +2797,13 @@ impl<'a> PyClassImplsBuilder<'a> {
         }
     }
 
+    fn getter_cache_size(self, getter_cache_size: usize) -> Self {
+        Self {
+            getter_cache_size,
+            ..self
+        }
+    }
+
     fn impl_all(&self, ctx: &Ctx) -> Result<TokenStream> {
         let tokens = [
             self.impl_pyclass(ctx),
@@ -1454,11 +2897,12 @@ impl<'a> PyClassImplsBuilder<'a> {
         let doc = self.doc.as_ref().map_or(quote! {"\0"}, |doc| quote! {#doc});
         let is_basetype = self.attr.options.subclass.is_some();
         let base = match &self.attr.options.extends {
-            Some(extends_attr) => extends_attr.value.clone(),
+            Some(extends_attr) => extends_attr.value.base.clone(),
             None => parse_quote! { #pyo3_path::PyAny },
         };
         let is_subclass = self.attr.options.extends.is_some();
         let is_mapping: bool = self.attr.options.mapping.is_some();
+        let has_getnewargs = self.attr.options.getnewargs.is_some();
         let is_sequence: bool = self.attr.options.sequence.is_some();
 
         ensure_spanned!(
@@ -1476,6 +2920,43 @@ impl<'a> PyClassImplsBuilder<'a> {
             TokenStream::new()
         };
 
+        let mixins = self
+            .attr
+            .options
+            .extends
+            .as_ref()
+            .map_or(&[][..], |extends| &extends.value.mixins[..]);
+        let mixins = if mixins.is_empty() {
+            TokenStream::new()
+        } else {
+            quote! {
+                fn mixins(py: #pyo3_path::Python<'_>) -> ::std::vec::Vec<*mut #pyo3_path::ffi::PyTypeObject> {
+                    ::std::vec![#(<#mixins as #pyo3_path::PyTypeInfo>::type_object_raw(py)),*]
+                }
+            }
+        };
+
+        let instance_counter = self.attr.options.track_instances.map(|_| {
+            quote! {
+                fn instance_counter() -> ::std::option::Option<&'static ::std::sync::atomic::AtomicUsize> {
+                    static INSTANCES: ::std::sync::atomic::AtomicUsize = ::std::sync::atomic::AtomicUsize::new(0);
+                    ::std::option::Option::Some(&INSTANCES)
+                }
+            }
+        });
+
+        let on_first_init = self.attr.options.on_first_init.as_ref().map(|on_first_init| {
+            let hook = &on_first_init.value;
+            quote! {
+                fn on_first_init(py: #pyo3_path::Python<'_>) -> #pyo3_path::PyResult<()> {
+                    static INITIALIZED: #pyo3_path::sync::GILOnceCell<()> = #pyo3_path::sync::GILOnceCell::new();
+                    INITIALIZED.get_or_try_init(py, || {
+                        #hook(&<#cls as #pyo3_path::PyTypeInfo>::type_object_bound(py))
+                    }).map(|_| ())
+                }
+            }
+        });
+
         // insert space for weak ref
         let weaklist_offset = if self.attr.options.weakref.is_some() {
             quote! {
@@ -1555,6 +3036,14 @@ impl<'a> PyClassImplsBuilder<'a> {
             quote! { #pyo3_path::impl_::pyclass::PyClassDummySlot }
         };
 
+        // insert space for `#[pyo3(get, cache)]` values
+        let getter_cache = if self.getter_cache_size > 0 {
+            let size = proc_macro2::Literal::usize_unsuffixed(self.getter_cache_size);
+            quote! { #pyo3_path::impl_::pyclass::PyClassGetterCacheSlot<#size> }
+        } else {
+            quote! { #pyo3_path::impl_::pyclass::PyClassDummySlot }
+        };
+
         let base_nativetype = if attr.options.extends.is_some() {
             quote! { <Self::BaseType as #pyo3_path::impl_::pyclass::PyClassBaseType>::BaseNativeType }
         } else {
@@ -1567,6 +3056,7 @@ impl<'a> PyClassImplsBuilder<'a> {
                 const IS_SUBCLASS: bool = #is_subclass;
                 const IS_MAPPING: bool = #is_mapping;
                 const IS_SEQUENCE: bool = #is_sequence;
+                const HAS_GETNEWARGS: bool = #has_getnewargs;
 
                 type BaseType = #base;
                 type ThreadChecker = #thread_checker;
@@ -1574,6 +3064,7 @@ impl<'a> PyClassImplsBuilder<'a> {
                 type PyClassMutability = <<#base as #pyo3_path::impl_::pyclass::PyClassBaseType>::PyClassMutability as #pyo3_path::impl_::pycell::PyClassMutability>::#class_mutability;
                 type Dict = #dict;
                 type WeakRef = #weakref;
+                type GetterCache = #getter_cache;
                 type BaseNativeType = #base_nativetype;
 
                 fn items_iter() -> #pyo3_path::impl_::pyclass::PyClassItemsIter {
@@ -1599,6 +3090,12 @@ impl<'a> PyClassImplsBuilder<'a> {
 
                 #weaklist_offset
 
+                #mixins
+
+                #instance_counter
+
+                #on_first_init
+
                 fn lazy_type_object() -> &'static #pyo3_path::impl_::pyclass::LazyTypeObject<Self> {
                     use #pyo3_path::impl_::pyclass::LazyTypeObject;
                     static TYPE_OBJECT: LazyTypeObject<#cls> = LazyTypeObject::new();
@@ -1704,6 +3201,10 @@ fn define_inventory_class(inventory_class_name: &syn::Ident, ctx: &Ctx) -> Token
 const UNIQUE_GET: &str = "`get` may only be specified once";
 const UNIQUE_SET: &str = "`set` may only be specified once";
 const UNIQUE_NAME: &str = "`name` may only be specified once";
+const UNIQUE_CACHE: &str = "`cache` may only be specified once";
+const UNIQUE_EQ_HASH: &str = "`eq_hash` may only be specified once";
+const UNIQUE_ITER: &str = "`iter` may only be specified once";
+const UNIQUE_REPR: &str = "`repr` may only be specified once";
 
 const DUPE_SET: &str = "useless `set` - the struct is already annotated with `set_all`";
 const DUPE_GET: &str = "useless `get` - the struct is already annotated with `get_all`";
@@ -1713,3 +3214,4 @@ const UNIT_SET: &str =
     "`set_all` on an unit struct does nothing, because unit structs have no fields";
 
 const USELESS_NAME: &str = "`name` is useless without `get` or `set`";
+const USELESS_CACHE: &str = "`cache` is useless without `get`";
@@ -2,7 +2,8 @@ use crate::utils::Ctx;
 use crate::{
     attributes::{
         self, get_pyo3_options, take_attributes, take_pyo3_options, CrateAttribute,
-        FromPyWithAttribute, NameAttribute, TextSignatureAttribute,
+        FromPyWithAttribute, MaxArgsAttribute, MinArgsAttribute, NameAttribute,
+        TextSignatureAttribute, ValidateAttribute,
     },
     deprecations::Deprecations,
     method::{self, CallingConvention, FnArg},
@@ -17,18 +18,26 @@ use syn::{
 };
 
 mod signature;
+mod signature_object;
 
 pub use self::signature::{FunctionSignature, SignatureAttribute};
+pub use self::signature_object::impl_signature_object;
 
 #[derive(Clone, Debug)]
 pub struct PyFunctionArgPyO3Attributes {
     pub from_py_with: Option<FromPyWithAttribute>,
     pub cancel_handle: Option<attributes::kw::cancel_handle>,
+    pub kwargs_struct: Option<attributes::kw::kwargs_struct>,
+    pub validate: Option<ValidateAttribute>,
+    pub strict: Option<attributes::kw::strict>,
 }
 
 enum PyFunctionArgPyO3Attribute {
     FromPyWith(FromPyWithAttribute),
     CancelHandle(attributes::kw::cancel_handle),
+    KwargsStruct(attributes::kw::kwargs_struct),
+    Validate(ValidateAttribute),
+    Strict(attributes::kw::strict),
 }
 
 impl Parse for PyFunctionArgPyO3Attribute {
@@ -38,6 +47,12 @@ impl Parse for PyFunctionArgPyO3Attribute {
             input.parse().map(PyFunctionArgPyO3Attribute::CancelHandle)
         } else if lookahead.peek(attributes::kw::from_py_with) {
             input.parse().map(PyFunctionArgPyO3Attribute::FromPyWith)
+        } else if lookahead.peek(attributes::kw::kwargs_struct) {
+            input.parse().map(PyFunctionArgPyO3Attribute::KwargsStruct)
+        } else if lookahead.peek(attributes::kw::strict) {
+            input.parse().map(PyFunctionArgPyO3Attribute::Strict)
+        } else if lookahead.peek(attributes::kw::validate) {
+            input.parse().map(PyFunctionArgPyO3Attribute::Validate)
         } else {
             Err(lookahead.error())
         }
@@ -50,6 +65,9 @@ impl PyFunctionArgPyO3Attributes {
         let mut attributes = PyFunctionArgPyO3Attributes {
             from_py_with: None,
             cancel_handle: None,
+            kwargs_struct: None,
+            validate: None,
+            strict: None,
         };
         take_attributes(attrs, |attr| {
             if let Some(pyo3_attrs) = get_pyo3_options(attr)? {
@@ -69,11 +87,44 @@ impl PyFunctionArgPyO3Attributes {
                             );
                             attributes.cancel_handle = Some(cancel_handle);
                         }
+                        PyFunctionArgPyO3Attribute::KwargsStruct(kwargs_struct) => {
+                            ensure_spanned!(
+                                attributes.kwargs_struct.is_none(),
+                                kwargs_struct.span() => "`kwargs_struct` may only be specified once per argument"
+                            );
+                            attributes.kwargs_struct = Some(kwargs_struct);
+                        }
+                        PyFunctionArgPyO3Attribute::Validate(validate) => {
+                            ensure_spanned!(
+                                attributes.validate.is_none(),
+                                validate.span() => "`validate` may only be specified once per argument"
+                            );
+                            attributes.validate = Some(validate);
+                        }
+                        PyFunctionArgPyO3Attribute::Strict(strict) => {
+                            ensure_spanned!(
+                                attributes.strict.is_none(),
+                                strict.span() => "`strict` may only be specified once per argument"
+                            );
+                            attributes.strict = Some(strict);
+                        }
                     }
                     ensure_spanned!(
                         attributes.from_py_with.is_none() || attributes.cancel_handle.is_none(),
                         attributes.cancel_handle.unwrap().span() => "`from_py_with` and `cancel_handle` cannot be specified together"
                     );
+                    ensure_spanned!(
+                        attributes.validate.is_none() || attributes.cancel_handle.is_none(),
+                        attributes.validate.as_ref().unwrap().span() => "`validate` and `cancel_handle` cannot be specified together"
+                    );
+                    ensure_spanned!(
+                        attributes.kwargs_struct.is_none() || (attributes.from_py_with.is_none() && attributes.cancel_handle.is_none()),
+                        attributes.kwargs_struct.unwrap().span() => "`kwargs_struct` cannot be combined with `from_py_with` or `cancel_handle`"
+                    );
+                    ensure_spanned!(
+                        attributes.strict.is_none() || (attributes.from_py_with.is_none() && attributes.cancel_handle.is_none() && attributes.kwargs_struct.is_none()),
+                        attributes.strict.unwrap().span() => "`strict` cannot be combined with `from_py_with`, `cancel_handle` or `kwargs_struct`"
+                    );
                 }
                 Ok(true)
             } else {
@@ -86,11 +137,16 @@ impl PyFunctionArgPyO3Attributes {
 
 #[derive(Default)]
 pub struct PyFunctionOptions {
+    pub allow_threads: Option<attributes::kw::allow_threads>,
     pub pass_module: Option<attributes::kw::pass_module>,
     pub name: Option<NameAttribute>,
     pub signature: Option<SignatureAttribute>,
     pub text_signature: Option<TextSignatureAttribute>,
     pub krate: Option<CrateAttribute>,
+    pub min_args: Option<MinArgsAttribute>,
+    pub max_args: Option<MaxArgsAttribute>,
+    pub signature_object: Option<attributes::kw::signature_object>,
+    pub unpack: Option<attributes::kw::unpack>,
 }
 
 impl Parse for PyFunctionOptions {
@@ -100,9 +156,14 @@ impl Parse for PyFunctionOptions {
         while !input.is_empty() {
             let lookahead = input.lookahead1();
             if lookahead.peek(attributes::kw::name)
+                || lookahead.peek(attributes::kw::allow_threads)
                 || lookahead.peek(attributes::kw::pass_module)
                 || lookahead.peek(attributes::kw::signature)
                 || lookahead.peek(attributes::kw::text_signature)
+                || lookahead.peek(attributes::kw::min_args)
+                || lookahead.peek(attributes::kw::max_args)
+                || lookahead.peek(attributes::kw::signature_object)
+                || lookahead.peek(attributes::kw::unpack)
             {
                 options.add_attributes(std::iter::once(input.parse()?))?;
                 if !input.is_empty() {
@@ -121,11 +182,16 @@ impl Parse for PyFunctionOptions {
 }
 
 pub enum PyFunctionOption {
+    AllowThreads(attributes::kw::allow_threads),
     Name(NameAttribute),
     PassModule(attributes::kw::pass_module),
     Signature(SignatureAttribute),
     TextSignature(TextSignatureAttribute),
     Crate(CrateAttribute),
+    MinArgs(MinArgsAttribute),
+    MaxArgs(MaxArgsAttribute),
+    SignatureObject(attributes::kw::signature_object),
+    Unpack(attributes::kw::unpack),
 }
 
 impl Parse for PyFunctionOption {
@@ -133,6 +199,8 @@ impl Parse for PyFunctionOption {
         let lookahead = input.lookahead1();
         if lookahead.peek(attributes::kw::name) {
             input.parse().map(PyFunctionOption::Name)
+        } else if lookahead.peek(attributes::kw::allow_threads) {
+            input.parse().map(PyFunctionOption::AllowThreads)
         } else if lookahead.peek(attributes::kw::pass_module) {
             input.parse().map(PyFunctionOption::PassModule)
         } else if lookahead.peek(attributes::kw::signature) {
@@ -141,6 +209,14 @@ impl Parse for PyFunctionOption {
             input.parse().map(PyFunctionOption::TextSignature)
         } else if lookahead.peek(syn::Token![crate]) {
             input.parse().map(PyFunctionOption::Crate)
+        } else if lookahead.peek(attributes::kw::min_args) {
+            input.parse().map(PyFunctionOption::MinArgs)
+        } else if lookahead.peek(attributes::kw::max_args) {
+            input.parse().map(PyFunctionOption::MaxArgs)
+        } else if lookahead.peek(attributes::kw::signature_object) {
+            input.parse().map(PyFunctionOption::SignatureObject)
+        } else if lookahead.peek(attributes::kw::unpack) {
+            input.parse().map(PyFunctionOption::Unpack)
         } else {
             Err(lookahead.error())
         }
@@ -171,11 +247,18 @@ impl PyFunctionOptions {
         }
         for attr in attrs {
             match attr {
+                PyFunctionOption::AllowThreads(allow_threads) => set_option!(allow_threads),
                 PyFunctionOption::Name(name) => set_option!(name),
                 PyFunctionOption::PassModule(pass_module) => set_option!(pass_module),
                 PyFunctionOption::Signature(signature) => set_option!(signature),
                 PyFunctionOption::TextSignature(text_signature) => set_option!(text_signature),
                 PyFunctionOption::Crate(krate) => set_option!(krate),
+                PyFunctionOption::MinArgs(min_args) => set_option!(min_args),
+                PyFunctionOption::MaxArgs(max_args) => set_option!(max_args),
+                PyFunctionOption::SignatureObject(signature_object) => {
+                    set_option!(signature_object)
+                }
+                PyFunctionOption::Unpack(unpack) => set_option!(unpack),
             }
         }
         Ok(())
@@ -198,11 +281,16 @@ pub fn impl_wrap_pyfunction(
 ) -> syn::Result<TokenStream> {
     check_generic(&func.sig)?;
     let PyFunctionOptions {
+        allow_threads,
         pass_module,
         name,
         signature,
         text_signature,
         krate,
+        min_args,
+        max_args,
+        signature_object,
+        unpack,
     } = options;
 
     let ctx = &Ctx::new(&krate);
@@ -234,24 +322,41 @@ pub fn impl_wrap_pyfunction(
         .map(FnArg::parse)
         .collect::<syn::Result<Vec<_>>>()?;
 
-    let signature = if let Some(signature) = signature {
+    let mut signature = if let Some(signature) = signature {
         FunctionSignature::from_arguments_and_attribute(arguments, signature)?
     } else {
         FunctionSignature::from_arguments(arguments)?
     };
+    signature.set_varargs_arity(min_args, max_args)?;
 
     let ty = method::get_return_info(&func.sig.output);
 
+    // the return value of an `async fn` is only available by awaiting the returned coroutine
+    let output = if func.sig.asyncness.is_none() {
+        Some(&func.sig.output)
+    } else {
+        None
+    };
+    let signature_object = signature_object
+        .map(|_| signature_object::impl_signature_object(&signature, None, output, ctx));
+
     let spec = method::FnSpec {
         tp,
         name: &func.sig.ident,
-        convention: CallingConvention::from_signature(&signature),
+        // the packed arguments are always passed as a tuple
+        convention: if unpack.is_some() {
+            CallingConvention::Varargs
+        } else {
+            CallingConvention::from_signature(&signature)
+        },
         python_name,
         signature,
         output: ty,
         text_signature,
         asyncness: func.sig.asyncness,
         unsafety: func.sig.unsafety,
+        allow_threads,
+        unpack,
         deprecations: Deprecations::new(ctx),
     };
 
@@ -260,7 +365,37 @@ pub fn impl_wrap_pyfunction(
 
     let wrapper_ident = format_ident!("__pyfunction_{}", spec.name);
     let wrapper = spec.get_wrapper_function(&wrapper_ident, None, ctx)?;
+    let stub = crate::stub::fn_stub(&spec, None, &spec.python_name.to_string(), ctx);
     let methoddef = spec.get_methoddef(wrapper_ident, &spec.get_doc(&func.attrs), ctx);
+    let (def_ty, methoddef, add_to_module, signature_object) = match signature_object {
+        Some(signature_object) => (
+            quote!(#pyo3_path::impl_::signature_function::PySignatureFunctionDef),
+            quote! {
+                #pyo3_path::impl_::signature_function::PySignatureFunctionDef::new(
+                    #methoddef,
+                    #pyo3_path::impl_::pymethods::PySignatureObjectFactory(Self::signature_object),
+                )
+            },
+            quote!(#pyo3_path::impl_::signature_function::add_to_module(module, &DEF)),
+            quote! {
+                fn signature_object(
+                    py: #pyo3_path::Python<'_>,
+                ) -> #pyo3_path::PyResult<#pyo3_path::Bound<'_, #pyo3_path::PyAny>> {
+                    #signature_object
+                }
+            },
+        ),
+        None => (
+            quote!(#pyo3_path::impl_::pymethods::PyMethodDef),
+            methoddef,
+            quote! {
+                use #pyo3_path::prelude::PyModuleMethods;
+                use ::std::convert::Into;
+                module.add_function(#pyo3_path::types::PyCFunction::internal_new(module.py(), &DEF, module.into())?)
+            },
+            quote!(),
+        ),
+    };
 
     let wrapped_pyfunction = quote! {
 
@@ -269,12 +404,10 @@ pub fn impl_wrap_pyfunction(
         #[doc(hidden)]
         #vis mod #name {
             pub(crate) struct MakeDef;
-            pub const DEF: #pyo3_path::impl_::pymethods::PyMethodDef = MakeDef::DEF;
+            pub const DEF: #def_ty = MakeDef::DEF;
 
             pub fn add_to_module(module: &#pyo3_path::Bound<'_, #pyo3_path::types::PyModule>) -> #pyo3_path::PyResult<()> {
-                use #pyo3_path::prelude::PyModuleMethods;
-                use ::std::convert::Into;
-                module.add_function(#pyo3_path::types::PyCFunction::internal_new(module.py(), &DEF, module.into())?)
+                #add_to_module
             }
         }
 
@@ -283,11 +416,23 @@ pub fn impl_wrap_pyfunction(
         // (and `super` doesn't always refer to the outer scope, e.g. if the `#[pyfunction] is
         // inside a function body)
         impl #name::MakeDef {
-            const DEF: #pyo3_path::impl_::pymethods::PyMethodDef = #methoddef;
+            const DEF: #def_ty = #methoddef;
+            #signature_object
         }
 
         #[allow(non_snake_case)]
         #wrapper
+
+        #stub
     };
     Ok(wrapped_pyfunction)
 }
+
+/// The `.pyi` return annotation of a function returning `output`, if it is known.
+pub fn stub_return_annotation(output: &syn::Type) -> Option<String> {
+    match output {
+        // no return type, i.e. `()`
+        syn::Type::Infer(_) => Some("None".to_owned()),
+        output => signature_object::return_type_annotation(output),
+    }
+}
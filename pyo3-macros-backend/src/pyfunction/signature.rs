@@ -9,7 +9,7 @@ use syn::{
 };
 
 use crate::{
-    attributes::{kw, KeywordAttribute},
+    attributes::{kw, KeywordAttribute, MaxArgsAttribute, MinArgsAttribute},
     method::FnArg,
 };
 
@@ -205,6 +205,9 @@ pub struct PythonSignature {
     // Tuples of keyword name and whether it is required
     pub keyword_only_parameters: Vec<(String, bool)>,
     pub kwargs: Option<String>,
+    // Bounds on the number of arguments collected into `*args`
+    pub min_varargs: Option<usize>,
+    pub max_varargs: Option<usize>,
 }
 
 impl PythonSignature {
@@ -391,6 +394,10 @@ impl<'a> FunctionSignature<'a> {
             match item {
                 SignatureItem::Argument(arg) => {
                     let fn_arg = next_non_py_argument_checked(&arg.ident)?;
+                    ensure_spanned!(
+                        !fn_arg.is_kwargs,
+                        arg.span() => "`kwargs_struct` arguments must be specified as `**kwargs` in the signature"
+                    );
                     parse_state.add_argument(
                         &mut python_signature,
                         arg.ident.unraw().to_string(),
@@ -398,7 +405,7 @@ impl<'a> FunctionSignature<'a> {
                         arg.span(),
                     )?;
                     if let Some((_, default)) = &arg.eq_and_default {
-                        fn_arg.default = Some(default.clone());
+                        fn_arg.default = Some(normalize_python_bool(default));
                     }
                 }
                 SignatureItem::VarargsSep(sep) => {
@@ -434,6 +441,45 @@ impl<'a> FunctionSignature<'a> {
         })
     }
 
+    /// Applies `#[pyo3(min_args = ..., max_args = ...)]`, which bound the number of arguments
+    /// collected into `*args`.
+    pub fn set_varargs_arity(
+        &mut self,
+        min_args: Option<MinArgsAttribute>,
+        max_args: Option<MaxArgsAttribute>,
+    ) -> syn::Result<()> {
+        for span in min_args
+            .as_ref()
+            .map(|attr| attr.kw.span())
+            .into_iter()
+            .chain(max_args.as_ref().map(|attr| attr.kw.span()))
+        {
+            ensure_spanned!(
+                self.python_signature.varargs.is_some(),
+                span => "`min_args` and `max_args` require a `*args` argument in the signature"
+            );
+        }
+        let min_varargs = min_args
+            .map(|attr| attr.value.base10_parse::<usize>())
+            .transpose()?;
+        let max_varargs = max_args
+            .map(|attr| {
+                attr.value
+                    .base10_parse::<usize>()
+                    .map(|max| (max, attr.value))
+            })
+            .transpose()?;
+        if let (Some(min), Some((max, max_lit))) = (min_varargs, &max_varargs) {
+            ensure_spanned!(
+                min <= *max,
+                max_lit.span() => "`max_args` must not be less than `min_args`"
+            );
+        }
+        self.python_signature.min_varargs = min_varargs;
+        self.python_signature.max_varargs = max_varargs.map(|(max, _)| max);
+        Ok(())
+    }
+
     /// Without `#[pyo3(signature)]` or `#[args]` - just take the Rust function arguments as positional.
     pub fn from_arguments(arguments: Vec<FnArg<'a>>) -> syn::Result<Self> {
         let mut python_signature = PythonSignature::default();
@@ -443,6 +489,16 @@ impl<'a> FunctionSignature<'a> {
                 continue;
             }
 
+            if arg.is_kwargs {
+                // `#[pyo3(kwargs_struct)]` arguments collect the `**kwargs`
+                ensure_spanned!(
+                    python_signature.kwargs.is_none(),
+                    arg.name.span() => "`kwargs_struct` may only be specified on one argument"
+                );
+                python_signature.kwargs = Some(arg.name.unraw().to_string());
+                continue;
+            }
+
             if arg.optional.is_none() {
                 // This argument is required, all previous arguments must also have been required
                 ensure_spanned!(
@@ -467,7 +523,7 @@ impl<'a> FunctionSignature<'a> {
         })
     }
 
-    fn default_value_for_parameter(&self, parameter: &str) -> String {
+    pub(super) fn default_value_for_parameter(&self, parameter: &str) -> String {
         let mut default = "...".to_string();
         if let Some(fn_arg) = self.arguments.iter().find(|arg| arg.name == parameter) {
             if let Some(arg_default) = fn_arg.default.as_ref() {
@@ -506,67 +562,98 @@ impl<'a> FunctionSignature<'a> {
     }
 
     pub fn text_signature(&self, self_argument: Option<&str>) -> String {
-        let mut output = String::new();
-        output.push('(');
-
-        if let Some(arg) = self_argument {
-            output.push('$');
-            output.push_str(arg);
-        }
+        let self_argument = self_argument.map(|arg| format!("${}", arg));
+        self.render_parameters(self_argument, |name, default| match default {
+            Some(default) => format!("{}={}", name, default),
+            None => name.to_string(),
+        })
+    }
 
-        let mut maybe_push_comma = {
-            let mut first = self_argument.is_none();
-            move |output: &mut String| {
-                if !first {
-                    output.push_str(", ");
-                } else {
-                    first = false;
+    /// The parameter list of this function in a `.pyi` stub, annotated with the Python types
+    /// of the Rust arguments where they are known.
+    pub fn stub_parameters(&self, self_argument: Option<&str>) -> String {
+        let annotation = |name: &str| {
+            self.arguments
+                .iter()
+                .find(|arg| arg.name == name && arg.attrs.from_py_with.is_none())
+                .and_then(|arg| super::signature_object::annotation(arg.ty))
+        };
+        self.render_parameters(self_argument.map(String::from), |name, default| {
+            match (annotation(name), default) {
+                (Some(annotation), Some(default)) => {
+                    format!("{}: {} = {}", name, annotation, default)
                 }
+                (Some(annotation), None) => format!("{}: {}", name, annotation),
+                (None, Some(default)) => format!("{}={}", name, default),
+                (None, None) => name.to_string(),
             }
-        };
+        })
+    }
 
+    /// Renders the Python parameter list, formatting each named parameter (with its default
+    /// value, if it has one) using `parameter`.
+    fn render_parameters(
+        &self,
+        self_argument: Option<String>,
+        mut parameter: impl FnMut(&str, Option<String>) -> String,
+    ) -> String {
         let py_sig = &self.python_signature;
+        let mut parameters: Vec<String> = self_argument.into_iter().collect();
 
-        for (i, parameter) in py_sig.positional_parameters.iter().enumerate() {
-            maybe_push_comma(&mut output);
-
-            output.push_str(parameter);
-
-            if i >= py_sig.required_positional_parameters {
-                output.push('=');
-                output.push_str(&self.default_value_for_parameter(parameter));
-            }
+        for (i, name) in py_sig.positional_parameters.iter().enumerate() {
+            let default = if i >= py_sig.required_positional_parameters {
+                Some(self.default_value_for_parameter(name))
+            } else {
+                None
+            };
+            parameters.push(parameter(name, default));
 
             if py_sig.positional_only_parameters > 0 && i + 1 == py_sig.positional_only_parameters {
-                output.push_str(", /")
+                parameters.push("/".to_string());
             }
         }
 
         if let Some(varargs) = &py_sig.varargs {
-            maybe_push_comma(&mut output);
-            output.push('*');
-            output.push_str(varargs);
+            parameters.push(format!("*{}", varargs));
         } else if !py_sig.keyword_only_parameters.is_empty() {
-            maybe_push_comma(&mut output);
-            output.push('*');
+            parameters.push("*".to_string());
         }
 
-        for (parameter, required) in &py_sig.keyword_only_parameters {
-            maybe_push_comma(&mut output);
-            output.push_str(parameter);
-            if !required {
-                output.push('=');
-                output.push_str(&self.default_value_for_parameter(parameter));
-            }
+        for (name, required) in &py_sig.keyword_only_parameters {
+            let default = if *required {
+                None
+            } else {
+                Some(self.default_value_for_parameter(name))
+            };
+            parameters.push(parameter(name, default));
         }
 
         if let Some(kwargs) = &py_sig.kwargs {
-            maybe_push_comma(&mut output);
-            output.push_str("**");
-            output.push_str(kwargs);
+            parameters.push(format!("**{}", kwargs));
         }
 
-        output.push(')');
-        output
+        format!("({})", parameters.join(", "))
+    }
+}
+
+/// Accepts the Python spellings `True` and `False` as default values, e.g. `flag = True`, by
+/// replacing them with the equivalent Rust literal.
+fn normalize_python_bool(default: &syn::Expr) -> syn::Expr {
+    if let syn::Expr::Path(syn::ExprPath {
+        qself: None, path, ..
+    }) = default
+    {
+        let value = if path.is_ident("True") {
+            true
+        } else if path.is_ident("False") {
+            false
+        } else {
+            return default.clone();
+        };
+        return syn::Expr::Lit(syn::ExprLit {
+            attrs: Vec::new(),
+            lit: syn::Lit::Bool(syn::LitBool::new(value, path.span())),
+        });
     }
+    default.clone()
 }
@@ -0,0 +1,245 @@
+//! Code generation for `#[pyo3(signature_object)]`, which exposes an `inspect.Signature` with
+//! the real default values and annotations derived from the Rust argument types.
+
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+use crate::method::FnArg;
+use crate::quotes::some_wrap;
+use crate::utils::{erase_lifetimes, Ctx};
+
+use super::FunctionSignature;
+
+/// Generates the body of the `fn(Python<'py>) -> PyResult<Bound<'py, PyAny>>` building the
+/// function's `__signature__`. `output` is `None` if the return annotation should be omitted, and
+/// `self_argument` names the leading positional-only parameter of a method.
+pub fn impl_signature_object(
+    signature: &FunctionSignature<'_>,
+    self_argument: Option<&str>,
+    output: Option<&syn::ReturnType>,
+    ctx: &Ctx,
+) -> TokenStream {
+    let Ctx { pyo3_path } = ctx;
+    let py_sig = &signature.python_signature;
+    let parameter = |name: &str, kind: &str, has_default: bool| {
+        let arg = signature.arguments.iter().find(|arg| arg.name == name);
+        let default = arg
+            .filter(|_| has_default)
+            .map(|arg| default_value(arg, ctx));
+        parameter(name, kind, arg, default, ctx)
+    };
+
+    let mut parameters = Vec::new();
+    if let Some(self_argument) = self_argument {
+        parameters.push(parameter(self_argument, "POSITIONAL_ONLY", false));
+    }
+    for (i, name) in py_sig.positional_parameters.iter().enumerate() {
+        let kind = if i < py_sig.positional_only_parameters {
+            "POSITIONAL_ONLY"
+        } else {
+            "POSITIONAL_OR_KEYWORD"
+        };
+        let required = i < py_sig.required_positional_parameters;
+        parameters.push(parameter(name, kind, !required));
+    }
+    if let Some(name) = &py_sig.varargs {
+        parameters.push(parameter(name, "VAR_POSITIONAL", false));
+    }
+    for (name, required) in &py_sig.keyword_only_parameters {
+        parameters.push(parameter(name, "KEYWORD_ONLY", !required));
+    }
+    if let Some(name) = &py_sig.kwargs {
+        parameters.push(parameter(name, "VAR_KEYWORD", false));
+    }
+
+    let return_annotation = option_tokens(output.and_then(return_annotation));
+    quote! {
+        #pyo3_path::impl_::signature_function::signature_object(
+            py,
+            ::std::vec![#(#parameters),*],
+            #return_annotation,
+        )
+    }
+}
+
+/// The Python value of the default of `arg`: the Rust default expression, evaluated and
+/// converted with `IntoPy` when the signature is built.
+fn default_value(arg: &FnArg<'_>, ctx: &Ctx) -> TokenStream {
+    let Ctx { pyo3_path } = ctx;
+    // as in the argument extraction, the default of an `Option<T>` is given without `Some`
+    let value = match (&arg.default, arg.optional) {
+        (Some(default), Some(_)) => some_wrap(quote!(#default), ctx),
+        (Some(default), None) => quote!(#default),
+        (None, _) => quote!(::std::option::Option::None),
+    };
+    let ty = erase_lifetimes(arg.ty.to_token_stream());
+    quote! {{
+        let default: #ty = #value;
+        #pyo3_path::IntoPy::<#pyo3_path::PyObject>::into_py(default, py)
+    }}
+}
+
+/// `default` evaluates to the `PyObject` of the default value.
+fn parameter(
+    name: &str,
+    kind: &str,
+    arg: Option<&FnArg<'_>>,
+    default: Option<TokenStream>,
+    ctx: &Ctx,
+) -> TokenStream {
+    let Ctx { pyo3_path } = ctx;
+    let annotation = arg
+        .filter(|arg| arg.attrs.from_py_with.is_none())
+        .and_then(|arg| annotation(arg.ty));
+    let annotation = option_tokens(annotation);
+    let default = match default {
+        Some(default) => quote!(::std::option::Option::Some(#default)),
+        None => quote!(::std::option::Option::None),
+    };
+    quote! {
+        #pyo3_path::impl_::signature_function::SignatureParameter {
+            name: #name,
+            kind: #kind,
+            default: #default,
+            annotation: #annotation,
+        }
+    }
+}
+
+fn option_tokens(value: Option<String>) -> TokenStream {
+    match value {
+        Some(value) => quote!(::std::option::Option::Some(#value)),
+        None => quote!(::std::option::Option::None),
+    }
+}
+
+fn return_annotation(output: &syn::ReturnType) -> Option<String> {
+    match output {
+        syn::ReturnType::Default => Some("None".to_string()),
+        syn::ReturnType::Type(_, ty) => return_type_annotation(ty),
+    }
+}
+
+/// The Python annotation for the value returned by a function with the Rust return type `ty`,
+/// which may be wrapped in a `Result`.
+pub(super) fn return_type_annotation(ty: &syn::Type) -> Option<String> {
+    match generic_args(ty, &["PyResult", "Result"]) {
+        Some(args) => args.first().and_then(|ty| annotation(ty)),
+        None => annotation(ty),
+    }
+}
+
+/// The Python annotation for a Rust argument or return type, as a Python expression which may
+/// refer to the `typing` module. `None` for types without a known Python equivalent.
+pub(super) fn annotation(ty: &syn::Type) -> Option<String> {
+    match ty {
+        syn::Type::Group(group) => annotation(&group.elem),
+        syn::Type::Paren(paren) => annotation(&paren.elem),
+        syn::Type::Reference(reference) => match &*reference.elem {
+            syn::Type::Slice(slice) if is_u8(&slice.elem) => Some("bytes".to_string()),
+            elem => annotation(elem),
+        },
+        syn::Type::Tuple(tuple) if tuple.elems.is_empty() => Some("None".to_string()),
+        syn::Type::Tuple(tuple) => Some(format!(
+            "typing.Tuple[{}]",
+            tuple
+                .elems
+                .iter()
+                .map(nested_annotation)
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+        syn::Type::Path(path) if path.qself.is_none() => {
+            let segment = path.path.segments.last()?;
+            let args = type_args(segment);
+            let simple = match segment.ident.to_string().as_str() {
+                "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64"
+                | "u128" | "usize" | "BigInt" | "BigUint" | "PyLong" => "int",
+                "f32" | "f64" | "PyFloat" => "float",
+                "bool" | "PyBool" => "bool",
+                "String" | "str" | "char" | "PyString" => "str",
+                "PyBytes" => "bytes",
+                "PyByteArray" => "bytearray",
+                "PyAny" | "PyObject" => "typing.Any",
+                "PyList" => "list",
+                "PyDict" => "dict",
+                "PyTuple" => "tuple",
+                "PySet" => "set",
+                "PyFrozenSet" => "frozenset",
+                "Option" => {
+                    return Some(format!("typing.Optional[{}]", nested_first(&args)));
+                }
+                "Vec" => return Some(format!("typing.List[{}]", nested_first(&args))),
+                "HashSet" | "BTreeSet" => {
+                    return Some(format!("typing.Set[{}]", nested_first(&args)));
+                }
+                "HashMap" | "BTreeMap" | "IndexMap" => {
+                    let key = nested_first(&args);
+                    let value = args
+                        .get(1)
+                        .map_or_else(|| "typing.Any".to_string(), |ty| nested_annotation(ty));
+                    return Some(format!("typing.Dict[{}, {}]", key, value));
+                }
+                "Cow" => {
+                    return match args.first() {
+                        Some(syn::Type::Slice(slice)) if is_u8(&slice.elem) => {
+                            Some("bytes".to_string())
+                        }
+                        Some(ty) => annotation(ty),
+                        None => None,
+                    };
+                }
+                // smart pointers are annotated with the type they point to
+                "Bound" | "Borrowed" | "Py" | "PyRef" | "PyRefMut" => {
+                    return args.last().and_then(|ty| annotation(ty));
+                }
+                _ => return None,
+            };
+            Some(simple.to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Annotation for a type nested inside a container, where unknown types become `typing.Any`.
+fn nested_annotation(ty: &syn::Type) -> String {
+    annotation(ty).unwrap_or_else(|| "typing.Any".to_string())
+}
+
+fn nested_first(args: &[&syn::Type]) -> String {
+    args.first()
+        .map_or_else(|| "typing.Any".to_string(), |ty| nested_annotation(ty))
+}
+
+fn type_args(segment: &syn::PathSegment) -> Vec<&syn::Type> {
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                syn::GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// The type arguments of `ty` if it is a path to one of `names`.
+fn generic_args<'a>(ty: &'a syn::Type, names: &[&str]) -> Option<Vec<&'a syn::Type>> {
+    match ty {
+        syn::Type::Path(path) if path.qself.is_none() => {
+            let segment = path.path.segments.last()?;
+            if names.iter().any(|name| segment.ident == name) {
+                Some(type_args(segment))
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+fn is_u8(ty: &syn::Type) -> bool {
+    matches!(ty, syn::Type::Path(path) if path.qself.is_none() && path.path.is_ident("u8"))
+}
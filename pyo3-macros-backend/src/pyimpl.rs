@@ -4,8 +4,9 @@ use crate::utils::Ctx;
 use crate::{
     attributes::{take_pyo3_options, CrateAttribute},
     konst::{ConstAttributes, ConstSpec},
-    pyfunction::PyFunctionOptions,
+    pyfunction::{stub_return_annotation, PyFunctionOptions},
     pymethod::{self, is_proto_method, MethodAndMethodDef, MethodAndSlotDef},
+    stub::StubClass,
 };
 use proc_macro2::TokenStream;
 use pymethod::GeneratedPyMethod;
@@ -95,6 +96,7 @@ pub fn impl_methods(
     let mut proto_impls = Vec::new();
     let mut methods = Vec::new();
     let mut associated_methods = Vec::new();
+    let mut stubs = Vec::new();
 
     let mut implemented_proto_fragments = HashSet::new();
 
@@ -103,8 +105,23 @@ pub fn impl_methods(
             syn::ImplItem::Fn(meth) => {
                 let mut fun_options = PyFunctionOptions::from_attrs(&mut meth.attrs)?;
                 fun_options.krate = fun_options.krate.or_else(|| options.krate.clone());
-                match pymethod::gen_py_method(ty, &mut meth.sig, &mut meth.attrs, fun_options, ctx)?
-                {
+                let mut method_stubs = Vec::new();
+                let generated = pymethod::gen_py_method(
+                    ty,
+                    &mut meth.sig,
+                    &mut meth.attrs,
+                    fun_options,
+                    &mut method_stubs,
+                    ctx,
+                )?;
+                let attrs = get_cfg_attributes(&meth.attrs);
+                stubs.extend(
+                    method_stubs
+                        .into_iter()
+                        .filter(|stub| !stub.is_empty())
+                        .map(|stub| quote!(#(#attrs)* const _: () = { #stub };)),
+                );
+                match generated {
                     GeneratedPyMethod::Method(MethodAndMethodDef {
                         associated_method,
                         method_def,
@@ -140,6 +157,17 @@ pub fn impl_methods(
                         associated_method,
                         method_def,
                     } = gen_py_const(ty, &spec, ctx);
+                    let stub = crate::stub::submit_stub(
+                        Some(StubClass::Type(ty)),
+                        "ClassAttribute",
+                        &spec.python_name().to_string(),
+                        "",
+                        stub_return_annotation(&konst.ty),
+                        ctx,
+                    );
+                    if !stub.is_empty() {
+                        stubs.push(quote!(#(#attrs)* const _: () = { #stub };));
+                    }
                     methods.push(quote!(#(#attrs)* #method_def));
                     associated_methods.push(quote!(#(#attrs)* #associated_method));
                     if is_proto_method(&spec.python_name().to_string()) {
@@ -179,6 +207,8 @@ pub fn impl_methods(
         impl #ty {
             #(#associated_methods)*
         }
+
+        #(#stubs)*
     })
 }
 
@@ -189,19 +189,55 @@ pub fn gen_py_method(
     sig: &mut syn::Signature,
     meth_attrs: &mut Vec<syn::Attribute>,
     options: PyFunctionOptions,
+    stubs: &mut Vec<TokenStream>,
     ctx: &Ctx,
 ) -> Result<GeneratedPyMethod> {
     check_generic(sig)?;
     ensure_function_options_valid(&options)?;
+    let mut options = options;
+    let signature_object = options.signature_object.take();
+    // the return value of an `async fn` is only available by awaiting the returned coroutine
+    let output = if sig.asyncness.is_none() {
+        Some(sig.output.clone())
+    } else {
+        None
+    };
     let method = PyMethod::parse(sig, meth_attrs, options, ctx)?;
     let spec = &method.spec;
     let Ctx { pyo3_path } = ctx;
 
+    // `__traverse__` is only called by the garbage collector
+    if !matches!(
+        method.kind,
+        PyMethodKind::Proto(PyMethodProtoKind::Traverse)
+    ) {
+        stubs.push(crate::stub::fn_stub(
+            spec,
+            Some(cls),
+            &method.method_name,
+            ctx,
+        ));
+    }
+
+    if let Some(signature_object) = signature_object {
+        ensure_spanned!(
+            matches!((&method.kind, &spec.tp), (PyMethodKind::Fn, FnType::Fn(_))),
+            signature_object.span() => "`signature_object` is only supported on `#[pyfunction]` and instance methods"
+        );
+        return Ok(GeneratedPyMethod::Method(impl_py_method_signature_object(
+            cls,
+            spec,
+            &spec.get_doc(meth_attrs),
+            output.as_ref(),
+            ctx,
+        )?));
+    }
+
     Ok(match (method.kind, &spec.tp) {
         // Class attributes go before protos so that class attributes can be used to set proto
         // method to None.
-        (_, FnType::ClassAttribute) => {
-            GeneratedPyMethod::Method(impl_py_class_attribute(cls, spec, ctx)?)
+        (_, FnType::ClassAttribute { lazy }) => {
+            GeneratedPyMethod::Method(impl_py_class_attribute(cls, spec, *lazy, ctx)?)
         }
         (PyMethodKind::Proto(proto_kind), _) => {
             ensure_no_forbidden_protocol_attributes(&proto_kind, spec, &method.method_name)?;
@@ -336,6 +372,54 @@ pub fn impl_py_method_def(
     })
 }
 
+/// An instance method with `#[pyo3(signature_object)]`, which is a class attribute holding a
+/// `SignatureFunction` rather than a method descriptor.
+fn impl_py_method_signature_object(
+    cls: &syn::Type,
+    spec: &FnSpec<'_>,
+    doc: &PythonDoc,
+    output: Option<&syn::ReturnType>,
+    ctx: &Ctx,
+) -> Result<MethodAndMethodDef> {
+    let Ctx { pyo3_path } = ctx;
+    let wrapper_ident = format_ident!("__pymethod_{}__", spec.python_name);
+    let signature_ident = format_ident!("__pymethod_signature_{}__", spec.python_name);
+    let function_ident = format_ident!("__pymethod_signature_function_{}__", spec.python_name);
+    let mut associated_method = spec.get_wrapper_function(&wrapper_ident, Some(cls), ctx)?;
+    let methoddef = spec.get_methoddef(quote! { #cls::#wrapper_ident }, doc, ctx);
+    let signature_object =
+        crate::pyfunction::impl_signature_object(&spec.signature, Some("self"), output, ctx);
+    let python_name = spec.null_terminated_python_name();
+    associated_method.extend(quote! {
+        fn #signature_ident(
+            py: #pyo3_path::Python<'_>,
+        ) -> #pyo3_path::PyResult<#pyo3_path::Bound<'_, #pyo3_path::PyAny>> {
+            #signature_object
+        }
+
+        fn #function_ident(py: #pyo3_path::Python<'_>) -> #pyo3_path::PyResult<#pyo3_path::PyObject> {
+            #pyo3_path::impl_::signature_function::signature_method(
+                py,
+                #methoddef,
+                <#cls as #pyo3_path::PyTypeInfo>::NAME,
+                #pyo3_path::impl_::pymethods::PySignatureObjectFactory(#cls::#signature_ident),
+            )
+        }
+    });
+    let method_def = quote! {
+        #pyo3_path::class::PyMethodDefType::ClassAttribute({
+            #pyo3_path::class::PyClassAttributeDef::new(
+                #python_name,
+                #pyo3_path::impl_::pymethods::PyClassAttributeFactory(#cls::#function_ident)
+            )
+        })
+    };
+    Ok(MethodAndMethodDef {
+        associated_method,
+        method_def,
+    })
+}
+
 /// Also used by pyclass.
 pub fn impl_py_method_def_new(
     cls: &syn::Type,
@@ -352,6 +436,35 @@ pub fn impl_py_method_def_new(
         || quote!(::std::option::Option::None),
         |text_signature| quote!(::std::option::Option::Some(#text_signature)),
     );
+    // Used for `#[pyclass(match_args)]` and `#[pyclass(getnewargs)]`, `*args` and keyword-only
+    // parameters cannot be matched (or passed positionally)
+    let python_signature = &spec.signature.python_signature;
+    let match_args = &python_signature.positional_parameters;
+    let non_positional = python_signature
+        .varargs
+        .iter()
+        .chain(
+            python_signature
+                .keyword_only_parameters
+                .iter()
+                .map(|(name, _)| name),
+        )
+        .chain(&python_signature.kwargs)
+        .next();
+    let getnewargs_check = non_positional.map(|name| {
+        let span = spec
+            .signature
+            .arguments
+            .iter()
+            .find(|arg| arg.name == name)
+            .map_or_else(|| spec.name.span(), |arg| arg.name.span());
+        quote_spanned! { span =>
+            // `#[pyclass(getnewargs)]` cannot pass this parameter to `__new__`
+            assert_non_positional_new_args_allowed::<
+                GetNewArgs<{ <#cls as #pyo3_path::impl_::pyclass::PyClassImpl>::HAS_GETNEWARGS }>,
+            >();
+        }
+    });
     let deprecations = &spec.deprecations;
     let slot_def = quote! {
         #pyo3_path::ffi::PyType_Slot {
@@ -372,6 +485,14 @@ pub fn impl_py_method_def_new(
                             #text_signature_body
                         }
                     }
+                    impl PyClassNewMatchArgs<#cls> for PyClassImplCollector<#cls> {
+                        #[inline]
+                        fn new_match_args(self) -> &'static [&'static str] {
+                            &[#(#match_args),*]
+                        }
+                    }
+
+                    #getnewargs_check
 
                     #pyo3_path::impl_::trampoline::newfunc(
                         subtype,
@@ -465,6 +586,7 @@ fn impl_traverse_slot(
 fn impl_py_class_attribute(
     cls: &syn::Type,
     spec: &FnSpec<'_>,
+    lazy: bool,
     ctx: &Ctx,
 ) -> syn::Result<MethodAndMethodDef> {
     let Ctx { pyo3_path } = ctx;
@@ -485,12 +607,24 @@ fn impl_py_class_attribute(
     let python_name = spec.null_terminated_python_name();
     let body = quotes::ok_wrap(fncall, ctx);
 
-    let associated_method = quote! {
+    let mut associated_method = quote! {
         fn #wrapper_ident(py: #pyo3_path::Python<'_>) -> #pyo3_path::PyResult<#pyo3_path::PyObject> {
             let function = #cls::#name; // Shadow the method name to avoid #3017
             #pyo3_path::impl_::wrap::map_result_into_py(py, #body)
         }
     };
+    let wrapper_ident = if lazy {
+        // The type dict holds a descriptor which calls the wrapper above on first access
+        let lazy_wrapper_ident = format_ident!("__pymethod_lazy_{}__", name);
+        associated_method.extend(quote! {
+            fn #lazy_wrapper_ident(py: #pyo3_path::Python<'_>) -> #pyo3_path::PyResult<#pyo3_path::PyObject> {
+                #pyo3_path::impl_::lazy_classattr::lazy_class_attribute(py, #cls::#wrapper_ident)
+            }
+        });
+        lazy_wrapper_ident
+    } else {
+        wrapper_ident
+    };
 
     let method_def = quote! {
         #pyo3_path::class::PyMethodDefType::ClassAttribute({
@@ -548,20 +682,39 @@ pub fn impl_py_setter_def(
     let mut holders = Holders::new();
     let setter_impl = match property_type {
         PropertyType::Descriptor {
-            field_index, field, ..
+            field_index,
+            field,
+            set_validator,
+            ..
         } => {
             let slf = SelfType::Receiver {
                 mutable: true,
                 span: Span::call_site(),
             }
             .receiver(cls, ExtractErrorMode::Raise, &mut holders, ctx);
-            if let Some(ident) = &field.ident {
+            let member = match &field.ident {
                 // named struct field
-                quote!({ #slf.#ident = _val; })
-            } else {
+                Some(ident) => quote!(#ident),
                 // tuple struct field
-                let index = syn::Index::from(field_index);
-                quote!({ #slf.#index = _val; })
+                None => {
+                    let index = syn::Index::from(field_index);
+                    quote!(#index)
+                }
+            };
+            match set_validator {
+                Some(validator) => {
+                    // a plain name refers to a method, anything else is called with `&self`
+                    let validate = match validator.path.get_ident() {
+                        Some(method) => quote_spanned! { validator.span() => slf.#method(_val) },
+                        None => quote_spanned! { validator.span() => #validator(slf, _val) },
+                    };
+                    quote!({
+                        let slf: &mut Self = #slf;
+                        let _val = #validate?;
+                        slf.#member = _val;
+                    })
+                }
+                None => quote!({ #slf.#member = _val; }),
             }
         }
         PropertyType::Function {
@@ -701,7 +854,10 @@ pub fn impl_py_getter_def(
     let mut holders = Holders::new();
     let body = match property_type {
         PropertyType::Descriptor {
-            field_index, field, ..
+            field_index,
+            field,
+            cache_index,
+            ..
         } => {
             let slf = SelfType::Receiver {
                 mutable: false,
@@ -715,15 +871,27 @@ pub fn impl_py_getter_def(
                 // tuple struct field
                 syn::Index::from(field_index).to_token_stream()
             };
-            quotes::map_result_into_ptr(
-                quotes::ok_wrap(
-                    quote! {
-                        ::std::clone::Clone::clone(&(#slf.#field_token))
-                    },
+            if let Some(cache_index) = cache_index {
+                quote! {{
+                    let _slf_ref = #slf;
+                    #pyo3_path::impl_::pyclass::cached_getter::<#cls>(py, _slf, #cache_index, || {
+                        #pyo3_path::IntoPy::<#pyo3_path::PyObject>::into_py(
+                            ::std::clone::Clone::clone(&(_slf_ref.#field_token)),
+                            py,
+                        )
+                    })
+                }}
+            } else {
+                quotes::map_result_into_ptr(
+                    quotes::ok_wrap(
+                        quote! {
+                            ::std::clone::Clone::clone(&(#slf.#field_token))
+                        },
+                        ctx,
+                    ),
                     ctx,
-                ),
-                ctx,
-            )
+                )
+            }
         }
         // Forward to `IntoPyCallbackOutput`, to handle `#[getter]`s returning results.
         PropertyType::Function {
@@ -810,6 +978,10 @@ pub enum PropertyType<'a> {
         field: &'a syn::Field,
         python_name: Option<&'a NameAttribute>,
         renaming_rule: Option<RenamingRule>,
+        /// Position of the field in the class's getter cache, for `#[pyo3(get, cache)]`.
+        cache_index: Option<usize>,
+        /// Validates (and may convert) values before they are stored, for `#[pyo3(set = ...)]`.
+        set_validator: Option<&'a syn::ExprPath>,
     },
     Function {
         self_type: &'a SelfType,
@@ -857,9 +1029,9 @@ impl PropertyType<'_> {
     }
 }
 
-const __STR__: SlotDef = SlotDef::new("Py_tp_str", "reprfunc");
+pub const __STR__: SlotDef = SlotDef::new("Py_tp_str", "reprfunc");
 pub const __REPR__: SlotDef = SlotDef::new("Py_tp_repr", "reprfunc");
-const __HASH__: SlotDef = SlotDef::new("Py_tp_hash", "hashfunc")
+pub const __HASH__: SlotDef = SlotDef::new("Py_tp_hash", "hashfunc")
     .ret_ty(Ty::PyHashT)
     .return_conversion(TokenGenerator(
         |Ctx { pyo3_path }: &Ctx| quote! { #pyo3_path::callback::HashCallbackOutput },
@@ -869,8 +1041,8 @@ pub const __RICHCMP__: SlotDef = SlotDef::new("Py_tp_richcompare", "richcmpfunc"
     .arguments(&[Ty::Object, Ty::CompareOp]);
 const __GET__: SlotDef = SlotDef::new("Py_tp_descr_get", "descrgetfunc")
     .arguments(&[Ty::MaybeNullObject, Ty::MaybeNullObject]);
-const __ITER__: SlotDef = SlotDef::new("Py_tp_iter", "getiterfunc");
-const __NEXT__: SlotDef = SlotDef::new("Py_tp_iternext", "iternextfunc")
+pub const __ITER__: SlotDef = SlotDef::new("Py_tp_iter", "getiterfunc");
+pub const __NEXT__: SlotDef = SlotDef::new("Py_tp_iternext", "iternextfunc")
     .return_specialized_conversion(
         TokenGenerator(|_| quote! { IterBaseKind, IterOptionKind, IterResultOptionKind }),
         TokenGenerator(|_| quote! { iter_tag }),
@@ -893,7 +1065,9 @@ const __INPLACE_CONCAT__: SlotDef =
     SlotDef::new("Py_sq_concat", "binaryfunc").arguments(&[Ty::Object]);
 const __INPLACE_REPEAT__: SlotDef =
     SlotDef::new("Py_sq_repeat", "ssizeargfunc").arguments(&[Ty::PySsizeT]);
-const __GETITEM__: SlotDef = SlotDef::new("Py_mp_subscript", "binaryfunc").arguments(&[Ty::Object]);
+const __GETITEM__: SlotDef = SlotDef::new("Py_mp_subscript", "binaryfunc")
+    .arguments(&[Ty::Object])
+    .sequence_index();
 
 const __POS__: SlotDef = SlotDef::new("Py_nb_positive", "unaryfunc");
 const __NEG__: SlotDef = SlotDef::new("Py_nb_negative", "unaryfunc");
@@ -956,11 +1130,11 @@ const __IOR__: SlotDef = SlotDef::new("Py_nb_inplace_or", "binaryfunc")
     .arguments(&[Ty::Object])
     .extract_error_mode(ExtractErrorMode::NotImplemented)
     .return_self();
-const __GETBUFFER__: SlotDef = SlotDef::new("Py_bf_getbuffer", "getbufferproc")
+pub const __GETBUFFER__: SlotDef = SlotDef::new("Py_bf_getbuffer", "getbufferproc")
     .arguments(&[Ty::PyBuffer, Ty::Int])
     .ret_ty(Ty::Int)
     .require_unsafe();
-const __RELEASEBUFFER__: SlotDef = SlotDef::new("Py_bf_releasebuffer", "releasebufferproc")
+pub const __RELEASEBUFFER__: SlotDef = SlotDef::new("Py_bf_releasebuffer", "releasebufferproc")
     .arguments(&[Ty::PyBuffer])
     .ret_ty(Ty::Void)
     .require_unsafe();
@@ -1142,6 +1316,7 @@ pub struct SlotDef {
     extract_error_mode: ExtractErrorMode,
     return_mode: Option<ReturnMode>,
     require_unsafe: bool,
+    sequence_index: bool,
 }
 
 const NO_ARGUMENTS: &[Ty] = &[];
@@ -1156,6 +1331,7 @@ impl SlotDef {
             extract_error_mode: ExtractErrorMode::Raise,
             return_mode: None,
             require_unsafe: false,
+            sequence_index: false,
         }
     }
 
@@ -1198,6 +1374,12 @@ impl SlotDef {
         self
     }
 
+    /// The first argument is an index, normalized for `#[pyclass(sequence)]`.
+    const fn sequence_index(mut self) -> Self {
+        self.sequence_index = true;
+        self
+    }
+
     pub fn generate_type_slot(
         &self,
         cls: &syn::Type,
@@ -1214,6 +1396,7 @@ impl SlotDef {
             ret_ty,
             return_mode,
             require_unsafe,
+            sequence_index,
         } = self;
         if *require_unsafe {
             ensure_spanned!(
@@ -1239,6 +1422,7 @@ impl SlotDef {
         )?;
         let name = spec.name;
         let holders = holders.init_holders(ctx);
+        let normalize_index = normalize_sequence_index(*sequence_index, cls, ctx);
         let associated_method = quote! {
             unsafe fn #wrapper_ident(
                 py: #pyo3_path::Python<'_>,
@@ -1247,6 +1431,7 @@ impl SlotDef {
             ) -> #pyo3_path::PyResult<#ret_ty> {
                 let function = #cls::#name; // Shadow the method name to avoid #3017
                 let _slf = _raw_slf;
+                #normalize_index
                 #holders
                 #body
             }
@@ -1304,11 +1489,25 @@ fn generate_method_body(
     })
 }
 
+/// Replaces a negative integer `arg0` by the index counted from the end of a
+/// `#[pyclass(sequence)]`.
+fn normalize_sequence_index(sequence_index: bool, cls: &syn::Type, ctx: &Ctx) -> TokenStream {
+    let Ctx { pyo3_path } = ctx;
+    if !sequence_index {
+        return TokenStream::new();
+    }
+    quote! {
+        let __pyo3_index = #pyo3_path::impl_::pyclass::normalize_sequence_index::<#cls>(py, _raw_slf, arg0)?;
+        let arg0 = __pyo3_index.as_ptr();
+    }
+}
+
 struct SlotFragmentDef {
     fragment: &'static str,
     arguments: &'static [Ty],
     extract_error_mode: ExtractErrorMode,
     ret_ty: Ty,
+    sequence_index: bool,
 }
 
 impl SlotFragmentDef {
@@ -1318,6 +1517,7 @@ impl SlotFragmentDef {
             arguments,
             extract_error_mode: ExtractErrorMode::Raise,
             ret_ty: Ty::Void,
+            sequence_index: false,
         }
     }
 
@@ -1331,6 +1531,12 @@ impl SlotFragmentDef {
         self
     }
 
+    /// The first argument is an index, normalized for `#[pyclass(sequence)]`.
+    const fn sequence_index(mut self) -> Self {
+        self.sequence_index = true;
+        self
+    }
+
     fn generate_pyproto_fragment(
         &self,
         cls: &syn::Type,
@@ -1343,6 +1549,7 @@ impl SlotFragmentDef {
             arguments,
             extract_error_mode,
             ret_ty,
+            sequence_index,
         } = self;
         let fragment_trait = format_ident!("PyClass{}SlotFragment", fragment);
         let method = syn::Ident::new(fragment, Span::call_site());
@@ -1363,6 +1570,7 @@ impl SlotFragmentDef {
         )?;
         let ret_ty = ret_ty.ffi_type(ctx);
         let holders = holders.init_holders(ctx);
+        let normalize_index = normalize_sequence_index(*sequence_index, cls, ctx);
         Ok(quote! {
             impl #cls {
                 unsafe fn #wrapper_ident(
@@ -1371,6 +1579,7 @@ impl SlotFragmentDef {
                     #(#arg_idents: #arg_types),*
                 ) -> #pyo3_path::PyResult<#ret_ty> {
                     let _slf = _raw_slf;
+                    #normalize_index
                     #holders
                     #body
                 }
@@ -1402,8 +1611,9 @@ const __DELATTR__: SlotFragmentDef = SlotFragmentDef::new("__delattr__", &[Ty::O
 const __SET__: SlotFragmentDef = SlotFragmentDef::new("__set__", &[Ty::Object, Ty::NonNullObject]);
 const __DELETE__: SlotFragmentDef = SlotFragmentDef::new("__delete__", &[Ty::Object]);
 const __SETITEM__: SlotFragmentDef =
-    SlotFragmentDef::new("__setitem__", &[Ty::Object, Ty::NonNullObject]);
-const __DELITEM__: SlotFragmentDef = SlotFragmentDef::new("__delitem__", &[Ty::Object]);
+    SlotFragmentDef::new("__setitem__", &[Ty::Object, Ty::NonNullObject]).sequence_index();
+const __DELITEM__: SlotFragmentDef =
+    SlotFragmentDef::new("__delitem__", &[Ty::Object]).sequence_index();
 
 macro_rules! binary_num_slot_fragment_def {
     ($ident:ident, $name:literal) => {
@@ -12,8 +12,11 @@ pub(crate) fn some_wrap(obj: TokenStream, ctx: &Ctx) -> TokenStream {
 pub(crate) fn ok_wrap(obj: TokenStream, ctx: &Ctx) -> TokenStream {
     let Ctx { pyo3_path } = ctx;
     quote! {
-        #pyo3_path::impl_::wrap::OkWrap::wrap(#obj)
-            .map_err(::core::convert::Into::<#pyo3_path::PyErr>::into)
+        #pyo3_path::impl_::wrap::OkWrap::wrap(#obj).map_err(|e| {
+            #[allow(unused_imports)]
+            use #pyo3_path::impl_::wrap::{IntoPyErrKind as _, MappedErrKind as _};
+            (&e).py_err_kind().convert(e)
+        })
     }
 }
 
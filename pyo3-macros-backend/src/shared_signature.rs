@@ -0,0 +1,188 @@
+//! Support for `#[pyo3(signature = name!())]`, referencing a signature declared once with
+//! `pyo3::shared_signature!`.
+//!
+//! Proc macros cannot look up other items, so `#[pyfunction]` and `#[pymethods]` hand the
+//! annotated item to the `name!` macro, which passes it back together with the signature to
+//! `resolve_shared_signature!`. That splices the signature in place of `name!()` and reapplies
+//! the original attribute, which then sees an ordinary `signature = (...)`.
+
+use proc_macro2::{Delimiter, Group, Ident, TokenStream, TokenTree};
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    Result,
+};
+
+/// If `attr` or the `#[pyo3(...)]` attributes of `item` reference a shared signature, returns
+/// the invocation of its macro which reapplies the `#[pyfunction]` or `#[pymethods]` attribute
+/// named `kind` with the signature resolved.
+pub fn defer_to_shared_signature(
+    kind: &str,
+    attr: &TokenStream,
+    item: &TokenStream,
+) -> Option<TokenStream> {
+    let empty = TokenStream::new();
+    let (path, _) = replace_shared_signature(attr.clone(), &empty)
+        .or_else(|| replace_in_attributes(item.clone(), &empty, kind == "pymethods"))?;
+    let kind = Ident::new(kind, proc_macro2::Span::call_site());
+    Some(quote! {
+        #path! { #kind [#attr] #item }
+    })
+}
+
+/// The input of `resolve_shared_signature!`, as expanded from a `pyo3::shared_signature!` macro:
+/// `$crate kind [attr] (signature) item`.
+pub struct ResolveSharedSignature {
+    krate: TokenTree,
+    kind: Ident,
+    attr: TokenStream,
+    signature: TokenStream,
+    item: TokenStream,
+}
+
+impl Parse for ResolveSharedSignature {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let krate = input.parse()?;
+        let kind = input.parse()?;
+        let attr;
+        syn::bracketed!(attr in input);
+        let signature;
+        syn::parenthesized!(signature in input);
+        Ok(Self {
+            krate,
+            kind,
+            attr: attr.parse()?,
+            signature: signature.parse()?,
+            item: input.parse()?,
+        })
+    }
+}
+
+/// Replaces the first shared signature referenced by the item with its definition, and
+/// reapplies the attribute.
+pub fn build_resolve_shared_signature(input: ResolveSharedSignature) -> TokenStream {
+    let ResolveSharedSignature {
+        krate,
+        kind,
+        mut attr,
+        signature,
+        mut item,
+    } = input;
+    // The shared signature referenced first is the one which the attribute deferred to.
+    if let Some((_, resolved)) = replace_shared_signature(attr.clone(), &signature) {
+        attr = resolved;
+    } else if let Some((_, resolved)) =
+        replace_in_attributes(item.clone(), &signature, kind == "pymethods")
+    {
+        item = resolved;
+    }
+    quote! {
+        #[#krate::#kind(#attr)]
+        #item
+    }
+}
+
+/// Finds the first `signature = path!()` in the `#[pyo3(...)]` attributes of `item`, returning
+/// `path` and `item` with the invocation replaced by `(signature)`.
+///
+/// Only the attributes of the item itself are searched, or with `in_body` the attributes of the
+/// items in its body (the methods of a `#[pymethods]` block), so that function bodies are never
+/// rewritten.
+fn replace_in_attributes(
+    item: TokenStream,
+    signature: &TokenStream,
+    in_body: bool,
+) -> Option<(TokenStream, TokenStream)> {
+    let mut tokens: Vec<TokenTree> = item.into_iter().collect();
+    for i in 0..tokens.len() {
+        let group = match &tokens[i] {
+            TokenTree::Group(group) => group,
+            _ => continue,
+        };
+        let resolved = match group.delimiter() {
+            Delimiter::Bracket if matches!(&tokens[..i], [.., TokenTree::Punct(p)] if p.as_char() == '#') => {
+                replace_in_pyo3_attribute(group.stream(), signature)
+            }
+            Delimiter::Brace if in_body => replace_in_attributes(group.stream(), signature, false),
+            _ => None,
+        };
+        if let Some((path, stream)) = resolved {
+            let mut resolved = Group::new(group.delimiter(), stream);
+            resolved.set_span(group.span());
+            tokens[i] = TokenTree::Group(resolved);
+            return Some((path, tokens.into_iter().collect()));
+        }
+    }
+    None
+}
+
+/// If `attribute` (the contents of `#[...]`) is `pyo3(...)`, replaces a shared signature in its
+/// arguments.
+fn replace_in_pyo3_attribute(
+    attribute: TokenStream,
+    signature: &TokenStream,
+) -> Option<(TokenStream, TokenStream)> {
+    let mut tokens: Vec<TokenTree> = attribute.into_iter().collect();
+    match tokens.as_slice() {
+        [TokenTree::Ident(ident), TokenTree::Group(args)]
+            if ident == "pyo3" && args.delimiter() == Delimiter::Parenthesis =>
+        {
+            let (path, stream) = replace_shared_signature(args.stream(), signature)?;
+            let mut resolved = Group::new(Delimiter::Parenthesis, stream);
+            resolved.set_span(args.span());
+            tokens[1] = TokenTree::Group(resolved);
+            Some((path, tokens.into_iter().collect()))
+        }
+        _ => None,
+    }
+}
+
+/// Finds the first `signature = path!()` in the attribute arguments `args`, returning `path` and
+/// `args` with the invocation replaced by `(signature)`.
+fn replace_shared_signature(
+    args: TokenStream,
+    signature: &TokenStream,
+) -> Option<(TokenStream, TokenStream)> {
+    let mut tokens: Vec<TokenTree> = args.into_iter().collect();
+    for i in 0..tokens.len() {
+        if let Some((path, len)) = match_shared_signature(&tokens[i..]) {
+            let mut group = Group::new(Delimiter::Parenthesis, signature.clone());
+            group.set_span(tokens[i + len - 1].span());
+            tokens.splice(i + 2..i + len, std::iter::once(TokenTree::Group(group)));
+            return Some((path, tokens.into_iter().collect()));
+        }
+    }
+    None
+}
+
+/// Matches `signature = path!()` at the start of `tokens`, returning `path` and the number of
+/// tokens matched.
+fn match_shared_signature(tokens: &[TokenTree]) -> Option<(TokenStream, usize)> {
+    let rest = match tokens {
+        [TokenTree::Ident(ident), TokenTree::Punct(eq), rest @ ..]
+            if ident == "signature" && eq.as_char() == '=' =>
+        {
+            rest
+        }
+        _ => return None,
+    };
+    let path_len = rest
+        .iter()
+        .take_while(|token| match token {
+            TokenTree::Ident(_) => true,
+            TokenTree::Punct(punct) => punct.as_char() == ':',
+            _ => false,
+        })
+        .count();
+    match rest.get(path_len..path_len + 2) {
+        Some([TokenTree::Punct(bang), TokenTree::Group(args)])
+            if path_len > 0
+                && bang.as_char() == '!'
+                && args.delimiter() == Delimiter::Parenthesis
+                && args.stream().is_empty() =>
+        {
+            Some((rest[..path_len].iter().cloned().collect(), path_len + 4))
+        }
+        _ => None,
+    }
+}
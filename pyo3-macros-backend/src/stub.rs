@@ -0,0 +1,102 @@
+//! Registration of the `.pyi` stubs of classes and functions in `pyo3::stub`, which is only
+//! generated with the `experimental-stubs` feature.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use crate::{
+    method::{FnSpec, FnType},
+    pyfunction::stub_return_annotation,
+    utils::{Ctx, PyO3CratePath},
+};
+
+/// The class a stub item belongs to.
+pub enum StubClass<'a> {
+    /// A class whose Python name is known, i.e. from `#[pyclass]`.
+    Named(&'a str),
+    /// A class whose Python name is read from `PyTypeInfo::NAME`, i.e. from `#[pymethods]`.
+    Type(&'a syn::Type),
+}
+
+/// Submits a `pyo3::stub::StubItem` to the stub registry.
+///
+/// Generates nothing without the `experimental-stubs` feature.
+pub fn submit_stub(
+    class: Option<StubClass<'_>>,
+    kind: &str,
+    name: &str,
+    parameters: &str,
+    returns: Option<String>,
+    ctx: &Ctx,
+) -> TokenStream {
+    if !cfg!(feature = "experimental-stubs") {
+        return TokenStream::new();
+    }
+    let Ctx { pyo3_path } = ctx;
+    // PyO3's own classes, such as `Coroutine`, are not part of any extension's API
+    if matches!(pyo3_path, PyO3CratePath::Given(path) if path.is_ident("crate")) {
+        return TokenStream::new();
+    }
+    let class = match class {
+        Some(StubClass::Named(name)) => quote!(::std::option::Option::Some(#name)),
+        Some(StubClass::Type(cls)) => {
+            quote!(::std::option::Option::Some(<#cls as #pyo3_path::PyTypeInfo>::NAME))
+        }
+        None => quote!(::std::option::Option::None),
+    };
+    let kind = format_ident!("{}", kind);
+    let returns = match returns {
+        Some(returns) => quote!(::std::option::Option::Some(#returns)),
+        None => quote!(::std::option::Option::None),
+    };
+    quote! {
+        #pyo3_path::inventory::submit! {
+            #pyo3_path::stub::StubItem {
+                module: ::std::module_path!(),
+                class: #class,
+                kind: #pyo3_path::stub::StubKind::#kind,
+                name: #name,
+                parameters: #parameters,
+                returns: #returns,
+            }
+        }
+    }
+}
+
+/// The stub of a `#[pyfunction]`, or of a method of `cls` named `python_name` in Python.
+pub fn fn_stub(
+    spec: &FnSpec<'_>,
+    cls: Option<&syn::Type>,
+    python_name: &str,
+    ctx: &Ctx,
+) -> TokenStream {
+    let (kind, self_argument) = match (&spec.tp, cls) {
+        (FnType::Getter(_), _) => ("Getter", Some("self")),
+        (FnType::Setter(_), _) => ("Setter", Some("self")),
+        (FnType::Fn(_), _) => ("Method", Some("self")),
+        (FnType::FnNew, _) | (FnType::FnNewClass(_), _) => ("New", Some("cls")),
+        (FnType::FnClass(_), _) => ("ClassMethod", Some("cls")),
+        (FnType::ClassAttribute { .. }, _) => ("ClassAttribute", None),
+        (FnType::FnStatic, Some(_)) => ("StaticMethod", None),
+        (FnType::FnStatic, None) | (FnType::FnModule(_), _) => ("Function", None),
+    };
+    let parameters = if spec.unpack.is_some() {
+        "(packed: typing.Union[tuple, dict], /)".to_owned()
+    } else {
+        spec.signature.stub_parameters(self_argument)
+    };
+    // the return value of an `async fn` is only available by awaiting the returned coroutine
+    let returns = if spec.asyncness.is_some() {
+        None
+    } else {
+        stub_return_annotation(&spec.output)
+    };
+    submit_stub(
+        cls.map(StubClass::Type),
+        kind,
+        python_name,
+        &parameters,
+        returns,
+        ctx,
+    )
+}
@@ -1,5 +1,5 @@
-use proc_macro2::{Span, TokenStream};
-use quote::ToTokens;
+use proc_macro2::{Spacing, Span, TokenStream, TokenTree};
+use quote::{quote, ToTokens};
 use syn::{punctuated::Punctuated, Token};
 
 use crate::attributes::{CrateAttribute, RenamingRule};
@@ -131,6 +131,12 @@ pub fn get_doc(attrs: &[syn::Attribute], mut text_signature: Option<String>) ->
     }
 }
 
+/// Builds the docstring from a `#[pyo3(doc = ...)]` expression, which may be a string literal or
+/// a macro such as `include_str!(...)`. This replaces any `#[doc = "..."]` attributes.
+pub fn get_doc_from_expr(expr: &syn::Expr) -> PythonDoc {
+    PythonDoc(quote!(concat!(#expr, "\0")))
+}
+
 impl quote::ToTokens for PythonDoc {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         self.0.to_tokens(tokens)
@@ -144,6 +150,33 @@ pub fn unwrap_ty_group(mut ty: &syn::Type) -> &syn::Type {
     ty
 }
 
+/// Replaces named lifetimes (other than `'static`) with `'_`, so the argument type can be used
+/// inside generated functions, where those lifetimes are not in scope.
+pub fn erase_lifetimes(tokens: TokenStream) -> TokenStream {
+    let mut output = Vec::new();
+    let mut in_lifetime = false;
+    for token in tokens {
+        let token = match token {
+            TokenTree::Group(group) => {
+                let mut new_group =
+                    proc_macro2::Group::new(group.delimiter(), erase_lifetimes(group.stream()));
+                new_group.set_span(group.span());
+                TokenTree::Group(new_group)
+            }
+            TokenTree::Ident(ident) if in_lifetime && ident != "static" => {
+                TokenTree::Ident(syn::Ident::new("_", ident.span()))
+            }
+            token => token,
+        };
+        in_lifetime = matches!(
+            &token,
+            TokenTree::Punct(punct) if punct.as_char() == '\'' && punct.spacing() == Spacing::Joint
+        );
+        output.push(token);
+    }
+    output.into_iter().collect()
+}
+
 pub struct Ctx {
     pub pyo3_path: PyO3CratePath,
 }
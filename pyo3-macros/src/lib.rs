@@ -5,9 +5,10 @@
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
 use pyo3_macros_backend::{
-    build_derive_from_pyobject, build_py_class, build_py_enum, build_py_function, build_py_methods,
-    pymodule_function_impl, pymodule_module_impl, PyClassArgs, PyClassMethodsType,
-    PyFunctionOptions,
+    build_derive_from_pyobject, build_derive_into_py, build_extract_args, build_py_class,
+    build_py_enum, build_py_function, build_py_methods, build_resolve_shared_signature,
+    defer_to_shared_signature, pymodule_function_impl, pymodule_module_impl, ExtractArgs,
+    PyClassArgs, PyClassMethodsType, PyFunctionOptions, ResolveSharedSignature,
 };
 use quote::quote;
 use syn::{parse::Nothing, parse_macro_input, Item};
@@ -24,6 +25,7 @@ use syn::{parse::Nothing, parse_macro_input, Item};
 /// |  Annotation  |  Description |
 /// | :-  | :- |
 /// | `#[pyo3(name = "...")]` | Defines the name of the module in Python. |
+/// | `#[pyo3(gil_used = false)]` | Declares that the module does not rely on the GIL, so importing it on a free-threaded build of Python does not re-enable the GIL. |
 ///
 /// For more on creating Python modules see the [module section of the guide][1].
 ///
@@ -132,6 +134,11 @@ pub fn pymethods(attr: TokenStream, input: TokenStream) -> TokenStream {
 /// [1]: https://pyo3.rs/latest/function.html
 #[proc_macro_attribute]
 pub fn pyfunction(attr: TokenStream, input: TokenStream) -> TokenStream {
+    if let Some(deferred) =
+        defer_to_shared_signature("pyfunction", &attr.clone().into(), &input.clone().into())
+    {
+        return deferred.into();
+    }
     let mut ast = parse_macro_input!(input as syn::ItemFn);
     let options = parse_macro_input!(attr as PyFunctionOptions);
 
@@ -144,6 +151,25 @@ pub fn pyfunction(attr: TokenStream, input: TokenStream) -> TokenStream {
     .into()
 }
 
+/// Replaces a signature referenced with `#[pyo3(signature = name!())]` by its definition.
+///
+/// Expanded from macros declared with `pyo3::shared_signature!`.
+#[doc(hidden)]
+#[proc_macro]
+pub fn resolve_shared_signature(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as ResolveSharedSignature);
+    build_resolve_shared_signature(input).into()
+}
+
+/// Extracts the arguments of a call with the same signature grammar as `#[pyfunction]`.
+///
+/// See [`pyo3::extract_args!`](https://docs.rs/pyo3/latest/pyo3/macro.extract_args.html).
+#[proc_macro]
+pub fn extract_args(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as ExtractArgs);
+    build_extract_args(input).unwrap_or_compile_error().into()
+}
+
 #[proc_macro_derive(FromPyObject, attributes(pyo3))]
 pub fn derive_from_py_object(item: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(item as syn::DeriveInput);
@@ -154,6 +180,16 @@ pub fn derive_from_py_object(item: TokenStream) -> TokenStream {
     .into()
 }
 
+#[proc_macro_derive(IntoPy, attributes(pyo3))]
+pub fn derive_into_py(item: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(item as syn::DeriveInput);
+    let expanded = build_derive_into_py(&ast).unwrap_or_compile_error();
+    quote!(
+        #expanded
+    )
+    .into()
+}
+
 fn pyclass_impl(
     attrs: TokenStream,
     mut ast: syn::ItemStruct,
@@ -189,6 +225,11 @@ fn pymethods_impl(
     input: TokenStream,
     methods_type: PyClassMethodsType,
 ) -> TokenStream {
+    if let Some(deferred) =
+        defer_to_shared_signature("pymethods", &attr.clone().into(), &input.clone().into())
+    {
+        return deferred.into();
+    }
     let mut ast = parse_macro_input!(input as syn::ItemImpl);
     // Apply all options as a #[pyo3] attribute on the ItemImpl
     // e.g. #[pymethods(crate = "crate")] impl Foo { }
@@ -182,6 +182,72 @@ pub unsafe trait Element: Copy {
     fn is_compatible_format(format: &CStr) -> bool;
 }
 
+/// Exports the contiguous data of a `#[pyclass(buffer)]` through Python's buffer protocol.
+///
+/// `#[pyclass(buffer)]` generates `__getbuffer__` and `__releasebuffer__` from this trait, so that
+/// `memoryview(obj)`, `bytes(obj)` or `numpy.asarray(obj)` access the data without copying it.
+/// While any buffer is exported the object stays borrowed, so the data can neither be mutated
+/// (and possibly reallocated) nor freed from Rust: `borrow_mut` fails while a read-only buffer
+/// exists, and any borrow fails while a writable buffer exists.
+///
+/// Writable buffers are only exported to consumers which request them (such as the `readinto`
+/// method of files), and only if [`buffer_mut`](PyClassBuffer::buffer_mut) is implemented.
+///
+/// # Example
+///
+/// ```rust
+/// use pyo3::buffer::PyClassBuffer;
+/// use pyo3::prelude::*;
+///
+/// #[pyclass(buffer)]
+/// struct Samples {
+///     data: Vec<f64>,
+/// }
+///
+/// impl PyClassBuffer for Samples {
+///     type Item = f64;
+///     const FORMAT: &'static str = "d";
+///
+///     fn buffer(&self) -> &[f64] {
+///         &self.data
+///     }
+///
+///     fn buffer_mut(&mut self) -> Option<&mut [f64]> {
+///         Some(&mut self.data)
+///     }
+/// }
+///
+/// Python::with_gil(|py| {
+///     let samples = Py::new(py, Samples { data: vec![0.5, 2.0] }).unwrap();
+///     pyo3::py_run!(py, samples, r#"
+///         view = memoryview(samples)
+///         assert view.format == "d" and view.tolist() == [0.5, 2.0]
+///     "#);
+/// });
+/// ```
+pub trait PyClassBuffer: crate::PyClass {
+    /// The type of the items of the buffer.
+    type Item: Element;
+
+    /// The [`struct` format string](https://docs.python.org/3/library/struct.html#format-strings)
+    /// describing [`Item`](PyClassBuffer::Item), e.g. `"d"` for `f64`.
+    ///
+    /// Exporting a buffer fails with `BufferError` if the format is not compatible with `Item`
+    /// according to [`Element::is_compatible_format`], or describes items of a different size.
+    const FORMAT: &'static str;
+
+    /// Returns the data exported by read-only buffers.
+    fn buffer(&self) -> &[Self::Item];
+
+    /// Returns the data exported by writable buffers, or `None` (the default) if the buffer is
+    /// read-only.
+    ///
+    /// This is never called for `#[pyclass(frozen)]` types, whose buffers are always read-only.
+    fn buffer_mut(&mut self) -> Option<&mut [Self::Item]> {
+        None
+    }
+}
+
 impl<'py, T: Element> FromPyObject<'py> for PyBuffer<T> {
     fn extract_bound(obj: &Bound<'_, PyAny>) -> PyResult<PyBuffer<T>> {
         Self::get_bound(obj)
@@ -20,6 +20,10 @@
 //! Note that you must use compatible versions of num-complex and PyO3.
 //! The required num-complex version may vary based on the version of PyO3.
 //!
+//! Like Python's `complex()`, extracting a [`Complex`] also accepts real numbers: `int` and
+//! `float` values (and objects implementing `__float__` or `__index__`) become the real part,
+//! with an imaginary part of zero.
+//!
 //! # Examples
 //!
 //! Using [num-complex](https://docs.rs/num-complex) and [nalgebra](https://docs.rs/nalgebra)
@@ -219,6 +223,26 @@ mod tests {
         });
     }
     #[test]
+    fn from_int_and_float() {
+        Python::with_gil(|py| {
+            let from_int = 3.to_object(py);
+            assert_eq!(
+                from_int.extract::<Complex<f64>>(py).unwrap(),
+                Complex::new(3.0, 0.0)
+            );
+            let from_float = (-1.5).to_object(py);
+            assert_eq!(
+                from_float.extract::<Complex<f32>>(py).unwrap(),
+                Complex::new(-1.5, 0.0)
+            );
+            let from_bool = true.to_object(py);
+            assert_eq!(
+                from_bool.extract::<Complex<f64>>(py).unwrap(),
+                Complex::new(1.0, 0.0)
+            );
+        });
+    }
+    #[test]
     fn from_complex_err() {
         Python::with_gil(|py| {
             let obj = vec![1].to_object(py);
@@ -76,8 +76,9 @@ where
     fn extract_bound(ob: &Bound<'py, PyAny>) -> Result<Self, PyErr> {
         let dict = ob.downcast::<PyDict>()?;
         let mut ret = collections::HashMap::with_capacity_and_hasher(dict.len(), S::default());
-        for (k, v) in dict {
-            ret.insert(k.extract()?, v.extract()?);
+        for item in dict.iter_typed() {
+            let (k, v) = item?;
+            ret.insert(k, v);
         }
         Ok(ret)
     }
@@ -96,8 +97,9 @@ where
     fn extract_bound(ob: &Bound<'py, PyAny>) -> Result<Self, PyErr> {
         let dict = ob.downcast::<PyDict>()?;
         let mut ret = collections::BTreeMap::new();
-        for (k, v) in dict {
-            ret.insert(k.extract()?, v.extract()?);
+        for item in dict.iter_typed() {
+            let (k, v) = item?;
+            ret.insert(k, v);
         }
         Ok(ret)
     }
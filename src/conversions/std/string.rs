@@ -138,10 +138,13 @@ impl<'a> crate::conversion::FromPyObjectBound<'a, '_> for &'a str {
     }
 }
 
+/// Extracts a `Cow` which borrows the UTF-8 data cached by the source `str` object where the
+/// Python version makes it available, avoiding any copying or heap allocations. Otherwise the
+/// contents are copied to an owned `Cow`.
 #[cfg(feature = "gil-refs")]
 impl<'py> FromPyObject<'py> for Cow<'py, str> {
     fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
-        ob.extract().map(Cow::Owned)
+        ob.downcast::<PyString>()?.clone().into_gil_ref().to_cow()
     }
 
     #[cfg(feature = "experimental-inspect")]
@@ -150,6 +153,9 @@ impl<'py> FromPyObject<'py> for Cow<'py, str> {
     }
 }
 
+/// Extracts a `Cow` which borrows the UTF-8 data cached by the source `str` object where the
+/// Python version makes it available, avoiding any copying or heap allocations. Otherwise the
+/// contents are copied to an owned `Cow`.
 #[cfg(not(feature = "gil-refs"))]
 impl<'a> crate::conversion::FromPyObjectBound<'a, '_> for Cow<'a, str> {
     fn from_py_object_bound(ob: crate::Borrowed<'a, '_, PyAny>) -> PyResult<Self> {
@@ -243,6 +249,16 @@ mod tests {
         })
     }
 
+    #[test]
+    #[cfg(any(Py_3_10, not(Py_LIMITED_API)))]
+    fn test_extract_cow_borrows() {
+        Python::with_gil(|py| {
+            let py_string = crate::types::PyString::new_bound(py, "Hello Python");
+            let s: Cow<'_, str> = py_string.extract().unwrap();
+            assert!(matches!(s, Cow::Borrowed("Hello Python")));
+        })
+    }
+
     #[test]
     fn test_extract_char() {
         Python::with_gil(|py| {
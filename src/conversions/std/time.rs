@@ -1,10 +1,11 @@
-use crate::exceptions::{PyOverflowError, PyValueError};
+use crate::exceptions::{PyOverflowError, PyTypeError, PyValueError};
 use crate::sync::GILOnceCell;
 use crate::types::any::PyAnyMethods;
 #[cfg(Py_LIMITED_API)]
 use crate::types::PyType;
 #[cfg(not(Py_LIMITED_API))]
 use crate::types::{timezone_utc_bound, PyDateTime, PyDelta, PyDeltaAccess};
+use crate::types::{PyBool, PyFloat, PyFloatMethods, PyLong};
 #[cfg(Py_LIMITED_API)]
 use crate::Py;
 use crate::{
@@ -16,6 +17,24 @@ const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
 
 impl FromPyObject<'_> for Duration {
     fn extract_bound(obj: &Bound<'_, PyAny>) -> PyResult<Self> {
+        // A plain number is taken as a number of seconds, like `time.sleep` does
+        if let Ok(seconds) = obj.downcast::<PyFloat>() {
+            return duration_from_secs_f64(seconds.value());
+        }
+        if obj.is_instance_of::<PyBool>() {
+            // `bool` is a subclass of `int`, but `True` seconds is most likely a mistake
+            return Err(PyTypeError::new_err(
+                "It is not possible to convert a bool to a Rust Duration",
+            ));
+        }
+        if obj.is_instance_of::<PyLong>() {
+            return match obj.extract::<u64>() {
+                Ok(seconds) => Ok(Duration::from_secs(seconds)),
+                Err(_) if obj.lt(0)? => Err(PyValueError::new_err(NEGATIVE_SECONDS)),
+                Err(err) => Err(err),
+            };
+        }
+
         #[cfg(not(Py_LIMITED_API))]
         let (days, seconds, microseconds) = {
             let delta = obj.downcast::<PyDelta>()?;
@@ -51,6 +70,26 @@ impl FromPyObject<'_> for Duration {
     }
 }
 
+const NEGATIVE_SECONDS: &str =
+    "It is not possible to convert a negative number of seconds to a Rust Duration";
+
+fn duration_from_secs_f64(seconds: f64) -> PyResult<Duration> {
+    if seconds.is_nan() {
+        Err(PyValueError::new_err(
+            "It is not possible to convert NaN seconds to a Rust Duration",
+        ))
+    } else if seconds < 0.0 {
+        Err(PyValueError::new_err(NEGATIVE_SECONDS))
+    } else if seconds >= u64::MAX as f64 {
+        // `u64::MAX as f64` rounds up, so this also rejects infinity and the rounded value itself
+        Err(PyOverflowError::new_err(
+            "Too many seconds to convert to a Rust Duration",
+        ))
+    } else {
+        Ok(Duration::from_secs_f64(seconds))
+    }
+}
+
 impl ToPyObject for Duration {
     fn to_object(&self, py: Python<'_>) -> PyObject {
         let days = self.as_secs() / SECONDS_PER_DAY;
@@ -213,6 +252,39 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_duration_frompyobject_seconds() {
+        Python::with_gil(|py| {
+            let extract = |seconds: &str| py.eval_bound(seconds, None, None)?.extract::<Duration>();
+            assert_eq!(extract("0").unwrap(), Duration::new(0, 0));
+            assert_eq!(extract("90").unwrap(), Duration::new(90, 0));
+            assert_eq!(extract("1.5").unwrap(), Duration::new(1, 500_000_000));
+            assert_eq!(extract("2**64 - 1").unwrap(), Duration::new(u64::MAX, 0));
+
+            for negative in ["-1", "-0.5"] {
+                assert_eq!(
+                    extract(negative).unwrap_err().to_string(),
+                    "ValueError: It is not possible to convert a negative number of seconds to a Rust Duration"
+                );
+            }
+            assert!(extract("float('nan')")
+                .unwrap_err()
+                .is_instance_of::<PyValueError>(py));
+            assert!(extract("float('inf')")
+                .unwrap_err()
+                .is_instance_of::<PyOverflowError>(py));
+            assert!(extract("2**64")
+                .unwrap_err()
+                .is_instance_of::<PyOverflowError>(py));
+            for boolean in ["True", "False"] {
+                assert_eq!(
+                    extract(boolean).unwrap_err().to_string(),
+                    "TypeError: It is not possible to convert a bool to a Rust Duration"
+                );
+            }
+        })
+    }
+
     #[test]
     fn test_duration_topyobject() {
         Python::with_gil(|py| {
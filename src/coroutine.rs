@@ -25,6 +25,36 @@ pub use cancel::CancelHandle;
 
 const COROUTINE_REUSED_ERROR: &str = "cannot reuse already awaited coroutine";
 
+/// A hook wrapping every poll of the Rust future of a [`Coroutine`], see [`set_runner`].
+///
+/// The runner must call `poll` exactly once.
+pub type Runner = fn(poll: &mut dyn FnMut());
+
+static RUNNER: parking_lot::Mutex<Option<Runner>> = parking_lot::const_mutex(None);
+
+/// Sets the hook run around every poll of the Rust futures of `async fn`s, or removes it with
+/// `None`, and returns the previously set runner.
+///
+/// The futures are still driven by the Python event loop, but the runner can set up the context
+/// they expect, e.g. enter a Rust async runtime running in the background so that its I/O
+/// resources and timers can be used.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// static RUNTIME: Lazy<tokio::runtime::Runtime> = Lazy::new(|| tokio::runtime::Runtime::new().unwrap());
+///
+/// fn enter_tokio(poll: &mut dyn FnMut()) {
+///     let _guard = RUNTIME.enter();
+///     poll()
+/// }
+///
+/// pyo3::coroutine::set_runner(Some(enter_tokio));
+/// ```
+pub fn set_runner(runner: Option<Runner>) -> Option<Runner> {
+    std::mem::replace(&mut *RUNNER.lock(), runner)
+}
+
 /// Python coroutine wrapping a [`Future`].
 #[pyclass(crate = "crate")]
 pub struct Coroutine {
@@ -89,9 +119,19 @@ impl Coroutine {
             self.waker = Some(Arc::new(AsyncioWaker::new()));
         }
         let waker = Waker::from(self.waker.clone().unwrap());
-        // poll the Rust future and forward its results if ready
+        // poll the Rust future (within the runner, if any) and forward its results if ready
         // polling is UnwindSafe because the future is dropped in case of panic
-        let poll = || future_rs.as_mut().poll(&mut Context::from_waker(&waker));
+        let runner = *RUNNER.lock();
+        let poll = || match runner {
+            Some(runner) => {
+                let mut poll = None;
+                runner(&mut || {
+                    poll = Some(future_rs.as_mut().poll(&mut Context::from_waker(&waker)));
+                });
+                poll.expect("coroutine runner did not poll the future")
+            }
+            None => future_rs.as_mut().poll(&mut Context::from_waker(&waker)),
+        };
         match panic::catch_unwind(panic::AssertUnwindSafe(poll)) {
             Ok(Poll::Ready(res)) => {
                 self.close();
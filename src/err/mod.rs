@@ -191,6 +191,48 @@ impl PyErr {
         })))
     }
 
+    /// Creates a new PyErr of type `T` whose `__cause__` is `cause`, like Python's
+    /// `raise T(*args) from cause`.
+    ///
+    /// As with [`PyErr::new`], the exception is only created when it is first needed.
+    /// See [`PyErr::with_cause`] to chain an existing error instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pyo3::exceptions::{PyOSError, PyRuntimeError};
+    /// use pyo3::prelude::*;
+    ///
+    /// #[pyfunction]
+    /// fn connect() -> PyResult<()> {
+    ///     let cause = PyOSError::new_err("connection refused");
+    ///     Err(PyErr::new_with_cause::<PyRuntimeError, _>("cannot reach the server", cause))
+    /// }
+    ///
+    /// Python::with_gil(|py| {
+    ///     let f = wrap_pyfunction_bound!(connect, py).unwrap();
+    ///     pyo3::py_run!(py, f, r#"
+    ///         try:
+    ///             f()
+    ///         except RuntimeError as e:
+    ///             assert isinstance(e.__cause__, OSError)
+    ///     "#);
+    /// });
+    /// ```
+    pub fn new_with_cause<T, A>(args: A, cause: PyErr) -> PyErr
+    where
+        T: PyTypeInfo,
+        A: PyErrArguments + Send + Sync + 'static,
+    {
+        PyErr::from_state(PyErrState::Lazy(Box::new(move |py| {
+            let err = PyErr::new::<T, A>(args).with_cause(py, cause);
+            PyErrStateLazyFnOutput {
+                ptype: err.get_type_bound(py).into(),
+                pvalue: err.into_value(py).into(),
+            }
+        })))
+    }
+
     /// Deprecated form of [`PyErr::from_type_bound`]
     #[cfg_attr(
         not(feature = "gil-refs"),
@@ -382,6 +424,28 @@ impl PyErr {
     }
 
     /// Gets whether an error is present in the Python interpreter's global state.
+    ///
+    /// Unlike [`PyErr::take`], this does not clear the error, so it can be used to decide how to
+    /// proceed (e.g. after a raw FFI call) before committing to fetching it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pyo3::{exceptions::PyValueError, PyErr, Python};
+    ///
+    /// Python::with_gil(|py| {
+    ///     assert!(!PyErr::occurred(py));
+    ///
+    ///     PyValueError::new_err("oops").restore(py);
+    ///     assert!(PyErr::occurred(py));
+    ///     // Checking does not clear the error...
+    ///     assert!(PyErr::occurred(py));
+    ///
+    ///     // ...but taking it does.
+    ///     assert!(PyErr::take(py).is_some());
+    ///     assert!(!PyErr::occurred(py));
+    /// });
+    /// ```
     #[inline]
     pub fn occurred(_: Python<'_>) -> bool {
         unsafe { !ffi::PyErr_Occurred().is_null() }
@@ -889,6 +953,131 @@ impl PyErr {
         }
     }
 
+    /// Sets `cause` as the cause of this exception and returns it, like Python's
+    /// `raise ... from cause`.
+    ///
+    /// This is a builder-style form of [`PyErr::set_cause`], convenient when converting a
+    /// lower-level error into a different exception without losing it from the traceback.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pyo3::exceptions::{PyKeyError, PyValueError};
+    /// use pyo3::prelude::*;
+    ///
+    /// #[pyfunction]
+    /// fn parse_setting(py: Python<'_>, key: &str) -> PyResult<i64> {
+    ///     let lookup = PyKeyError::new_err(key.to_owned());
+    ///     Err(PyValueError::new_err("invalid setting").with_cause(py, lookup))
+    /// }
+    ///
+    /// Python::with_gil(|py| {
+    ///     let f = wrap_pyfunction_bound!(parse_setting, py).unwrap();
+    ///     pyo3::py_run!(py, f, r#"
+    ///         try:
+    ///             f("timeout")
+    ///         except ValueError as e:
+    ///             assert isinstance(e.__cause__, KeyError)
+    ///     "#);
+    /// });
+    /// ```
+    pub fn with_cause(self, py: Python<'_>, cause: PyErr) -> Self {
+        self.set_cause(py, Some(cause));
+        self
+    }
+
+    /// Creates an exception group holding `errors`.
+    ///
+    /// This is equivalent to the Python expression `BaseExceptionGroup(message, errors)`. As in
+    /// Python, the result is an `ExceptionGroup` if all of `errors` are instances of `Exception`,
+    /// so it can be handled with `except*` as well as `except Exception`.
+    ///
+    /// If the group cannot be created, e.g. because `errors` is empty, the error raised by the
+    /// constructor is returned instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pyo3::exceptions::{PyTypeError, PyValueError};
+    /// use pyo3::prelude::*;
+    ///
+    /// Python::with_gil(|py| {
+    ///     let group = PyErr::new_group(
+    ///         py,
+    ///         "task failures",
+    ///         vec![PyValueError::new_err("bad value"), PyTypeError::new_err("bad type")],
+    ///     );
+    ///     assert_eq!(group.group_exceptions(py).map(|errs| errs.len()), Some(2));
+    ///
+    ///     let (values, rest) = group.split_group::<PyValueError>(py).unwrap();
+    ///     assert_eq!(values.unwrap().group_exceptions(py).unwrap().len(), 1);
+    ///     assert_eq!(rest.unwrap().group_exceptions(py).unwrap().len(), 1);
+    /// });
+    /// ```
+    #[cfg(Py_3_11)]
+    pub fn new_group(
+        py: Python<'_>,
+        message: &str,
+        errors: impl IntoIterator<Item = PyErr>,
+    ) -> PyErr {
+        let errors: Vec<_> = errors.into_iter().map(|err| err.into_value(py)).collect();
+        let errors = crate::types::PyList::new_bound(py, errors);
+        match exceptions::PyBaseExceptionGroup::type_object_bound(py).call1((message, errors)) {
+            Ok(group) => PyErr::from_value_bound(group),
+            Err(err) => err,
+        }
+    }
+
+    /// Returns the exceptions directly contained in this error, if it is an exception group.
+    ///
+    /// This is equivalent to the Python expression `self.exceptions`; returns `None` if the error
+    /// is not an instance of `BaseExceptionGroup`.
+    #[cfg(Py_3_11)]
+    pub fn group_exceptions(&self, py: Python<'_>) -> Option<Vec<PyErr>> {
+        let value = self.value_bound(py);
+        if !value.is_instance_of::<exceptions::PyBaseExceptionGroup>() {
+            return None;
+        }
+        let exceptions = value.getattr(crate::intern!(py, "exceptions")).ok()?;
+        exceptions
+            .iter()
+            .ok()?
+            .map(|exc| exc.map(PyErr::from_value_bound))
+            .collect::<PyResult<_>>()
+            .ok()
+    }
+
+    /// Splits this error into the parts which are and are not instances of `T`, like an
+    /// `except* T` clause does.
+    ///
+    /// For an exception group this is equivalent to the Python expression `self.split(T)`: each
+    /// part is a new group preserving the nested structure, or `None` if it would be empty. Any
+    /// other error is returned whole on the side it belongs to.
+    #[cfg(Py_3_11)]
+    pub fn split_group<T>(&self, py: Python<'_>) -> PyResult<(Option<PyErr>, Option<PyErr>)>
+    where
+        T: PyTypeInfo,
+    {
+        let value = self.value_bound(py);
+        if !value.is_instance_of::<exceptions::PyBaseExceptionGroup>() {
+            return Ok(if self.is_instance_of::<T>(py) {
+                (Some(self.clone_ref(py)), None)
+            } else {
+                (None, Some(self.clone_ref(py)))
+            });
+        }
+        let (matched, rest): (
+            Option<Bound<'_, PyBaseException>>,
+            Option<Bound<'_, PyBaseException>>,
+        ) = value
+            .call_method1(crate::intern!(py, "split"), (T::type_object_bound(py),))?
+            .extract()?;
+        Ok((
+            matched.map(|exc| PyErr::from_value_bound(exc.into_any())),
+            rest.map(|exc| PyErr::from_value_bound(exc.into_any())),
+        ))
+    }
+
     #[inline]
     fn from_state(state: PyErrState) -> PyErr {
         PyErr {
@@ -1320,6 +1509,96 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_pyerr_with_cause() {
+        use crate::types::any::PyAnyMethods;
+        Python::with_gil(|py| {
+            let cause = exceptions::PyKeyError::new_err("apple");
+            let cause_value = cause.value_bound(py).clone();
+            let err = exceptions::PyValueError::new_err("banana").with_cause(py, cause);
+
+            let value = err.value_bound(py);
+            assert!(value.getattr("__cause__").unwrap().is(&cause_value));
+            // as with `raise ... from ...`, only the cause is shown in tracebacks
+            assert!(value
+                .getattr("__suppress_context__")
+                .unwrap()
+                .extract::<bool>()
+                .unwrap());
+        });
+    }
+
+    #[test]
+    fn test_pyerr_new_with_cause() {
+        use crate::types::any::PyAnyMethods;
+        Python::with_gil(|py| {
+            let cause = exceptions::PyKeyError::new_err("apple");
+            let cause_value = cause.value_bound(py).clone();
+            let err = PyErr::new_with_cause::<exceptions::PyValueError, _>("banana", cause);
+
+            assert!(err.is_instance_of::<exceptions::PyValueError>(py));
+            let value = err.value_bound(py);
+            assert_eq!(value.to_string(), "banana");
+            assert!(value.getattr("__cause__").unwrap().is(&cause_value));
+        });
+    }
+
+    #[test]
+    #[cfg(Py_3_11)]
+    fn test_exception_group() {
+        use crate::types::{dict::PyDictMethods, typeobject::PyTypeMethods, PyDict};
+        Python::with_gil(|py| {
+            let group = PyErr::new_group(
+                py,
+                "failures",
+                vec![
+                    PyValueError::new_err("a"),
+                    PyErr::new_group(py, "nested", vec![PyTypeError::new_err("b")]),
+                ],
+            );
+            assert_eq!(group.get_type_bound(py).name().unwrap(), "ExceptionGroup");
+            let exceptions = group.group_exceptions(py).unwrap();
+            assert_eq!(exceptions.len(), 2);
+            assert!(exceptions[0].is_instance_of::<PyValueError>(py));
+            assert!(PyValueError::new_err("a").group_exceptions(py).is_none());
+
+            let (matched, rest) = group.split_group::<PyTypeError>(py).unwrap();
+            assert_eq!(
+                matched.unwrap().to_string(),
+                "ExceptionGroup: failures (1 sub-exception)"
+            );
+            assert!(
+                rest.unwrap().group_exceptions(py).unwrap()[0].is_instance_of::<PyValueError>(py)
+            );
+
+            let (matched, rest) = PyValueError::new_err("c")
+                .split_group::<PyValueError>(py)
+                .unwrap();
+            assert!(matched.is_some() && rest.is_none());
+
+            let locals = PyDict::new_bound(py);
+            locals.set_item("group", group.into_value(py)).unwrap();
+            py.run_bound(
+                r#"
+caught = []
+try:
+    raise group
+except* ValueError as eg:
+    caught.append(eg)
+except* TypeError as eg:
+    caught.append(eg)
+assert len(caught) == 2
+"#,
+                None,
+                Some(&locals),
+            )
+            .unwrap();
+
+            let err = PyErr::new_group(py, "empty", vec![]);
+            assert!(err.is_instance_of::<PyValueError>(py));
+        });
+    }
+
     #[test]
     fn warnings() {
         use crate::types::any::PyAnyMethods;
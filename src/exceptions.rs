@@ -9,10 +9,15 @@
 //! yourself to import Python classes that are ultimately derived from
 //! `BaseException`.
 
-use crate::{ffi, Bound, PyResult, Python};
+use crate::{ffi, Bound, PyErr, PyResult, Python};
+use parking_lot::{const_mutex, Mutex};
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
 use std::ffi::CStr;
+use std::fmt::Display;
 use std::ops;
 use std::os::raw::c_char;
+use std::sync::Arc;
 
 /// The boilerplate to convert between a Rust type and a Python exception.
 #[doc(hidden)]
@@ -768,6 +773,76 @@ impl_native_exception!(
     native_doc!("EncodingWarning")
 );
 
+type ErrorMapper<E> = Box<dyn Fn(E) -> PyErr + Send + Sync>;
+
+/// Conversions registered with [`register_mapper`], keyed by the `TypeId` of the error type.
+static ERROR_MAPPERS: Mutex<Option<HashMap<TypeId, Arc<dyn Any + Send + Sync>>>> =
+    const_mutex(None);
+
+/// Registers the conversion to `PyErr` of the error type `E`, for `#[pyfunction]`s and
+/// `#[pymethods]` returning `Result<T, E>` where `E` does not implement `Into<PyErr>`.
+///
+/// This allows returning errors from crates you don't own, for which the orphan rule forbids
+/// implementing `From<E> for PyErr`. Errors without a registered mapper are raised as
+/// `RuntimeError` with their `Display` text. Registering a mapper for a type which already has
+/// one replaces it.
+///
+/// # Examples
+///
+/// ```
+/// use pyo3::exceptions::{register_mapper, PyValueError};
+/// use pyo3::prelude::*;
+/// # use std::fmt;
+///
+/// // Imagine this error type is defined in another crate.
+/// #[derive(Debug)]
+/// pub struct ParseError(String);
+/// # impl fmt::Display for ParseError {
+/// #     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+/// #         write!(f, "failed to parse {:?}", self.0)
+/// #     }
+/// # }
+///
+/// #[pyfunction]
+/// fn parse(input: &str) -> Result<u32, ParseError> {
+///     input.parse().map_err(|_| ParseError(input.to_owned()))
+/// }
+///
+/// register_mapper(|e: ParseError| PyValueError::new_err(e.to_string()));
+/// Python::with_gil(|py| {
+///     let parse = wrap_pyfunction_bound!(parse, py).unwrap();
+///     let err = parse.call1(("x",)).unwrap_err();
+///     assert!(err.is_instance_of::<PyValueError>(py));
+/// });
+/// ```
+pub fn register_mapper<E, F>(mapper: F)
+where
+    E: Display + 'static,
+    F: Fn(E) -> PyErr + Send + Sync + 'static,
+{
+    let mapper: ErrorMapper<E> = Box::new(mapper);
+    ERROR_MAPPERS
+        .lock()
+        .get_or_insert_with(HashMap::new)
+        .insert(TypeId::of::<E>(), Arc::new(mapper));
+}
+
+/// Converts `error` with the mapper registered for `E`, or else to a `RuntimeError`.
+pub(crate) fn map_error<E: Display + 'static>(error: E) -> PyErr {
+    // Release the lock before calling the mapper, which may register other mappers.
+    let mapper = ERROR_MAPPERS
+        .lock()
+        .as_ref()
+        .and_then(|mappers| mappers.get(&TypeId::of::<E>()).cloned());
+    match mapper
+        .as_ref()
+        .and_then(|mapper| mapper.downcast_ref::<ErrorMapper<E>>())
+    {
+        Some(mapper) => mapper(error),
+        None => PyRuntimeError::new_err(error.to_string()),
+    }
+}
+
 #[cfg(test)]
 macro_rules! test_exception {
     ($exc_ty:ident $(, |$py:tt| $constructor:expr )?) => {
@@ -153,13 +153,96 @@ where
     drop(pool);
 
     // Finalize the Python interpreter.
+    prepare_finalization(Python::assume_gil_acquired());
     ffi::Py_Finalize();
 
     result
 }
 
+/// Finalizes a Python interpreter which was initialized by [`prepare_freethreaded_python`].
+///
+/// This acquires the GIL, releases the Python objects held in PyO3's global state and then calls
+/// `Py_Finalize`. The following are cleared before finalization:
+/// - reference count changes which PyO3 deferred because the GIL was not held (e.g. a `Py<T>`
+///   dropped on another thread) are applied, so that those objects are freed;
+/// - the modules cached by [`Python::import_bound_cached`] are released.
+///
+/// Values stored in statics such as [`GILOnceCell`](crate::sync::GILOnceCell) (which includes
+/// `#[pyclass]` type objects and strings created by [`intern!`](crate::intern)) are not cleared.
+/// They are leaked, and must not be used after finalization.
+///
+/// If the Python interpreter is not initialized, this function has no effect.
+///
+/// # Panics
+/// - If the GIL is held by the current thread, e.g. when called inside [`Python::with_gil`].
+///
+/// # Safety
+/// - This function should only be called once per process, from the thread which initialized the
+///   interpreter. It is not thread-safe; no other threads may be running Python code.
+/// - No Python APIs can be used after this function has been called, including dropping any
+///   `Py<T>` which is still alive.
+///
+/// # Examples
+/// ```rust
+/// use pyo3::prelude::*;
+///
+/// # fn main() -> PyResult<()> {
+/// pyo3::prepare_freethreaded_python();
+/// Python::with_gil(|py| -> PyResult<()> {
+///     let json = py.import_bound_cached("json")?;
+///     json.call_method1("dumps", ("Hello World",))?;
+///     Ok(())
+/// })?;
+/// unsafe { pyo3::finalize_python() };
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(not(any(PyPy, GraalPy)))]
+pub unsafe fn finalize_python() {
+    assert!(
+        !gil_is_acquired(),
+        "called `finalize_python` while the GIL is held by the current thread."
+    );
+
+    if ffi::Py_IsInitialized() == 0 {
+        return;
+    }
+
+    // Restores the thread state saved by `prepare_freethreaded_python`; Python does not need the
+    // GIL to be released again after finalization.
+    ffi::PyGILState_Ensure();
+    prepare_finalization(Python::assume_gil_acquired());
+    ffi::Py_Finalize();
+}
+
+/// Releases the Python objects held in PyO3's global state, ahead of `Py_Finalize`.
+#[cfg(not(any(PyPy, GraalPy)))]
+unsafe fn prepare_finalization(py: Python<'_>) {
+    crate::marker::drop_import_cache();
+    POOL.update_counts(py);
+}
+
 /// RAII type that represents the Global Interpreter Lock acquisition.
-pub(crate) struct GILGuard {
+///
+/// Created by [`Python::acquire_gil`]; the GIL is released when the guard is dropped. Use
+/// [`GILGuard::python`] to obtain a [`Python`] token, whose lifetime is tied to the borrow of the
+/// guard so that no token (nor any `Bound<'py, T>` derived from it) can outlive the guard.
+///
+/// `GILGuard` is neither `Send` nor `Sync`, so it cannot be moved to (or shared with) another
+/// thread, including into a [`Python::allow_threads`] closure.
+///
+/// The guard does not implement `Deref<Target = Python>`: `Deref::Target` cannot borrow from the
+/// guard, so such an implementation would hand out a token which outlives it. Using the token
+/// after the guard has been dropped is rejected at compile time:
+///
+/// ```compile_fail
+/// # use pyo3::prelude::*;
+/// let gil = Python::acquire_gil();
+/// let py = gil.python();
+/// drop(gil);
+/// py.None();
+/// ```
+pub struct GILGuard {
     gstate: ffi::PyGILState_STATE,
     #[allow(deprecated)] // TODO: remove this with the gil-refs feature in 0.22
     pool: mem::ManuallyDrop<GILPool>,
@@ -229,6 +312,16 @@ impl GILGuard {
 
         Some(GILGuard { gstate, pool })
     }
+
+    /// Gets the Python token associated with this [`GILGuard`].
+    ///
+    /// The token borrows the guard, so it (and everything bound to it) must go out of scope
+    /// before the guard is dropped.
+    #[inline]
+    pub fn python(&self) -> Python<'_> {
+        // SAFETY: the GIL is held for as long as `self` is alive.
+        unsafe { Python::assume_gil_acquired() }
+    }
 }
 
 /// The Drop implementation for `GILGuard` will release the GIL.
@@ -310,6 +403,12 @@ impl SuspendGIL {
 
 impl Drop for SuspendGIL {
     fn drop(&mut self) {
+        // A `GILGuard` acquired inside `allow_threads` must not outlive the closure, otherwise
+        // this thread would still hold the GIL when the suspended thread state is restored.
+        assert!(
+            !gil_is_acquired(),
+            "a `GILGuard` acquired inside `Python::allow_threads` was not dropped before the closure returned"
+        );
         GIL_COUNT.with(|c| c.set(self.count));
         unsafe {
             ffi::PyEval_RestoreThread(self.tstate);
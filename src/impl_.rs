@@ -12,6 +12,10 @@ pub mod deprecations;
 pub mod extract_argument;
 pub mod freelist;
 pub mod frompyobject;
+#[cfg(feature = "macros")]
+pub mod iterator;
+#[cfg(feature = "macros")]
+pub mod lazy_classattr;
 pub(crate) mod not_send;
 pub mod panic;
 pub mod pycell;
@@ -19,6 +23,11 @@ pub mod pyclass;
 pub mod pyfunction;
 pub mod pymethods;
 pub mod pymodule;
+#[cfg(feature = "macros")]
+pub mod signature_function;
 #[doc(hidden)]
 pub mod trampoline;
 pub mod wrap;
+
+#[cfg(feature = "macros")]
+pub use pyo3_macros::resolve_shared_signature;
@@ -3,9 +3,14 @@ use crate::{
     exceptions::PyTypeError,
     ffi,
     pyclass::boolean_struct::False,
-    types::{any::PyAnyMethods, dict::PyDictMethods, tuple::PyTupleMethods, PyDict, PyTuple},
-    Borrowed, Bound, PyAny, PyClass, PyErr, PyRef, PyRefMut, PyResult, PyTypeCheck, Python,
+    types::{
+        any::PyAnyMethods, dict::PyDictMethods, string::PyStringMethods, tuple::PyTupleMethods,
+        typeobject::PyTypeMethods, PyDict, PyFloat, PyString, PyTuple,
+    },
+    Borrowed, Bound, DowncastError, FromPyObject, PyAny, PyClass, PyErr, PyRef, PyRefMut, PyResult,
+    PyTypeCheck, Python,
 };
+use std::marker::PhantomData;
 
 /// Helper type used to keep implementation more concise.
 ///
@@ -193,6 +198,160 @@ pub fn from_py_with_with_default<'a, 'py, T>(
     }
 }
 
+/// Alternative to [`extract_argument`] used for `#[pyo3(kwargs_struct)]` arguments, which extract
+/// the collected `**kwargs` (or an empty dict, if no keyword arguments were passed) into `T`.
+///
+/// `fields` are the keys of `T` if it is known to be extracted from a dict by key, as
+/// `(key, required)` pairs (see [`KwargsStructFields`]). Then unknown and missing required keys
+/// raise `TypeError`, and missing optional keys are passed to `T` as `None`.
+#[doc(hidden)]
+pub fn extract_kwargs_struct<'py, T>(
+    py: Python<'py>,
+    description: &FunctionDescription,
+    kwargs: Option<&Bound<'py, PyAny>>,
+    fields: Option<&[(&str, bool)]>,
+    arg_name: &str,
+) -> PyResult<T>
+where
+    T: FromPyObject<'py>,
+{
+    let kwargs = match kwargs {
+        Some(kwargs) => kwargs.downcast::<PyDict>()?.clone(),
+        None => PyDict::new_bound(py),
+    };
+    let kwargs = match fields {
+        Some(fields) => complete_kwargs_struct(description, &kwargs, fields)?,
+        None => kwargs,
+    };
+    kwargs
+        .extract()
+        .map_err(|e| argument_extraction_error(py, arg_name, e))
+}
+
+fn complete_kwargs_struct<'py>(
+    description: &FunctionDescription,
+    kwargs: &Bound<'py, PyDict>,
+    fields: &[(&str, bool)],
+) -> PyResult<Bound<'py, PyDict>> {
+    for key in kwargs.keys() {
+        let known = match key.downcast::<PyString>() {
+            Ok(key) => {
+                let key = key.to_cow()?;
+                fields.iter().any(|(name, _)| *name == key)
+            }
+            Err(_) => false,
+        };
+        if !known {
+            return Err(description.unexpected_keyword_argument(key.as_borrowed()));
+        }
+    }
+    let completed = kwargs.copy()?;
+    let mut missing = Vec::new();
+    for &(name, required) in fields {
+        if !completed.contains(name)? {
+            if required {
+                missing.push(name);
+            } else {
+                completed.set_item(name, kwargs.py().None())?;
+            }
+        }
+    }
+    if !missing.is_empty() {
+        return Err(description.missing_required_arguments("keyword", &missing));
+    }
+    Ok(completed)
+}
+
+/// Implemented by `#[derive(FromPyObject)]` for structs whose fields are all extracted by string
+/// key, so that `#[pyo3(kwargs_struct)]` arguments can check the keyword arguments passed.
+#[doc(hidden)]
+pub trait KwargsStructFields {
+    /// The keys of the struct, and whether each of them is required (i.e. not an `Option`).
+    const FIELDS: &'static [(&'static str, bool)];
+}
+
+/// Finds the [`KwargsStructFields`] of `T`, if it implements it, using autoref specialization.
+#[doc(hidden)]
+pub struct KwargsStructProbe<T>(PhantomData<T>);
+
+impl<T> KwargsStructProbe<T> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> Default for KwargsStructProbe<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[doc(hidden)]
+pub trait ProbeKwargsStructFields {
+    fn kwargs_struct_fields(&self) -> Option<&'static [(&'static str, bool)]>;
+}
+
+impl<T: KwargsStructFields> ProbeKwargsStructFields for KwargsStructProbe<T> {
+    fn kwargs_struct_fields(&self) -> Option<&'static [(&'static str, bool)]> {
+        Some(T::FIELDS)
+    }
+}
+
+#[doc(hidden)]
+pub trait ProbeNoKwargsStructFields {
+    fn kwargs_struct_fields(&self) -> Option<&'static [(&'static str, bool)]> {
+        None
+    }
+}
+
+impl<T> ProbeNoKwargsStructFields for &KwargsStructProbe<T> {}
+
+/// Runs a `#[pyo3(validate = ...)]` function on an extracted argument.
+#[doc(hidden)]
+pub fn validate_argument<T: ?Sized>(
+    py: Python<'_>,
+    value: &T,
+    arg_name: &str,
+    validator: impl FnOnce(&T) -> PyResult<()>,
+) -> PyResult<()> {
+    validator(value).map_err(|e| argument_extraction_error(py, arg_name, e))
+}
+
+/// Types which can be used with `#[pyo3(strict)]` arguments.
+///
+/// Strict extraction only accepts instances of the matching Python type, rejecting objects
+/// which the regular `FromPyObject` implementation would convert (e.g. `int` for `f64`).
+pub trait StrictArgument: Sized {
+    fn extract_strict(obj: &Bound<'_, PyAny>) -> PyResult<Self>;
+}
+
+macro_rules! strict_float_argument {
+    ($t:ty) => {
+        impl StrictArgument for $t {
+            fn extract_strict(obj: &Bound<'_, PyAny>) -> PyResult<Self> {
+                if obj.is_instance_of::<PyFloat>() {
+                    obj.extract()
+                } else {
+                    Err(DowncastError::new(obj, "float").into())
+                }
+            }
+        }
+    };
+}
+
+strict_float_argument!(f32);
+strict_float_argument!(f64);
+
+impl<T: StrictArgument> StrictArgument for Option<T> {
+    fn extract_strict(obj: &Bound<'_, PyAny>) -> PyResult<Self> {
+        if obj.is_none() {
+            Ok(None)
+        } else {
+            T::extract_strict(obj).map(Some)
+        }
+    }
+}
+
 /// Adds the argument name to the error message of an error which occurred during argument extraction.
 ///
 /// Only modifies TypeError. (Cannot guarantee all exceptions have constructors from
@@ -216,6 +375,56 @@ pub fn argument_extraction_error(py: Python<'_>, arg_name: &str, error: PyErr) -
     }
 }
 
+/// Extracts `*args: Vec<T>` element by element, so that a failed conversion names the offending
+/// element and its position in the call. `first_position` is the 1-based position of the first
+/// element of `args`.
+///
+/// Like [`argument_extraction_error`], only modifies TypeError.
+#[doc(hidden)]
+pub fn extract_varargs_vec<'py, T>(
+    args: &Bound<'py, PyTuple>,
+    arg_name: &str,
+    first_position: usize,
+) -> PyResult<Vec<T>>
+where
+    T: FromPyObject<'py>,
+{
+    args.iter()
+        .enumerate()
+        .map(|(index, item)| {
+            item.extract().map_err(|error| {
+                varargs_extraction_error(args.py(), arg_name, index, first_position + index, error)
+            })
+        })
+        .collect()
+}
+
+#[cold]
+fn varargs_extraction_error(
+    py: Python<'_>,
+    arg_name: &str,
+    index: usize,
+    position: usize,
+    error: PyErr,
+) -> PyErr {
+    if error
+        .get_type_bound(py)
+        .is(&py.get_type_bound::<PyTypeError>())
+    {
+        let remapped_error = PyTypeError::new_err(format!(
+            "argument '{}[{}]' (positional argument {}): {}",
+            arg_name,
+            index,
+            position,
+            error.value_bound(py)
+        ));
+        remapped_error.set_cause(py, error.cause(py));
+        remapped_error
+    } else {
+        error
+    }
+}
+
 /// Unwraps the Option<&PyAny> produced by the FunctionDescription `extract_arguments_` methods.
 /// They check if required methods are all provided.
 ///
@@ -410,6 +619,82 @@ impl FunctionDescription {
         Ok((varargs, varkeywords))
     }
 
+    /// Equivalent of `extract_arguments_tuple_dict` for `#[pyo3(unpack)]` functions, which are
+    /// called with a single argument: a tuple of the positional arguments or a dict of the
+    /// keyword arguments.
+    ///
+    /// A dict of keyword arguments is copied into `packed_kwargs`, which must be kept alive until
+    /// the arguments have been extracted: the caller still owns the dict, and extracting an
+    /// argument can run Python code which modifies it.
+    ///
+    /// # Safety
+    /// - `args` must be a pointer to a PyTuple.
+    /// - `kwargs` must be a pointer to a PyDict, or NULL.
+    pub unsafe fn extract_arguments_unpacked<'py, V, K>(
+        &self,
+        py: Python<'py>,
+        args: *mut ffi::PyObject,
+        kwargs: *mut ffi::PyObject,
+        output: &mut [Option<PyArg<'py>>],
+        packed_kwargs: &mut Option<Bound<'py, PyDict>>,
+    ) -> PyResult<(V::Varargs, K::Varkeywords)>
+    where
+        V: VarargsHandler<'py>,
+        K: VarkeywordsHandler<'py>,
+    {
+        // Safety: as for `extract_arguments_tuple_dict`
+        let args: Borrowed<'py, 'py, PyTuple> =
+            Borrowed::from_ptr(py, args).downcast_unchecked::<PyTuple>();
+        let kwargs: Option<Borrowed<'py, 'py, PyDict>> =
+            Borrowed::from_ptr_or_opt(py, kwargs).map(|kwargs| kwargs.downcast_unchecked());
+
+        let has_kwargs = matches!(kwargs, Some(kwargs) if !kwargs.is_empty());
+        if args.len() != 1 || has_kwargs {
+            return Err(PyTypeError::new_err(format!(
+                "{} takes a single tuple or dict of arguments",
+                self.full_name()
+            )));
+        }
+
+        // A packed tuple is owned by `args` and cannot change, so the arguments borrowed from it
+        // below live as long as the call.
+        let packed = PyTupleMethods::get_borrowed_item(&*args, 0)?;
+        if let Ok(packed_args) = packed.downcast::<PyTuple>() {
+            self.extract_arguments_tuple_dict::<V, K>(
+                py,
+                packed_args.as_ptr(),
+                std::ptr::null_mut(),
+                output,
+            )
+        } else if let Ok(dict) = packed.downcast::<PyDict>() {
+            // Like for `f(**kwargs)`, borrow the arguments from a copy of the dict
+            let packed_kwargs = packed_kwargs.insert(dict.copy()?);
+            // `handle_kwargs` relies on the keys being strings, as they are for `**kwargs`
+            if packed_kwargs
+                .iter()
+                .any(|(key, _)| !key.is_instance_of::<PyString>())
+            {
+                return Err(PyTypeError::new_err(format!(
+                    "{} keywords must be strings",
+                    self.full_name()
+                )));
+            }
+            let no_args = PyTuple::empty_bound(py);
+            self.extract_arguments_tuple_dict::<V, K>(
+                py,
+                no_args.as_ptr(),
+                packed_kwargs.as_ptr(),
+                output,
+            )
+        } else {
+            Err(PyTypeError::new_err(format!(
+                "{} argument must be a tuple or dict, not '{}'",
+                self.full_name(),
+                packed.get_type().qualname()?
+            )))
+        }
+    }
+
     #[inline]
     fn handle_kwargs<'py, K, I>(
         &self,
@@ -536,6 +821,38 @@ impl FunctionDescription {
         Ok(())
     }
 
+    /// Checks the number of arguments collected into `*args` against the bounds given by
+    /// `#[pyo3(min_args = ..., max_args = ...)]`.
+    #[doc(hidden)]
+    pub fn check_varargs_arity(
+        &self,
+        varargs: &Bound<'_, PyTuple>,
+        min_args: Option<usize>,
+        max_args: Option<usize>,
+    ) -> PyResult<()> {
+        let args_provided = varargs.len();
+        let (bound, expected) = match (min_args, max_args) {
+            (Some(min), _) if args_provided < min => ("at least", min),
+            (_, Some(max)) if args_provided > max => ("at most", max),
+            _ => return Ok(()),
+        };
+        let was = if args_provided == 1 { "was" } else { "were" };
+        let arguments = if expected == 1 {
+            "argument"
+        } else {
+            "arguments"
+        };
+        Err(PyTypeError::new_err(format!(
+            "{} takes {} {} variadic positional {} but {} {} given",
+            self.full_name(),
+            bound,
+            expected,
+            arguments,
+            args_provided,
+            was
+        )))
+    }
+
     #[cold]
     fn too_many_positional_arguments(&self, args_provided: usize) -> PyErr {
         let was = if args_provided == 1 { "was" } else { "were" };
@@ -624,7 +941,7 @@ impl FunctionDescription {
             .collect();
 
         debug_assert!(!missing_keyword_only_arguments.is_empty());
-        self.missing_required_arguments("keyword", &missing_keyword_only_arguments)
+        self.missing_required_arguments("keyword-only", &missing_keyword_only_arguments)
     }
 
     #[cold]
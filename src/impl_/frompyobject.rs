@@ -56,6 +56,38 @@ pub fn failed_to_extract_enum(
     PyTypeError::new_err(err_msg)
 }
 
+/// Reads the discriminant of an enum deriving `FromPyObject` with `#[pyo3(tag = "...")]`.
+pub fn extract_enum_tag(obj: &Bound<'_, PyAny>, type_name: &str, tag: &str) -> PyResult<String> {
+    let py = obj.py();
+    let value = obj.get_item(tag).map_err(|err| {
+        let new_err = PyTypeError::new_err(format!(
+            "failed to extract enum {}: missing tag '{}'",
+            type_name, tag
+        ));
+        new_err.set_cause(py, Some(err));
+        new_err
+    })?;
+    value.extract().map_err(|err| {
+        let new_err = PyTypeError::new_err(format!(
+            "failed to extract enum {}: tag '{}' must be a str",
+            type_name, tag
+        ));
+        new_err.set_cause(py, Some(err));
+        new_err
+    })
+}
+
+#[cold]
+pub fn unknown_enum_tag(type_name: &str, tag: &str, value: &str, tag_values: &[&str]) -> PyErr {
+    PyTypeError::new_err(format!(
+        "failed to extract enum {}: unknown tag '{}' value '{}', expected one of: '{}'",
+        type_name,
+        tag,
+        value,
+        tag_values.join("', '")
+    ))
+}
+
 /// Flattens a chain of errors into a single string.
 fn extract_traceback(py: Python<'_>, mut error: PyErr) -> String {
     use std::fmt::Write;
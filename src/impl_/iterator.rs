@@ -0,0 +1,50 @@
+//! Python iterator wrapping a Rust [`Iterator`], used when a `#[pyfunction]`/`#[pymethods]`
+//! returns `impl Iterator`.
+
+use pyo3_macros::{pyclass, pymethods};
+
+use crate::{impl_::wrap::OkWrap, Bound, IntoPy, PyErr, PyObject, PyResult, Python};
+
+type NextFn = Box<dyn FnMut(Python<'_>) -> Option<PyResult<PyObject>> + Send>;
+
+/// Python iterator pulling items from a Rust iterator one `__next__` call at a time.
+#[pyclass(crate = "crate")]
+pub struct RustIterator {
+    next: NextFn,
+}
+
+impl RustIterator {
+    /// Wraps `iter`, whose items may be values or `Result`s of values.
+    ///
+    /// Each item is only converted to Python when it is requested; an `Err` item is raised
+    /// from that `__next__` call and iteration may continue afterwards.
+    pub fn new<I, T>(mut iter: I) -> Self
+    where
+        I: Iterator + Send + 'static,
+        I::Item: OkWrap<T>,
+        <I::Item as OkWrap<T>>::Error: Into<PyErr>,
+        T: IntoPy<PyObject>,
+    {
+        Self {
+            next: Box::new(move |py| {
+                let item = iter.next()?;
+                Some(
+                    OkWrap::wrap(item)
+                        .map(|value| value.into_py(py))
+                        .map_err(Into::into),
+                )
+            }),
+        }
+    }
+}
+
+#[pymethods(crate = "crate")]
+impl RustIterator {
+    fn __iter__(slf: Bound<'_, Self>) -> Bound<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        (self.next)(py).transpose()
+    }
+}
@@ -0,0 +1,104 @@
+//! Descriptor computing a `#[classattr(lazy)]` on first access.
+
+use std::thread::{self, ThreadId};
+
+use parking_lot::{const_mutex, Condvar, Mutex};
+use pyo3_macros::{pyclass, pymethods};
+
+use crate::{
+    exceptions::PyRuntimeError, sync::GILOnceCell, Bound, IntoPy, Py, PyAny, PyObject, PyResult,
+    Python,
+};
+
+/// Placed in the type dict in place of the value of a `#[classattr(lazy)]`, and computes the
+/// value when it is first read from the class or an instance.
+///
+/// The value is cached, and the attribute function runs once even when several threads read the
+/// attribute: while one thread runs it, the others wait with the GIL released. If it fails, the
+/// error is raised from that read and the function runs again on the next one.
+#[pyclass(crate = "crate", frozen)]
+pub struct LazyClassAttribute {
+    value: GILOnceCell<PyObject>,
+    init: fn(Python<'_>) -> PyResult<PyObject>,
+    /// The thread currently running `init`, if any.
+    initializing: Mutex<Option<ThreadId>>,
+    initialized: Condvar,
+}
+
+impl LazyClassAttribute {
+    pub fn new(init: fn(Python<'_>) -> PyResult<PyObject>) -> Self {
+        Self {
+            value: GILOnceCell::new(),
+            init,
+            initializing: const_mutex(None),
+            initialized: Condvar::new(),
+        }
+    }
+
+    fn get_or_try_init(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let thread_id = thread::current().id();
+        loop {
+            if let Some(value) = self.value.get(py) {
+                return Ok(value.clone_ref(py));
+            }
+            let mut initializing = self.initializing.lock();
+            match *initializing {
+                None => {
+                    *initializing = Some(thread_id);
+                    break;
+                }
+                Some(id) if id == thread_id => {
+                    return Err(PyRuntimeError::new_err(
+                        "lazy class attribute accessed during its own initialization",
+                    ))
+                }
+                Some(_) => {
+                    // The initializing thread may need the GIL to finish
+                    drop(initializing);
+                    py.allow_threads(|| {
+                        let mut initializing = self.initializing.lock();
+                        while initializing.is_some() {
+                            self.initialized.wait(&mut initializing);
+                        }
+                    });
+                }
+            }
+        }
+
+        // Wakes up waiting threads even if `init` panics
+        struct Guard<'a>(&'a LazyClassAttribute);
+
+        impl Drop for Guard<'_> {
+            fn drop(&mut self) {
+                *self.0.initializing.lock() = None;
+                self.0.initialized.notify_all();
+            }
+        }
+
+        let _guard = Guard(self);
+        let value = (self.init)(py)?;
+        // Only this thread sets the value, so the cell is still empty
+        let _ = self.value.set(py, value.clone_ref(py));
+        Ok(value)
+    }
+}
+
+#[pymethods(crate = "crate")]
+impl LazyClassAttribute {
+    fn __get__(
+        &self,
+        py: Python<'_>,
+        _instance: &Bound<'_, PyAny>,
+        _owner: &Bound<'_, PyAny>,
+    ) -> PyResult<PyObject> {
+        self.get_or_try_init(py)
+    }
+}
+
+/// Creates the descriptor of a `#[classattr(lazy)]` computed by `init`.
+pub fn lazy_class_attribute(
+    py: Python<'_>,
+    init: fn(Python<'_>) -> PyResult<PyObject>,
+) -> PyResult<PyObject> {
+    Py::new(py, LazyClassAttribute::new(init)).map(|attribute| attribute.into_py(py))
+}
@@ -1,27 +1,56 @@
 use crate::{
-    exceptions::{PyAttributeError, PyNotImplementedError, PyRuntimeError, PyValueError},
+    exceptions::{
+        PyAttributeError, PyIndexError, PyNotImplementedError, PyRuntimeError, PyValueError,
+    },
     ffi,
     impl_::freelist::FreeList,
     impl_::pycell::{GetBorrowChecker, PyClassMutability, PyClassObjectLayout},
     internal_tricks::extract_c_string,
     pyclass_init::PyObjectInit,
     types::any::PyAnyMethods,
-    types::PyBool,
-    Borrowed, Py, PyAny, PyClass, PyErr, PyMethodDefType, PyNativeType, PyResult, PyTypeInfo,
-    Python,
+    types::{PyBool, PyStringMethods, PyTypeMethods},
+    Borrowed, Bound, IntoPy, Py, PyAny, PyClass, PyErr, PyMethodDefType, PyNativeType, PyObject,
+    PyResult, PyTypeInfo, Python,
 };
 use std::{
     borrow::Cow,
+    cell::UnsafeCell,
     ffi::{CStr, CString},
     marker::PhantomData,
     os::raw::{c_int, c_void},
     ptr::NonNull,
+    sync::atomic::AtomicUsize,
     thread,
 };
 
 mod lazy_type_object;
 pub use lazy_type_object::LazyTypeObject;
 
+/// Instance counters of `#[pyclass(track_instances)]` types, keyed by type object address.
+static INSTANCE_COUNTERS: parking_lot::Mutex<Vec<(usize, &'static AtomicUsize)>> =
+    parking_lot::const_mutex(Vec::new());
+
+/// Makes the instance counter of a `#[pyclass(track_instances)]` type discoverable from its type
+/// object, see [`PyTypeMethods::instance_count`](crate::types::PyTypeMethods::instance_count).
+pub(crate) fn register_instance_counter(
+    type_object: *mut ffi::PyTypeObject,
+    counter: &'static AtomicUsize,
+) {
+    INSTANCE_COUNTERS
+        .lock()
+        .push((type_object as usize, counter));
+}
+
+pub(crate) fn instance_counter_of(
+    type_object: *mut ffi::PyTypeObject,
+) -> Option<&'static AtomicUsize> {
+    INSTANCE_COUNTERS
+        .lock()
+        .iter()
+        .find(|(ptr, _)| *ptr == type_object as usize)
+        .map(|(_, counter)| *counter)
+}
+
 /// Gets the offset of the dictionary from the start of the object in bytes.
 #[inline]
 pub fn dict_offset<T: PyClass>() -> ffi::Py_ssize_t {
@@ -105,6 +134,233 @@ impl PyClassWeakRef for PyClassWeakRefSlot {
     }
 }
 
+/// Represents the storage of `#[pyo3(get, cache)]` field getters for `#[pyclass]`.
+pub trait PyClassGetterCache {
+    /// Initial form of the cache, with no values stored.
+    const INIT: Self;
+    /// Whether the cache can store any values, and so needs to be cleared.
+    const HAS_GETTER_CACHE: bool = false;
+    /// Returns the value cached for getter `index`, computing and storing it with `f` if needed.
+    fn get_or_insert_with(
+        &self,
+        py: Python<'_>,
+        index: usize,
+        f: impl FnOnce() -> PyObject,
+    ) -> PyObject;
+    /// Drops all cached values, so that the next access of each getter converts the field again.
+    #[inline]
+    fn clear(&self, _py: Python<'_>) {}
+    private_decl! {}
+}
+
+impl PyClassGetterCache for PyClassDummySlot {
+    private_impl! {}
+    const INIT: Self = PyClassDummySlot;
+    #[inline]
+    fn get_or_insert_with(
+        &self,
+        _py: Python<'_>,
+        _index: usize,
+        f: impl FnOnce() -> PyObject,
+    ) -> PyObject {
+        f()
+    }
+}
+
+#[allow(clippy::declare_interior_mutable_const)]
+const EMPTY_GETTER_CACHE_ENTRY: UnsafeCell<Option<PyObject>> = UnsafeCell::new(None);
+
+/// Actual getter cache, which holds the converted values of `N` fields.
+///
+/// `#[pyclass]` automatically adds this when at least one field is annotated with
+/// `#[pyo3(get, cache)]`.
+pub struct PyClassGetterCacheSlot<const N: usize>([UnsafeCell<Option<PyObject>>; N]);
+
+impl<const N: usize> PyClassGetterCache for PyClassGetterCacheSlot<N> {
+    private_impl! {}
+    const INIT: Self = Self([EMPTY_GETTER_CACHE_ENTRY; N]);
+    const HAS_GETTER_CACHE: bool = N > 0;
+    fn get_or_insert_with(
+        &self,
+        py: Python<'_>,
+        index: usize,
+        f: impl FnOnce() -> PyObject,
+    ) -> PyObject {
+        let entry = self.0[index].get();
+        // Safety: the GIL is held, and the entry is never borrowed across code which can run
+        // Python (and therefore re-enter this getter).
+        if let Some(value) = unsafe { &*entry } {
+            return value.clone_ref(py);
+        }
+        let value = f();
+        let previous = unsafe { (*entry).replace(value.clone_ref(py)) };
+        drop(previous);
+        value
+    }
+    fn clear(&self, _py: Python<'_>) {
+        for entry in &self.0 {
+            // Safety: as above; the value is dropped only after the entry has been emptied.
+            drop(unsafe { (*entry.get()).take() });
+        }
+    }
+}
+
+/// Implementation of `#[pyo3(get, cache)]` field getters.
+///
+/// # Safety
+/// `slf` must be a pointer to an instance of `T` (or a subclass) which is currently borrowed.
+pub unsafe fn cached_getter<T: PyClass>(
+    py: Python<'_>,
+    slf: *mut ffi::PyObject,
+    index: usize,
+    f: impl FnOnce() -> PyObject,
+) -> PyResult<*mut ffi::PyObject> {
+    let class_object = &*slf.cast::<PyClassObject<T>>();
+    Ok(class_object
+        .getter_cache()
+        .get_or_insert_with(py, index, f)
+        .into_ptr())
+}
+
+/// Implementation of `__getbuffer__` for `#[pyclass(buffer)]`.
+///
+/// The object stays borrowed (mutably for writable buffers) until [`class_buffer_release`].
+///
+/// # Safety
+/// `view` must be null or point to a `Py_buffer` to fill.
+#[cfg(any(not(Py_LIMITED_API), Py_3_11))]
+pub unsafe fn class_buffer_get<T: crate::buffer::PyClassBuffer>(
+    slf: &Bound<'_, T>,
+    view: *mut ffi::Py_buffer,
+    flags: c_int,
+) -> PyResult<()> {
+    use crate::{exceptions::PyBufferError, pycell::impl_::PyClassBorrowChecker};
+
+    if view.is_null() {
+        return Err(PyBufferError::new_err("View is null"));
+    }
+    let format = CString::new(T::FORMAT)
+        .map_err(|_| PyBufferError::new_err("buffer format must not contain a nul byte"))?;
+    check_buffer_format::<T::Item>(&format)?;
+    let format = if flags & ffi::PyBUF_FORMAT == ffi::PyBUF_FORMAT {
+        format.into_raw()
+    } else {
+        std::ptr::null_mut()
+    };
+
+    let class_object = slf.get_class_object();
+    let writable = flags & ffi::PyBUF_WRITABLE == ffi::PyBUF_WRITABLE;
+    let data = if writable {
+        let data = if <T::Frozen as crate::pyclass::boolean_struct::private::Boolean>::VALUE {
+            Err(PyBufferError::new_err("Object is not writable"))
+        } else {
+            match class_object.borrow_checker().try_borrow_mut() {
+                Ok(()) => match (*class_object.get_ptr()).buffer_mut() {
+                    Some(data) => Ok(data),
+                    None => {
+                        class_object.borrow_checker().release_borrow_mut();
+                        Err(PyBufferError::new_err("Object is not writable"))
+                    }
+                },
+                Err(e) => Err(PyBufferError::new_err(format!(
+                    "cannot export a writable buffer: {}",
+                    e
+                ))),
+            }
+        };
+        data.map(|data| (data.as_mut_ptr(), data.len()))
+    } else {
+        slf.try_borrow().map_err(Into::into).map(|borrow| {
+            // released again in `class_buffer_release`
+            let data = (*class_object.get_ptr()).buffer();
+            std::mem::forget(borrow);
+            (data.as_ptr() as *mut T::Item, data.len())
+        })
+    };
+    let (buf, len) = match data {
+        Ok(data) => data,
+        Err(e) => {
+            if !format.is_null() {
+                drop(CString::from_raw(format));
+            }
+            return Err(e);
+        }
+    };
+
+    let itemsize = std::mem::size_of::<T::Item>() as ffi::Py_ssize_t;
+    (*view).obj = slf.clone().into_any().into_ptr();
+    (*view).buf = buf.cast();
+    (*view).len = len as ffi::Py_ssize_t * itemsize;
+    (*view).readonly = (!writable).into();
+    (*view).itemsize = itemsize;
+    (*view).format = format;
+    (*view).ndim = 1;
+    // the shape (the number of items) is kept alive by `internal` until the buffer is released
+    let shape = Box::into_raw(Box::new(len as ffi::Py_ssize_t));
+    (*view).internal = shape.cast();
+    (*view).shape = if flags & ffi::PyBUF_ND == ffi::PyBUF_ND {
+        shape
+    } else {
+        std::ptr::null_mut()
+    };
+    (*view).strides = if flags & ffi::PyBUF_STRIDES == ffi::PyBUF_STRIDES {
+        &mut (*view).itemsize
+    } else {
+        std::ptr::null_mut()
+    };
+    (*view).suboffsets = std::ptr::null_mut();
+    Ok(())
+}
+
+/// Checks that the `PyClassBuffer::FORMAT` of a `#[pyclass(buffer)]` describes its items, so that
+/// consumers never read the data as items of a different type or size.
+#[cfg(any(not(Py_LIMITED_API), Py_3_11))]
+fn check_buffer_format<I: crate::buffer::Element>(format: &CStr) -> PyResult<()> {
+    use crate::buffer::ElementType;
+
+    let itemsize = std::mem::size_of::<I>();
+    let size_matches = match ElementType::from_format(format) {
+        ElementType::SignedInteger { bytes }
+        | ElementType::UnsignedInteger { bytes }
+        | ElementType::Float { bytes } => bytes == itemsize,
+        ElementType::Bool => itemsize == 1,
+        // e.g. struct formats, which only the element type can check
+        ElementType::Unknown => true,
+    };
+    if size_matches && I::is_compatible_format(format) {
+        Ok(())
+    } else {
+        Err(crate::exceptions::PyBufferError::new_err(format!(
+            "buffer format {:?} does not describe items of type `{}`",
+            format,
+            std::any::type_name::<I>()
+        )))
+    }
+}
+
+/// Implementation of `__releasebuffer__` for `#[pyclass(buffer)]`.
+///
+/// # Safety
+/// `view` must have been filled by [`class_buffer_get`] for `slf`.
+#[cfg(any(not(Py_LIMITED_API), Py_3_11))]
+pub unsafe fn class_buffer_release<T: crate::buffer::PyClassBuffer>(
+    slf: &Bound<'_, T>,
+    view: *mut ffi::Py_buffer,
+) {
+    use crate::pycell::impl_::PyClassBorrowChecker;
+
+    let borrow_checker = slf.get_class_object().borrow_checker();
+    if (*view).readonly == 0 {
+        borrow_checker.release_borrow_mut();
+    } else {
+        borrow_checker.release_borrow();
+    }
+    if !(*view).format.is_null() {
+        drop(CString::from_raw((*view).format));
+    }
+    drop(Box::from_raw((*view).internal.cast::<ffi::Py_ssize_t>()));
+}
+
 /// This type is used as a "dummy" type on which dtolnay specializations are
 /// applied to apply implementations from `#[pymethods]`
 pub struct PyClassImplCollector<T>(PhantomData<T>);
@@ -154,6 +410,9 @@ pub trait PyClassImpl: Sized + 'static {
     /// #[pyclass(sequence)]
     const IS_SEQUENCE: bool = false;
 
+    /// #[pyclass(getnewargs)]
+    const HAS_GETNEWARGS: bool = false;
+
     /// Base class
     type BaseType: PyTypeInfo + PyClassBaseType;
 
@@ -166,6 +425,9 @@ pub trait PyClassImpl: Sized + 'static {
     /// Specify this class has `#[pyclass(weakref)]` or not.
     type WeakRef: PyClassWeakRef;
 
+    /// Storage for the fields annotated with `#[pyo3(get, cache)]`, if any.
+    type GetterCache: PyClassGetterCache;
+
     /// The closest native ancestor. This is `PyAny` by default, and when you declare
     /// `#[pyclass(extends=PyDict)]`, it's `PyDict`.
     type BaseNativeType: PyTypeInfo + PyNativeType;
@@ -197,6 +459,24 @@ pub trait PyClassImpl: Sized + 'static {
         None
     }
 
+    /// Additional base classes from #[pyclass(extends = (Base, Mixin, ...))]
+    #[inline]
+    fn mixins(_py: Python<'_>) -> Vec<*mut ffi::PyTypeObject> {
+        Vec::new()
+    }
+
+    /// Live instance counter for #[pyclass(track_instances)]
+    #[inline]
+    fn instance_counter() -> Option<&'static AtomicUsize> {
+        None
+    }
+
+    /// #[pyclass(on_first_init = ...)]
+    #[inline]
+    fn on_first_init(_py: Python<'_>) -> PyResult<()> {
+        Ok(())
+    }
+
     fn lazy_type_object() -> &'static LazyTypeObject<Self>;
 }
 
@@ -223,6 +503,61 @@ pub fn build_pyclass_doc(
     }
 }
 
+/// Runtime helper implementing `__repr__` for `#[pyclass(repr)]`.
+///
+/// Formats `fields` as `ClassName(name=value, ...)` using the `repr()` of each value, where
+/// unnamed (tuple struct) fields are written positionally. An object which is already being
+/// formatted further up the stack, i.e. which contains itself, is written as `ClassName(...)`.
+pub fn repr_fields(
+    slf: &Bound<'_, PyAny>,
+    fields: &[(Option<&'static str>, Bound<'_, PyAny>)],
+) -> PyResult<String> {
+    let py = slf.py();
+    let class_name = slf.get_type().qualname()?;
+    match unsafe { ffi::Py_ReprEnter(slf.as_ptr()) } {
+        0 => {}
+        -1 => return Err(PyErr::fetch(py)),
+        _ => return Ok(format!("{}(...)", class_name)),
+    }
+    let result = fields
+        .iter()
+        .map(|(name, value)| {
+            let value = value.repr()?;
+            let value = value.to_cow()?;
+            Ok(match name {
+                Some(name) => format!("{}={}", name, value),
+                None => value.into_owned(),
+            })
+        })
+        .collect::<PyResult<Vec<_>>>();
+    unsafe { ffi::Py_ReprLeave(slf.as_ptr()) };
+    Ok(format!("{}({})", class_name, result?.join(", ")))
+}
+
+/// Runtime helper implementing `__class_getitem__` for `#[pyclass(subscriptable)]`.
+///
+/// Returns `types.GenericAlias(cls, item)`, as builtin containers like `list` do. Before Python
+/// 3.9, which has no `types.GenericAlias`, `cls` itself is returned so that annotations such as
+/// `MyClass[int]` still evaluate.
+pub fn class_getitem<'py>(
+    cls: &Bound<'py, crate::types::PyType>,
+    item: &Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyAny>> {
+    #[cfg(Py_3_9)]
+    {
+        use crate::sync::GILOnceCell;
+        static GENERIC_ALIAS: GILOnceCell<Py<crate::types::PyType>> = GILOnceCell::new();
+        GENERIC_ALIAS
+            .get_or_try_init_type_ref(cls.py(), "types", "GenericAlias")?
+            .call1((cls, item))
+    }
+    #[cfg(not(Py_3_9))]
+    {
+        let _ = item;
+        Ok(cls.clone().into_any())
+    }
+}
+
 /// Iterator used to process all class items during type instantiation.
 pub struct PyClassItemsIter {
     /// Iteration state
@@ -1011,6 +1346,33 @@ impl<T> PyClassNewTextSignature<T> for &'_ PyClassImplCollector<T> {
     }
 }
 
+/// Whether a class has `#[pyclass(getnewargs)]`, see [`assert_non_positional_new_args_allowed`].
+pub struct GetNewArgs<const ENABLED: bool>;
+
+/// Implemented unless `#[pyclass(getnewargs)]` is used, which only passes the positional
+/// parameters of `#[new]` to `__new__` and so cannot restore `*args`, `**kwargs` or keyword-only
+/// parameters.
+pub trait NonPositionalNewArgsAllowed {}
+
+impl NonPositionalNewArgsAllowed for GetNewArgs<false> {}
+
+/// Fails to compile when a `#[new]` method with non-positional parameters belongs to a class
+/// with `#[pyclass(getnewargs)]`.
+#[inline]
+pub fn assert_non_positional_new_args_allowed<T: NonPositionalNewArgsAllowed>() {}
+
+// Positional parameter names of __new__, for `#[pyclass(match_args)]` and `#[pyclass(getnewargs)]`
+pub trait PyClassNewMatchArgs<T> {
+    fn new_match_args(self) -> &'static [&'static str];
+}
+
+impl<T> PyClassNewMatchArgs<T> for &'_ PyClassImplCollector<T> {
+    #[inline]
+    fn new_match_args(self) -> &'static [&'static str] {
+        &[]
+    }
+}
+
 // Thread checkers
 
 #[doc(hidden)]
@@ -1126,6 +1488,74 @@ pub(crate) unsafe extern "C" fn tp_dealloc_with_gc<T: PyClass>(obj: *mut ffi::Py
     crate::impl_::trampoline::dealloc(obj, PyClassObject::<T>::tp_dealloc)
 }
 
+/// Returns `index`, counted from the end of `obj` if it is a negative integer and `T` is a
+/// `#[pyclass(sequence)]`, as Python's builtin sequences do.
+///
+/// Negative indices beyond the start of the sequence raise `IndexError`, so the methods of
+/// sequences only ever receive non-negative integer indices.
+///
+/// # Safety
+///
+/// `obj` and `index` must be valid pointers to Python objects.
+pub unsafe fn normalize_sequence_index<'py, T: PyClassImpl>(
+    py: Python<'py>,
+    obj: *mut ffi::PyObject,
+    index: *mut ffi::PyObject,
+) -> PyResult<Bound<'py, PyAny>> {
+    let index = Bound::from_borrowed_ptr(py, index);
+    if !T::IS_SEQUENCE || ffi::PyLong_Check(index.as_ptr()) == 0 {
+        return Ok(index);
+    }
+    let value = ffi::PyNumber_AsSsize_t(index.as_ptr(), ffi::PyExc_IndexError);
+    if value == -1 {
+        if let Some(err) = PyErr::take(py) {
+            return Err(err);
+        }
+    }
+    if value >= 0 {
+        return Ok(index);
+    }
+    let len = ffi::PyObject_Size(obj);
+    if len == -1 {
+        return Err(PyErr::fetch(py));
+    }
+    if value + len < 0 {
+        return Err(PyIndexError::new_err("index out of range"));
+    }
+    Ok((value + len).into_py(py).into_bound(py))
+}
+
+/// `sq_item` of a `#[pyclass(sequence)]`, whose indices Python has already counted from the end.
+pub(crate) unsafe extern "C" fn get_sequence_item(
+    obj: *mut ffi::PyObject,
+    index: ffi::Py_ssize_t,
+) -> *mut ffi::PyObject {
+    if index < 0 {
+        ffi::PyErr_SetString(
+            ffi::PyExc_IndexError,
+            "index out of range\0".as_ptr().cast(),
+        );
+        return std::ptr::null_mut();
+    }
+    get_sequence_item_from_mapping(obj, index)
+}
+
+/// `sq_ass_item` of a `#[pyclass(sequence)]`, see [`get_sequence_item`].
+pub(crate) unsafe extern "C" fn assign_sequence_item(
+    obj: *mut ffi::PyObject,
+    index: ffi::Py_ssize_t,
+    value: *mut ffi::PyObject,
+) -> c_int {
+    if index < 0 {
+        ffi::PyErr_SetString(
+            ffi::PyExc_IndexError,
+            "index out of range\0".as_ptr().cast(),
+        );
+        return -1;
+    }
+    assign_sequence_item_from_mapping(obj, index, value)
+}
+
 pub(crate) unsafe extern "C" fn get_sequence_item_from_mapping(
     obj: *mut ffi::PyObject,
     index: ffi::Py_ssize_t,
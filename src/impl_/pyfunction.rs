@@ -7,8 +7,11 @@ pub use crate::impl_::pymethods::PyMethodDef;
 
 /// Trait to enable the use of `wrap_pyfunction` with both `Python` and `PyModule`,
 /// and also to infer the return type of either `&'py PyCFunction` or `Bound<'py, PyCFunction>`.
-pub trait WrapPyFunctionArg<'py, T> {
-    fn wrap_pyfunction(self, method_def: &PyMethodDef) -> PyResult<T>;
+///
+/// `D` is the definition of the function, which for `#[pyo3(signature_object)]` functions is a
+/// [`PySignatureFunctionDef`][crate::impl_::signature_function::PySignatureFunctionDef].
+pub trait WrapPyFunctionArg<'py, T, D = PyMethodDef> {
+    fn wrap_pyfunction(self, method_def: &D) -> PyResult<T>;
 }
 
 impl<'py> WrapPyFunctionArg<'py, Bound<'py, PyCFunction>> for Bound<'py, PyModule> {
@@ -94,6 +94,16 @@ pub struct PyClassAttributeFactory(pub for<'p> fn(Python<'p>) -> PyResult<PyObje
 // TODO: it would be nice to use CStr in these types, but then the constructors can't be const fn
 // until `CStr::from_bytes_with_nul_unchecked` is const fn.
 
+/// Builds the `inspect.Signature` of a function or method with `#[pyo3(signature_object)]`.
+#[derive(Clone, Copy)]
+pub struct PySignatureObjectFactory(pub for<'py> fn(Python<'py>) -> PyResult<Bound<'py, PyAny>>);
+
+impl std::fmt::Debug for PySignatureObjectFactory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PySignatureObjectFactory")
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct PyMethodDef {
     pub(crate) ml_name: &'static str,
@@ -518,6 +528,17 @@ impl<'a> From<BoundRef<'a, 'a, PyModule>> for &'a PyModule {
     }
 }
 
+impl<'a, 'py, T> TryFrom<BoundRef<'a, 'py, PyModule>> for crate::types::ModuleState<'a, 'py, T>
+where
+    T: Send + Sync + 'static,
+{
+    type Error = PyErr;
+    #[inline]
+    fn try_from(bound: BoundRef<'a, 'py, PyModule>) -> Result<Self, Self::Error> {
+        crate::types::ModuleState::new(bound.0)
+    }
+}
+
 #[allow(deprecated)]
 impl<'a, 'py, T: PyClass> From<BoundRef<'a, 'py, T>> for &'a crate::PyCell<T> {
     #[inline]
@@ -563,6 +584,17 @@ impl<T> From<BoundRef<'_, '_, T>> for Py<T> {
     }
 }
 
+/// Converts the module passed to a `#[pyo3(pass_module)]` function into the type of its first
+/// argument.
+#[inline]
+pub fn extract_module_argument<'a, 'py, T>(module: BoundRef<'a, 'py, PyModule>) -> PyResult<T>
+where
+    T: TryFrom<BoundRef<'a, 'py, PyModule>>,
+    PyErr: From<T::Error>,
+{
+    T::try_from(module).map_err(PyErr::from)
+}
+
 impl<'py, T> std::ops::Deref for BoundRef<'_, 'py, T> {
     type Target = Bound<'py, T>;
     #[inline]
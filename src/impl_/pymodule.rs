@@ -1,6 +1,6 @@
 //! Implementation details of `#[pymodule]` which need to be accessible from proc-macro generated code.
 
-use std::cell::UnsafeCell;
+use std::{any::Any, cell::UnsafeCell, os::raw::c_void};
 
 #[cfg(all(
     not(any(PyPy, GraalPy)),
@@ -27,6 +27,12 @@ pub struct ModuleDef {
     interpreter: AtomicI64,
     /// Initialized module object, cached to avoid reinitialization.
     module: GILOnceCell<Py<PyModule>>,
+    /// Whether the module relies on the GIL (only relevant for free-threaded builds).
+    #[cfg_attr(
+        not(all(not(Py_LIMITED_API), py_sys_config = "Py_GIL_DISABLED")),
+        allow(dead_code)
+    )]
+    gil_used: bool,
 }
 
 /// Wrapper to enable initializer to be used in const fns.
@@ -48,12 +54,12 @@ impl ModuleDef {
             m_base: ffi::PyModuleDef_HEAD_INIT,
             m_name: std::ptr::null(),
             m_doc: std::ptr::null(),
-            m_size: 0,
+            m_size: std::mem::size_of::<ModuleState>() as ffi::Py_ssize_t,
             m_methods: std::ptr::null_mut(),
             m_slots: std::ptr::null_mut(),
             m_traverse: None,
             m_clear: None,
-            m_free: None,
+            m_free: Some(free_module_state),
         };
 
         let ffi_def = UnsafeCell::new(ffi::PyModuleDef {
@@ -73,8 +79,18 @@ impl ModuleDef {
             ))]
             interpreter: AtomicI64::new(-1),
             module: GILOnceCell::new(),
+            gil_used: true,
         }
     }
+
+    /// Declares whether the module relies on the GIL, used for [`#[pyo3(gil_used = ...)]`][crate::pymodule].
+    ///
+    /// On free-threaded builds of Python, a module which declares it does not use the GIL will not
+    /// cause the interpreter to re-enable the GIL when it is imported.
+    pub const fn gil_used(mut self, gil_used: bool) -> Self {
+        self.gil_used = gil_used;
+        self
+    }
     /// Builds a module using user given initializer. Used for [`#[pymodule]`][crate::pymodule].
     pub fn make_module(&'static self, py: Python<'_>) -> PyResult<Py<PyModule>> {
         #[cfg(all(PyPy, not(Py_3_8)))]
@@ -138,6 +154,18 @@ impl ModuleDef {
                         ffi::PyModule_Create(self.ffi_def.get()),
                     )?
                 };
+                unsafe { init_module_state(module.as_ptr()) };
+                #[cfg(all(not(Py_LIMITED_API), py_sys_config = "Py_GIL_DISABLED"))]
+                {
+                    let gil_used = if self.gil_used {
+                        ffi::Py_MOD_GIL_USED
+                    } else {
+                        ffi::Py_MOD_GIL_NOT_USED
+                    };
+                    if unsafe { ffi::PyUnstable_Module_SetGIL(module.as_ptr(), gil_used) } < 0 {
+                        return Err(crate::PyErr::fetch(py));
+                    }
+                }
                 self.initializer.0(module.bind(py))?;
                 Ok(module)
             })
@@ -145,6 +173,52 @@ impl ModuleDef {
     }
 }
 
+/// Marker written into the state of every module created from a [`ModuleDef`], so PyO3 can tell
+/// its own modules apart from modules which use their state storage for something else.
+const MODULE_STATE_MAGIC: usize = 0x5079_4f33;
+
+/// Per-module storage reserved by [`ModuleDef`], see [`PyModuleMethods::set_state`].
+///
+/// [`PyModuleMethods::set_state`]: crate::types::PyModuleMethods::set_state
+#[repr(C)]
+pub(crate) struct ModuleState {
+    magic: usize,
+    pub(crate) value: Option<Box<dyn Any + Send + Sync>>,
+}
+
+unsafe fn init_module_state(module: *mut ffi::PyObject) {
+    let state = ffi::PyModule_GetState(module) as *mut ModuleState;
+    if !state.is_null() {
+        state.write(ModuleState {
+            magic: MODULE_STATE_MAGIC,
+            value: None,
+        });
+    }
+}
+
+/// Returns the state of `module` if it was created from a [`ModuleDef`], or null otherwise.
+///
+/// # Safety
+/// `module` must be a valid pointer to a module object and the GIL must be held.
+pub(crate) unsafe fn get_module_state(module: *mut ffi::PyObject) -> *mut ModuleState {
+    let def = ffi::PyModule_GetDef(module);
+    if def.is_null() || (*def).m_size != std::mem::size_of::<ModuleState>() as ffi::Py_ssize_t {
+        return std::ptr::null_mut();
+    }
+    let state = ffi::PyModule_GetState(module) as *mut ModuleState;
+    if state.is_null() || (*state).magic != MODULE_STATE_MAGIC {
+        return std::ptr::null_mut();
+    }
+    state
+}
+
+unsafe extern "C" fn free_module_state(module: *mut c_void) {
+    let state = get_module_state(module.cast());
+    if !state.is_null() {
+        std::ptr::drop_in_place(state);
+    }
+}
+
 /// Trait to add an element (class, function...) to a module.
 ///
 /// Currently only implemented for classes.
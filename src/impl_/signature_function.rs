@@ -0,0 +1,280 @@
+//! Function objects of `#[pyo3(signature_object)]` functions and methods.
+//!
+//! Builtin function objects have no `__dict__` and cannot be subclassed, so they cannot carry a
+//! `__signature__`. These functions are instead created as a [`SignatureFunction`], which calls
+//! the same generated wrapper and provides the `inspect.Signature` built from the Rust
+//! signature.
+
+use std::ptr::NonNull;
+
+use pyo3_macros::{pyclass, pymethods};
+
+use crate::{
+    exceptions::PyTypeError,
+    ffi,
+    ffi_ptr_ext::FfiPtrExt,
+    impl_::{
+        pyfunction::{OnlyBound, WrapPyFunctionArg},
+        pymethods::{PyMethodDef, PySignatureObjectFactory},
+    },
+    sync::GILOnceCell,
+    types::{
+        any::PyAnyMethods, IntoPyDict, PyCFunction, PyDict, PyModule, PyModuleMethods, PyString,
+        PyTuple, PyTupleMethods,
+    },
+    Borrowed, Bound, Py, PyAny, PyNativeType, PyObject, PyResult, Python,
+};
+
+/// The definition of a `#[pyfunction]` with `#[pyo3(signature_object)]`, which
+/// `wrap_pyfunction!` turns into a [`SignatureFunction`].
+pub struct PySignatureFunctionDef {
+    def: PyMethodDef,
+    signature: PySignatureObjectFactory,
+}
+
+impl PySignatureFunctionDef {
+    pub const fn new(def: PyMethodDef, signature: PySignatureObjectFactory) -> Self {
+        Self { def, signature }
+    }
+}
+
+/// A method definition which is never freed, so that function objects can point to it.
+struct StaticMethodDef(NonNull<ffi::PyMethodDef>);
+
+// Safety: the definition is immutable once created.
+unsafe impl Send for StaticMethodDef {}
+unsafe impl Sync for StaticMethodDef {}
+
+impl StaticMethodDef {
+    fn new(method_def: &PyMethodDef) -> PyResult<Self> {
+        let (def, destructor) = method_def.as_method_def()?;
+        // Like `PyCFunction::internal_new`, leak the def so that it outlives all function objects
+        std::mem::forget(destructor);
+        Ok(Self(NonNull::from(Box::leak(Box::new(def)))))
+    }
+}
+
+enum Callable {
+    /// A `#[pyfunction]`, called through the builtin function created from its definition.
+    Function(Py<PyCFunction>),
+    /// An instance method, which is called with the instance as its first argument.
+    Method(StaticMethodDef),
+}
+
+/// A function or method defined with `#[pyo3(signature_object)]`.
+///
+/// It has the `__name__`, `__qualname__`, `__module__` and `__doc__` of the builtin function it
+/// replaces, and binds to instances like a method when it is a class attribute for an instance
+/// method.
+#[pyclass(crate = "crate", frozen, dict)]
+pub struct SignatureFunction {
+    callable: Callable,
+    factory: PySignatureObjectFactory,
+    signature: GILOnceCell<PyObject>,
+}
+
+impl SignatureFunction {
+    fn create<'py>(
+        py: Python<'py>,
+        callable: Callable,
+        factory: PySignatureObjectFactory,
+        builtin: &Bound<'py, PyAny>,
+        qualname: Option<String>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let function = Bound::new(
+            py,
+            Self {
+                callable,
+                factory,
+                signature: GILOnceCell::new(),
+            },
+        )?
+        .into_any();
+        for attr in ["__name__", "__qualname__", "__module__", "__doc__"] {
+            function.setattr(attr, builtin.getattr(attr)?)?;
+        }
+        if let Some(qualname) = qualname {
+            function.setattr("__qualname__", qualname)?;
+        }
+        Ok(function)
+    }
+
+    fn function<'py>(
+        function: Bound<'py, PyCFunction>,
+        def: &PySignatureFunctionDef,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let py = function.py();
+        let builtin = function.clone().into_any();
+        Self::create(
+            py,
+            Callable::Function(function.unbind()),
+            def.signature,
+            &builtin,
+            None,
+        )
+    }
+}
+
+#[pymethods(crate = "crate")]
+impl SignatureFunction {
+    #[pyo3(signature = (*args, **kwargs))]
+    fn __call__(
+        &self,
+        py: Python<'_>,
+        args: &Bound<'_, PyTuple>,
+        kwargs: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<PyObject> {
+        match &self.callable {
+            Callable::Function(function) => function.bind(py).call(args, kwargs),
+            Callable::Method(def) => {
+                let instance = args.get_item(0).map_err(|_| {
+                    PyTypeError::new_err("unbound method needs an instance as first argument")
+                })?;
+                // the generated wrapper checks the type of the instance
+                let bound = unsafe {
+                    ffi::PyCFunction_NewEx(def.0.as_ptr(), instance.as_ptr(), std::ptr::null_mut())
+                        .assume_owned_or_err(py)?
+                };
+                bound.call(args.get_slice(1, args.len()), kwargs)
+            }
+        }
+        .map(Bound::unbind)
+    }
+
+    fn __get__(
+        slf: &Bound<'_, Self>,
+        instance: &Bound<'_, PyAny>,
+        _owner: &Bound<'_, PyAny>,
+    ) -> PyResult<PyObject> {
+        let py = slf.py();
+        match slf.get().callable {
+            Callable::Method(_) if !instance.is_none() => py
+                .import_bound_cached("types")?
+                .getattr("MethodType")?
+                .call1((slf, instance))
+                .map(Bound::unbind),
+            _ => Ok(slf.clone().into_any().unbind()),
+        }
+    }
+
+    #[getter]
+    fn __signature__(&self, py: Python<'_>) -> PyResult<PyObject> {
+        self.signature
+            .get_or_try_init(py, || (self.factory.0)(py).map(Bound::unbind))
+            .map(|signature| signature.clone_ref(py))
+    }
+}
+
+/// Creates the class attribute of an instance method with `#[pyo3(signature_object)]`.
+pub fn signature_method(
+    py: Python<'_>,
+    method_def: PyMethodDef,
+    class_name: &str,
+    signature: PySignatureObjectFactory,
+) -> PyResult<PyObject> {
+    let def = StaticMethodDef::new(&method_def)?;
+    // only used for its name and doc
+    let builtin = unsafe {
+        ffi::PyCFunction_NewEx(def.0.as_ptr(), std::ptr::null_mut(), std::ptr::null_mut())
+            .assume_owned_or_err(py)?
+    };
+    let qualname = format!("{}.{}", class_name, builtin.getattr("__name__")?);
+    SignatureFunction::create(
+        py,
+        Callable::Method(def),
+        signature,
+        &builtin,
+        Some(qualname),
+    )
+    .map(Bound::unbind)
+}
+
+/// Adds a `#[pyfunction]` with `#[pyo3(signature_object)]` to `module`.
+pub fn add_to_module(module: &Bound<'_, PyModule>, def: &PySignatureFunctionDef) -> PyResult<()> {
+    let function = module.wrap_pyfunction(def)?;
+    let name = function.getattr("__name__")?;
+    module.add(name.downcast_into::<PyString>()?, function)
+}
+
+macro_rules! wrap_signature_function {
+    ($($arg:ty),*) => {$(
+        impl<'py> WrapPyFunctionArg<'py, Bound<'py, PyAny>, PySignatureFunctionDef> for $arg {
+            fn wrap_pyfunction(self, def: &PySignatureFunctionDef) -> PyResult<Bound<'py, PyAny>> {
+                let function: Bound<'py, PyCFunction> = self.wrap_pyfunction(&def.def)?;
+                SignatureFunction::function(function, def)
+            }
+        }
+    )*};
+}
+
+wrap_signature_function!(
+    Bound<'py, PyModule>,
+    &'_ Bound<'py, PyModule>,
+    Borrowed<'_, 'py, PyModule>,
+    &'_ Borrowed<'_, 'py, PyModule>
+);
+
+impl<'py> WrapPyFunctionArg<'py, Bound<'py, PyAny>, PySignatureFunctionDef> for Python<'py> {
+    fn wrap_pyfunction(self, def: &PySignatureFunctionDef) -> PyResult<Bound<'py, PyAny>> {
+        let function = PyCFunction::internal_new(self, &def.def, None)?;
+        SignatureFunction::function(function, def)
+    }
+}
+
+impl<'py> WrapPyFunctionArg<'py, Bound<'py, PyAny>, PySignatureFunctionDef> for &'py PyModule {
+    fn wrap_pyfunction(self, def: &PySignatureFunctionDef) -> PyResult<Bound<'py, PyAny>> {
+        self.as_borrowed().wrap_pyfunction(def)
+    }
+}
+
+impl<'py, T> WrapPyFunctionArg<'py, Bound<'py, PyAny>, PySignatureFunctionDef> for OnlyBound<T>
+where
+    T: WrapPyFunctionArg<'py, Bound<'py, PyAny>, PySignatureFunctionDef>,
+{
+    fn wrap_pyfunction(self, def: &PySignatureFunctionDef) -> PyResult<Bound<'py, PyAny>> {
+        self.0.wrap_pyfunction(def)
+    }
+}
+
+/// A parameter of a generated `inspect.Signature`, see [`signature_object`].
+pub struct SignatureParameter {
+    pub name: &'static str,
+    /// Name of the `inspect.Parameter` kind, e.g. `"POSITIONAL_OR_KEYWORD"`.
+    pub kind: &'static str,
+    /// The default value, converted from the Rust default expression.
+    pub default: Option<PyObject>,
+    /// Python expression evaluated with `typing` in scope, e.g. `"typing.Optional[str]"`.
+    pub annotation: Option<&'static str>,
+}
+
+/// Builds an `inspect.Signature`, used by the code generated for `#[pyo3(signature_object)]`.
+pub fn signature_object<'py>(
+    py: Python<'py>,
+    parameters: Vec<SignatureParameter>,
+    return_annotation: Option<&'static str>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let inspect = py.import_bound_cached("inspect")?;
+    let parameter = inspect.getattr("Parameter")?;
+    let empty = parameter.getattr("empty")?;
+    let globals = [("typing", py.import_bound_cached("typing")?)].into_py_dict_bound(py);
+    let eval = |expression: Option<&str>| match expression {
+        Some(expression) => py.eval_bound(expression, Some(&globals), None),
+        None => Ok(empty.clone()),
+    };
+
+    let parameters = parameters
+        .into_iter()
+        .map(|p| {
+            let kwargs = PyDict::new_bound(py);
+            kwargs.set_item("annotation", eval(p.annotation)?)?;
+            if let Some(default) = p.default {
+                kwargs.set_item("default", default)?;
+            }
+            parameter.call((p.name, parameter.getattr(p.kind)?), Some(&kwargs))
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    let kwargs = [("return_annotation", eval(return_annotation)?)].into_py_dict_bound(py);
+    inspect
+        .getattr("Signature")?
+        .call((parameters,), Some(&kwargs))
+}
@@ -1,6 +1,6 @@
-use std::convert::Infallible;
+use std::{convert::Infallible, fmt::Display};
 
-use crate::{ffi, IntoPy, PyObject, PyResult, Python};
+use crate::{ffi, IntoPy, PyErr, PyObject, PyResult, Python};
 
 /// Used to wrap values in `Option<T>` for default arguments.
 pub trait SomeWrap<T> {
@@ -49,6 +49,46 @@ where
     }
 }
 
+/// Converts the error returned by `#[pyfunction]` and `#[pymethods]` into a `PyErr`, using
+/// autoref specialization: `(&error).py_err_kind().convert(error)` uses `Into<PyErr>` if
+/// implemented, and otherwise the mapper registered with `exceptions::register_mapper`.
+pub struct IntoPyErrTag;
+
+impl IntoPyErrTag {
+    #[inline]
+    pub fn convert<E: Into<PyErr>>(self, error: E) -> PyErr {
+        error.into()
+    }
+}
+
+pub trait IntoPyErrKind {
+    #[inline]
+    fn py_err_kind(&self) -> IntoPyErrTag {
+        IntoPyErrTag
+    }
+}
+
+impl<E: Into<PyErr>> IntoPyErrKind for E {}
+
+/// See [`IntoPyErrTag`].
+pub struct MappedErrTag;
+
+impl MappedErrTag {
+    #[cold]
+    pub fn convert<E: Display + 'static>(self, error: E) -> PyErr {
+        crate::exceptions::map_error(error)
+    }
+}
+
+pub trait MappedErrKind {
+    #[inline]
+    fn py_err_kind(&self) -> MappedErrTag {
+        MappedErrTag
+    }
+}
+
+impl<E: Display + 'static> MappedErrKind for &E {}
+
 /// This is a follow-up function to `OkWrap::wrap` that converts the result into
 /// a `*mut ffi::PyObject` pointer.
 pub fn map_result_into_ptr<T: IntoPy<PyObject>>(
@@ -0,0 +1,140 @@
+//! Contains [`Lazy`], a return value which is only computed if Python asks for it.
+
+use parking_lot::Mutex;
+
+use crate::{
+    exceptions::{PyRuntimeError, PyTypeError},
+    sync::GILOnceCell,
+    types::{tuple::PyTupleMethods, PyCFunction, PyDict, PyTuple},
+    Bound, IntoPy, PyObject, PyResult, Python,
+};
+
+/// A deferred computation which converts to a Python callable.
+///
+/// Returning `Lazy::new(f)` from a `#[pyfunction]` (or anywhere else [`IntoPy`] is used) hands
+/// Python a zero-argument callable instead of a value. `f` is only run the first time the
+/// callable is called, so callers which never use the result never pay for it.
+///
+/// The outcome of `f` is cached: every later call returns the same object, or raises the same
+/// exception if `f` failed. While `f` is running, calling the callable again (e.g. from within
+/// `f` itself) raises `RuntimeError`.
+///
+/// # Examples
+///
+/// ```
+/// use pyo3::lazy::Lazy;
+/// use pyo3::prelude::*;
+///
+/// #[pyfunction]
+/// fn report(py: Python<'_>) -> (u32, PyObject) {
+///     let summary = 42;
+///     let details = Lazy::new(|_py| -> PyResult<_> { Ok(vec![1, 2, 3]) });
+///     (summary, details.into_py(py))
+/// }
+///
+/// Python::with_gil(|py| {
+///     let report = wrap_pyfunction_bound!(report, py).unwrap();
+///     pyo3::py_run!(py, report, r#"
+///         summary, details = report()
+///         assert summary == 42
+///         assert details() == [1, 2, 3]
+///         assert details() is details()
+///     "#);
+/// });
+/// ```
+pub struct Lazy<F>(F);
+
+impl<F> Lazy<F> {
+    /// Wraps `f`, which computes the value on first access.
+    pub fn new<T>(f: F) -> Self
+    where
+        F: FnOnce(Python<'_>) -> PyResult<T> + Send + 'static,
+        T: IntoPy<PyObject>,
+    {
+        Lazy(f)
+    }
+}
+
+impl<F, T> IntoPy<PyObject> for Lazy<F>
+where
+    F: FnOnce(Python<'_>) -> PyResult<T> + Send + 'static,
+    T: IntoPy<PyObject>,
+{
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        let thunk = Mutex::new(Some(self.0));
+        let value: GILOnceCell<PyResult<PyObject>> = GILOnceCell::new();
+        let get = move |args: &Bound<'_, PyTuple>,
+                        kwargs: Option<&Bound<'_, PyDict>>|
+              -> PyResult<PyObject> {
+            let py = args.py();
+            if !args.is_empty() || kwargs.is_some() {
+                return Err(PyTypeError::new_err("lazy value takes no arguments"));
+            }
+            if value.get(py).is_none() {
+                let thunk = thunk.lock().take().ok_or_else(|| {
+                    PyRuntimeError::new_err("lazy value is already being computed")
+                })?;
+                let _ = value.set(py, thunk(py).map(|value| value.into_py(py)));
+            }
+            match value.get(py) {
+                Some(Ok(value)) => Ok(value.clone_ref(py)),
+                Some(Err(err)) => Err(err.clone_ref(py)),
+                None => unreachable!("lazy value was just set"),
+            }
+        };
+        // Only fails if Python is out of memory, which `IntoPy` has no way to report.
+        PyCFunction::new_closure_bound(py, Some("lazy\0"), None, get)
+            .expect("failed to create lazy value")
+            .into_any()
+            .unbind()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exceptions::PyValueError;
+    use crate::types::any::PyAnyMethods;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    #[test]
+    fn test_lazy_computes_once() {
+        Python::with_gil(|py| {
+            let calls = Arc::new(AtomicUsize::new(0));
+            let counter = calls.clone();
+            let lazy = Lazy::new(move |_py| -> PyResult<_> {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Ok("value")
+            })
+            .into_py(py)
+            .into_bound(py);
+            assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+            let first = lazy.call0().unwrap();
+            assert_eq!(first.extract::<&str>().unwrap(), "value");
+            assert!(lazy.call0().unwrap().is(&first));
+            assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+            assert!(lazy
+                .call1((1,))
+                .unwrap_err()
+                .is_instance_of::<PyTypeError>(py));
+        });
+    }
+
+    #[test]
+    fn test_lazy_caches_error() {
+        Python::with_gil(|py| {
+            let lazy = Lazy::new(|_py| -> PyResult<()> { Err(PyValueError::new_err("failed")) })
+                .into_py(py)
+                .into_bound(py);
+            for _ in 0..2 {
+                let err = lazy.call0().unwrap_err();
+                assert_eq!(err.to_string(), "ValueError: failed");
+            }
+        });
+    }
+}
@@ -94,6 +94,9 @@
 //! - `multiple-pymethods`: Enables the use of multiple [`#[pymethods]`](macro@crate::pymethods)
 //! blocks per [`#[pyclass]`](macro@crate::pyclass). This adds a dependency on the [inventory]
 //! crate, which is not supported on all platforms.
+//! - `experimental-stubs`: Enables the `pyo3::stub` module, which writes `.pyi` type stubs for the
+//! classes and functions defined with PyO3's macros. This also adds a dependency on the
+//! [inventory] crate.
 //!
 //! The following features enable interactions with other crates in the Rust ecosystem:
 //! - [`anyhow`]: Enables a conversion from [anyhow]’s [`Error`][anyhow_error] type to [`PyErr`].
@@ -319,10 +322,13 @@ pub use crate::conversion::{FromPyPointer, PyTryFrom, PyTryInto};
 pub use crate::err::{
     DowncastError, DowncastIntoError, PyDowncastError, PyErr, PyErrArguments, PyResult, ToPyErr,
 };
+pub use crate::gil::GILGuard;
 #[allow(deprecated)]
 pub use crate::gil::GILPool;
 #[cfg(not(any(PyPy, GraalPy)))]
-pub use crate::gil::{prepare_freethreaded_python, with_embedded_python_interpreter};
+pub use crate::gil::{
+    finalize_python, prepare_freethreaded_python, with_embedded_python_interpreter,
+};
 pub use crate::instance::{Borrowed, Bound, Py, PyNativeType, PyObject};
 pub use crate::marker::Python;
 #[allow(deprecated)]
@@ -417,9 +423,12 @@ pub use {
     unindent, // Re-exported for py_run
 };
 
-#[cfg(all(feature = "macros", feature = "multiple-pymethods"))]
+#[cfg(all(
+    feature = "macros",
+    any(feature = "multiple-pymethods", feature = "experimental-stubs")
+))]
 #[doc(hidden)]
-pub use inventory; // Re-exported for `#[pyclass]` and `#[pymethods]` with `multiple-pymethods`.
+pub use inventory; // Re-exported for `multiple-pymethods` and `experimental-stubs`.
 
 /// Tests and helpers which reside inside PyO3's main library. Declared first so that macros
 /// are available in unit tests.
@@ -447,6 +456,7 @@ mod gil;
 #[doc(hidden)]
 pub mod impl_;
 mod instance;
+pub mod lazy;
 pub mod marker;
 pub mod marshal;
 #[macro_use]
@@ -457,6 +467,8 @@ pub mod pybacked;
 pub mod pycell;
 pub mod pyclass;
 pub mod pyclass_init;
+#[cfg(feature = "experimental-stubs")]
+pub mod stub;
 
 pub mod type_object;
 pub mod types;
@@ -466,7 +478,7 @@ mod version;
 pub use crate::conversions::*;
 
 #[cfg(feature = "macros")]
-pub use pyo3_macros::{pyfunction, pymethods, pymodule, FromPyObject};
+pub use pyo3_macros::{pyfunction, pymethods, pymodule, FromPyObject, IntoPy};
 
 /// A proc macro used to expose Rust structs and fieldless enums as Python objects.
 ///
@@ -479,6 +491,49 @@ pub use pyo3_macros::{pyfunction, pymethods, pymodule, FromPyObject};
 #[cfg(feature = "macros")]
 pub use pyo3_macros::pyclass;
 
+/// Extracts the arguments of a Python call into Rust values, using the same signature grammar
+/// as `#[pyfunction]`.
+///
+/// The macro takes the positional arguments as `&Bound<'py, PyTuple>`, the keyword arguments
+/// as `Option<&Bound<'py, PyDict>>` and a function signature without a body, optionally
+/// annotated with `#[pyo3(signature = (...))]`. It evaluates to a `PyResult` of a tuple with
+/// one value per argument of the signature. `/`, `*`, `*args` and `**kwargs` are supported and
+/// invalid calls raise the same `TypeError`s as a `#[pyfunction]` would, using the name of the
+/// function in the messages.
+///
+/// The extracted values may not borrow from the arguments, so use owned types such as
+/// `String` or `Bound<'py, PyAny>` rather than `&str` or `&Bound<'py, PyAny>`.
+///
+/// ```
+/// use pyo3::prelude::*;
+/// use pyo3::types::{PyDict, PyTuple};
+///
+/// #[pyfunction]
+/// #[pyo3(signature = (*args, **kwargs))]
+/// fn dispatch(args: &Bound<'_, PyTuple>, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<String> {
+///     let (a, b, c) = pyo3::extract_args!(
+///         args,
+///         kwargs,
+///         #[pyo3(signature = (a, /, b = None, *, c))]
+///         fn dispatch(a: i32, b: Option<i32>, c: bool)
+///     )?;
+///     Ok(format!("{} {:?} {}", a, b, c))
+/// }
+///
+/// # Python::with_gil(|py| {
+/// #     let dispatch = wrap_pyfunction_bound!(dispatch, py).unwrap();
+/// #     pyo3::py_run!(py, dispatch, r#"
+/// #         assert dispatch(1, c=True) == "1 None true"
+/// #         try:
+/// #             dispatch(a=1, c=True)
+/// #         except TypeError as e:
+/// #             assert "positional-only" in str(e)
+/// #     "#);
+/// # });
+/// ```
+#[cfg(feature = "macros")]
+pub use pyo3_macros::extract_args;
+
 #[cfg(feature = "macros")]
 #[macro_use]
 mod macros;
@@ -179,6 +179,63 @@ macro_rules! wrap_pyfunction_bound {
     }};
 }
 
+/// Declares a signature which several `#[pyfunction]`s and `#[pymethods]` can share.
+///
+/// `shared_signature!(name = (...))` declares a macro `name`, which is referenced with
+/// `#[pyo3(signature = name!())]` in place of the signature itself. The signature grammar is the
+/// same as for `#[pyo3(signature = (...))]`.
+///
+/// The declared macro is an ordinary `macro_rules!` macro, so it must be declared before use. To
+/// use it in other modules, add `pub(crate) use name;` after the declaration.
+///
+/// ```
+/// use pyo3::prelude::*;
+///
+/// pyo3::shared_signature!(range_signature = (start, stop = None, *, step = 1));
+///
+/// #[pyfunction]
+/// #[pyo3(signature = range_signature!())]
+/// fn count(start: i64, stop: Option<i64>, step: i64) -> i64 {
+///     (stop.unwrap_or(start) - start) / step
+/// }
+///
+/// #[pyclass]
+/// struct Ranges;
+///
+/// #[pymethods]
+/// impl Ranges {
+///     #[staticmethod]
+///     #[pyo3(signature = range_signature!())]
+///     fn bounds(start: i64, stop: Option<i64>, step: i64) -> (i64, Option<i64>, i64) {
+///         (start, stop, step)
+///     }
+/// }
+///
+/// # Python::with_gil(|py| {
+/// #     let count = wrap_pyfunction_bound!(count, py).unwrap();
+/// #     let ranges = py.get_type_bound::<Ranges>();
+/// #     pyo3::py_run!(py, count ranges, r#"
+/// #         assert count(0, 10, step=2) == 5
+/// #         assert ranges.bounds(3) == (3, None, 1)
+/// #     "#);
+/// # });
+/// ```
+#[macro_export]
+macro_rules! shared_signature {
+    ($name:ident = ($($signature:tt)*)) => {
+        $crate::shared_signature!(@define ($) $name ($($signature)*));
+    };
+    (@define ($d:tt) $name:ident ($($signature:tt)*)) => {
+        macro_rules! $name {
+            ($d kind:ident [$d ($d attr:tt)*] $d ($d item:tt)*) => {
+                $crate::impl_::resolve_shared_signature! {
+                    $crate $d kind [$d ($d attr)*] ($($signature)*) $d ($d item)*
+                }
+            };
+        }
+    };
+}
+
 /// Returns a function that takes a [`Python`](crate::Python) instance and returns a
 /// Python module.
 ///
@@ -130,10 +130,38 @@ use crate::version::PythonVersionInfo;
 use crate::{ffi, Bound, IntoPy, Py, PyNativeType, PyObject, PyTypeCheck, PyTypeInfo};
 #[allow(deprecated)]
 use crate::{gil::GILPool, FromPyPointer};
+use parking_lot::{const_mutex, Mutex};
+use std::collections::HashMap;
 use std::ffi::{CStr, CString};
 use std::marker::PhantomData;
 use std::os::raw::c_int;
 
+/// Modules imported by [`Python::import_bound_cached`].
+static IMPORT_CACHE: Mutex<Option<HashMap<&'static str, Py<PyModule>>>> = const_mutex(None);
+
+/// Releases the modules held by [`Python::import_bound_cached`].
+///
+/// # Safety
+/// The GIL must be held, even if PyO3 does not know about it (e.g. during finalization).
+pub(crate) unsafe fn drop_import_cache() {
+    if let Some(cache) = IMPORT_CACHE.lock().take() {
+        for (_, module) in cache {
+            ffi::Py_DECREF(module.into_ptr());
+        }
+    }
+}
+
+/// Registered with `Py_AtExit` so that the cache does not outlive the interpreter.
+extern "C" fn forget_import_cache() {
+    if let Some(cache) = IMPORT_CACHE.lock().take() {
+        // The interpreter has already been finalized, so the module objects must not be
+        // decref'd; just drop our pointers to them.
+        for (_, module) in cache {
+            std::mem::forget(module);
+        }
+    }
+}
+
 /// Types that are safe to access while the GIL is not held.
 ///
 /// # Safety
@@ -441,6 +469,41 @@ impl Python<'_> {
         f(unsafe { Python::assume_gil_acquired() })
     }
 
+    /// Acquires the global interpreter lock and returns a [`GILGuard`] which releases it on drop.
+    ///
+    /// This is an alternative to [`Python::with_gil`] for call patterns which do not fit a
+    /// closure. The [`Python`] token is obtained from [`GILGuard::python`] and borrows the guard,
+    /// so the borrow checker prevents the token from being used after the guard is dropped.
+    ///
+    /// To keep nested acquisitions from being released out of order, only one `GILGuard` may
+    /// exist on a thread, and only while the GIL is not otherwise held by PyO3. Code which may
+    /// already hold the GIL should use [`Python::with_gil`] instead, which handles nesting.
+    ///
+    /// # Panics
+    ///
+    /// - If the GIL is already held by the current thread, e.g. inside `with_gil` or a
+    ///   `#[pyfunction]`.
+    /// - For the same reasons as [`Python::with_gil`] if the interpreter is not initialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pyo3::prelude::*;
+    ///
+    /// # fn main() -> PyResult<()> {
+    /// let gil = Python::acquire_gil();
+    /// let py = gil.python();
+    /// let x: i32 = py.eval_bound("5", None, None)?.extract()?;
+    /// assert_eq!(x, 5);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn acquire_gil() -> GILGuard {
+        GILGuard::acquire().expect(
+            "`Python::acquire_gil` called while the GIL is already held; use `Python::with_gil`",
+        )
+    }
+
     /// Like [`Python::with_gil`] except Python interpreter state checking is skipped.
     ///
     /// Normally when the GIL is acquired, we check that the Python interpreter is an
@@ -775,6 +838,93 @@ impl<'py> Python<'py> {
         PyModule::import_bound(self, name)
     }
 
+    /// Imports the Python module with the specified name, caching the module object for
+    /// subsequent calls.
+    ///
+    /// The first call for a given `name` goes through Python's import machinery exactly like
+    /// [`Python::import_bound`]. Later calls return the cached module without performing any
+    /// import lookup, which makes this suitable for modules imported repeatedly on hot paths,
+    /// such as `json` or `datetime`.
+    ///
+    /// Because the module is cached, changes to `sys.modules` made after the first import (e.g.
+    /// by `importlib.reload`) are not observed. The cache is cleared when the interpreter is
+    /// finalized.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pyo3::prelude::*;
+    ///
+    /// # fn main() -> PyResult<()> {
+    /// Python::with_gil(|py| -> PyResult<()> {
+    ///     let json = py.import_bound_cached("json")?;
+    ///     assert!(json.is(&py.import_bound_cached("json")?));
+    ///     Ok(())
+    /// })
+    /// # }
+    /// ```
+    pub fn import_bound_cached(self, name: &'static str) -> PyResult<Bound<'py, PyModule>> {
+        if let Some(module) = IMPORT_CACHE
+            .lock()
+            .as_ref()
+            .and_then(|cache| cache.get(name))
+        {
+            return Ok(module.bind(self).clone());
+        }
+
+        // The lock must not be held while importing, as the import may run arbitrary Python
+        // code (including calls back into this function).
+        let module = self.import_bound(name)?;
+        IMPORT_CACHE
+            .lock()
+            .get_or_insert_with(|| {
+                // Safety: `forget_import_cache` does not call into Python.
+                unsafe { ffi::Py_AtExit(Some(forget_import_cache)) };
+                HashMap::new()
+            })
+            .entry(name)
+            .or_insert_with(|| module.clone().unbind());
+        Ok(module)
+    }
+
+    /// Imports the callable at the dotted `path`, such as `"json.loads"`.
+    ///
+    /// Everything before the last `.` is imported as a module (so `"os.path.join"` imports
+    /// `os.path`), and the last component is looked up as an attribute of that module. Fails
+    /// with `ValueError` if `path` contains no `.`, and with `TypeError` if the attribute is not
+    /// callable.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pyo3::prelude::*;
+    ///
+    /// # fn main() -> PyResult<()> {
+    /// Python::with_gil(|py| -> PyResult<()> {
+    ///     let loads = py.import_callable_bound("json.loads")?;
+    ///     let value: Vec<i32> = loads.call1(("[1, 2]",))?.extract()?;
+    ///     assert_eq!(value, [1, 2]);
+    ///     Ok(())
+    /// })
+    /// # }
+    /// ```
+    pub fn import_callable_bound(self, path: &str) -> PyResult<Bound<'py, PyAny>> {
+        let (module, name) = path.rsplit_once('.').ok_or_else(|| {
+            crate::exceptions::PyValueError::new_err(format!(
+                "'{}' is not a dotted path to a callable",
+                path
+            ))
+        })?;
+        let callable = self.import_bound(module)?.getattr(name)?;
+        if !callable.is_callable() {
+            return Err(crate::exceptions::PyTypeError::new_err(format!(
+                "'{}' is not callable",
+                path
+            )));
+        }
+        Ok(callable)
+    }
+
     /// Gets the Python builtin value `None`.
     #[allow(non_snake_case)] // the Python keyword starts with uppercase
     #[inline]
@@ -1344,6 +1494,38 @@ mod tests {
         assert_eq!(state, GIL_NOT_HELD);
     }
 
+    #[test]
+    #[cfg(not(Py_LIMITED_API))]
+    fn test_acquire_gil_guard() {
+        const GIL_NOT_HELD: c_int = 0;
+        const GIL_HELD: c_int = 1;
+
+        let gil = Python::acquire_gil();
+        let state = unsafe { crate::ffi::PyGILState_Check() };
+        assert_eq!(state, GIL_HELD);
+
+        let py = gil.python();
+        assert!(py.None().is_none(py));
+        Python::with_gil(|inner| assert!(inner.None().is_none(inner)));
+
+        py.allow_threads(|| {
+            let state = unsafe { crate::ffi::PyGILState_Check() };
+            assert_eq!(state, GIL_NOT_HELD);
+        });
+
+        drop(gil);
+        let state = unsafe { crate::ffi::PyGILState_Check() };
+        assert_eq!(state, GIL_NOT_HELD);
+    }
+
+    #[test]
+    #[should_panic(expected = "GIL is already held")]
+    fn test_acquire_gil_nested_panics() {
+        Python::with_gil(|_py| {
+            let _gil = Python::acquire_gil();
+        });
+    }
+
     #[test]
     fn test_ellipsis() {
         Python::with_gil(|py| {
@@ -1370,4 +1552,43 @@ mod tests {
             assert!(matches!(namespace.get_item("__builtins__"), Ok(Some(..))));
         })
     }
+
+    #[test]
+    fn test_import_bound_cached() {
+        Python::with_gil(|py| {
+            let first = py.import_bound_cached("json").unwrap();
+            let second = py.import_bound_cached("json").unwrap();
+            assert!(first.is(&second));
+            assert!(first.is(&py.import_bound("json").unwrap()));
+
+            assert!(py
+                .import_bound_cached("this_module_does_not_exist")
+                .unwrap_err()
+                .is_instance_of::<crate::exceptions::PyModuleNotFoundError>(py));
+        })
+    }
+
+    #[test]
+    fn test_import_callable_bound() {
+        Python::with_gil(|py| {
+            let join = py.import_callable_bound("os.path.join").unwrap();
+            assert!(join.is(&py.import_bound("os.path").unwrap().getattr("join").unwrap()));
+
+            let err = py.import_callable_bound("json").unwrap_err();
+            assert!(err.is_instance_of::<crate::exceptions::PyValueError>(py));
+            assert_eq!(
+                err.to_string(),
+                "ValueError: 'json' is not a dotted path to a callable"
+            );
+
+            let err = py.import_callable_bound("sys.version").unwrap_err();
+            assert!(err.is_instance_of::<crate::exceptions::PyTypeError>(py));
+            assert_eq!(err.to_string(), "TypeError: 'sys.version' is not callable");
+
+            assert!(py
+                .import_callable_bound("json.does_not_exist")
+                .unwrap_err()
+                .is_instance_of::<crate::exceptions::PyAttributeError>(py));
+        })
+    }
 }
@@ -22,7 +22,7 @@ pub use crate::types::{PyAny, PyModule};
 pub use crate::PyNativeType;
 
 #[cfg(feature = "macros")]
-pub use pyo3_macros::{pyclass, pyfunction, pymethods, pymodule, FromPyObject};
+pub use pyo3_macros::{pyclass, pyfunction, pymethods, pymodule, FromPyObject, IntoPy};
 
 #[cfg(feature = "macros")]
 pub use crate::{wrap_pyfunction, wrap_pyfunction_bound};
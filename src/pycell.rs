@@ -899,10 +899,12 @@ impl<'p, T: PyClass<Frozen = False>> DerefMut for PyRefMut<'p, T> {
 
 impl<'p, T: PyClass<Frozen = False>> Drop for PyRefMut<'p, T> {
     fn drop(&mut self) {
-        self.inner
-            .get_class_object()
-            .borrow_checker()
-            .release_borrow_mut()
+        let class_object = self.inner.get_class_object();
+        class_object.borrow_checker().release_borrow_mut();
+        // The fields may have changed, so cached getter values are stale.
+        if <PyClassObject<T> as PyClassObjectLayout<T>>::HAS_GETTER_CACHE {
+            class_object.clear_getter_caches(self.inner.py());
+        }
     }
 }
 
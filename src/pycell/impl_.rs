@@ -6,7 +6,8 @@ use std::marker::PhantomData;
 use std::mem::ManuallyDrop;
 
 use crate::impl_::pyclass::{
-    PyClassBaseType, PyClassDict, PyClassImpl, PyClassThreadChecker, PyClassWeakRef,
+    PyClassBaseType, PyClassDict, PyClassGetterCache, PyClassImpl, PyClassThreadChecker,
+    PyClassWeakRef,
 };
 use crate::type_object::{get_tp_free, PyLayout, PySizedLayout};
 use crate::{ffi, PyClass, PyTypeInfo, Python};
@@ -206,6 +207,10 @@ pub trait PyClassObjectLayout<T>: PyLayout<T> {
     /// - slf must be a valid pointer to an instance of a T or a subclass.
     /// - slf must not be used after this call (as it will be freed).
     unsafe fn tp_dealloc(py: Python<'_>, slf: *mut ffi::PyObject);
+    /// Whether this class or one of its bases has `#[pyo3(get, cache)]` getters.
+    const HAS_GETTER_CACHE: bool;
+    /// Drops the values cached by `#[pyo3(get, cache)]` getters of this class and its bases.
+    fn clear_getter_caches(&self, py: Python<'_>);
 }
 
 impl<T, U> PyClassObjectLayout<T> for PyClassObjectBase<U>
@@ -213,10 +218,12 @@ where
     U: PySizedLayout<T>,
     T: PyTypeInfo,
 {
+    const HAS_GETTER_CACHE: bool = false;
     fn ensure_threadsafe(&self) {}
     fn check_threadsafe(&self) -> Result<(), PyBorrowError> {
         Ok(())
     }
+    fn clear_getter_caches(&self, _py: Python<'_>) {}
     unsafe fn tp_dealloc(py: Python<'_>, slf: *mut ffi::PyObject) {
         let type_obj = T::type_object_raw(py);
         // For `#[pyclass]` types which inherit from PyAny, we can just call tp_free
@@ -260,6 +267,7 @@ pub(crate) struct PyClassObjectContents<T: PyClassImpl> {
     pub(crate) thread_checker: T::ThreadChecker,
     pub(crate) dict: T::Dict,
     pub(crate) weakref: T::WeakRef,
+    pub(crate) getter_cache: T::GetterCache,
 }
 
 impl<T: PyClassImpl> PyClassObject<T> {
@@ -293,6 +301,10 @@ impl<T: PyClassImpl> PyClassObject<T> {
 }
 
 impl<T: PyClassImpl> PyClassObject<T> {
+    pub(crate) fn getter_cache(&self) -> &T::GetterCache {
+        &self.contents.getter_cache
+    }
+
     pub(crate) fn borrow_checker(&self) -> &<T::PyClassMutability as PyClassMutability>::Checker {
         T::PyClassMutability::borrow_checker(self)
     }
@@ -305,6 +317,8 @@ impl<T: PyClassImpl> PyClassObjectLayout<T> for PyClassObject<T>
 where
     <T::BaseType as PyClassBaseType>::LayoutAsBase: PyClassObjectLayout<T::BaseType>,
 {
+    const HAS_GETTER_CACHE: bool = <T::GetterCache as PyClassGetterCache>::HAS_GETTER_CACHE
+        || <T::BaseType as PyClassBaseType>::LayoutAsBase::HAS_GETTER_CACHE;
     fn ensure_threadsafe(&self) {
         self.contents.thread_checker.ensure();
         self.ob_base.ensure_threadsafe();
@@ -315,14 +329,22 @@ where
         }
         self.ob_base.check_threadsafe()
     }
+    fn clear_getter_caches(&self, py: Python<'_>) {
+        self.contents.getter_cache.clear(py);
+        self.ob_base.clear_getter_caches(py);
+    }
     unsafe fn tp_dealloc(py: Python<'_>, slf: *mut ffi::PyObject) {
         // Safety: Python only calls tp_dealloc when no references to the object remain.
         let class_object = &mut *(slf.cast::<PyClassObject<T>>());
+        if let Some(counter) = T::instance_counter() {
+            counter.fetch_sub(1, std::sync::atomic::Ordering::Relaxed);
+        }
         if class_object.contents.thread_checker.can_drop(py) {
             ManuallyDrop::drop(&mut class_object.contents.value);
         }
         class_object.contents.dict.clear_dict(py);
         class_object.contents.weakref.clear_weakrefs(slf, py);
+        class_object.contents.getter_cache.clear(py);
         <T::BaseType as PyClassBaseType>::LayoutAsBase::tp_dealloc(py, slf)
     }
 }
@@ -191,6 +191,47 @@ where
     }
 }
 
+/// The cleanup of a `#[pyclass(context_manager)]`, which makes the class usable in a `with`
+/// statement.
+///
+/// The generated `__enter__` returns the object itself, and the generated `__exit__` calls
+/// [`exit`](PyContextManager::exit) with the exception raised in the `with` block, if any.
+///
+/// # Example
+///
+/// ```rust
+/// use pyo3::exceptions::{PyBaseException, PyKeyError};
+/// use pyo3::prelude::*;
+/// use pyo3::pyclass::PyContextManager;
+///
+/// #[pyclass(context_manager)]
+/// struct Transaction {
+///     committed: Option<bool>,
+/// }
+///
+/// impl PyContextManager for Transaction {
+///     fn exit(
+///         slf: &Bound<'_, Self>,
+///         exception: Option<&Bound<'_, PyBaseException>>,
+///     ) -> PyResult<bool> {
+///         // commit unless the `with` block raised, in which case roll back
+///         slf.borrow_mut().committed = Some(exception.is_none());
+///         // swallow `KeyError`s, let any other exception propagate
+///         Ok(exception.map_or(false, |exception| exception.is_instance_of::<PyKeyError>()))
+///     }
+/// }
+/// ```
+pub trait PyContextManager: PyClass {
+    /// Called by `__exit__` when the `with` block is left, with the exception it raised (if any).
+    ///
+    /// Returning `Ok(true)` suppresses `exception`, as with a truthy return value of a Python
+    /// `__exit__`. Returning `Err` raises that error instead (chained to `exception` by Python).
+    fn exit(
+        slf: &crate::Bound<'_, Self>,
+        exception: Option<&crate::Bound<'_, crate::exceptions::PyBaseException>>,
+    ) -> PyResult<bool>;
+}
+
 /// A workaround for [associated const equality](https://github.com/rust-lang/rust/issues/92827).
 ///
 /// This serves to have True / False values in the [`PyClass`] trait's `Frozen` type.
@@ -200,10 +241,16 @@ pub mod boolean_struct {
         use super::*;
 
         /// A way to "seal" the boolean traits.
-        pub trait Boolean {}
+        pub trait Boolean {
+            const VALUE: bool;
+        }
 
-        impl Boolean for True {}
-        impl Boolean for False {}
+        impl Boolean for True {
+            const VALUE: bool = true;
+        }
+        impl Boolean for False {
+            const VALUE: bool = false;
+        }
     }
 
     pub struct True(());
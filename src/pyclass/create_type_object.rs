@@ -5,16 +5,18 @@ use crate::{
     ffi,
     impl_::pycell::PyClassObject,
     impl_::pyclass::{
-        assign_sequence_item_from_mapping, get_sequence_item_from_mapping, tp_dealloc,
-        tp_dealloc_with_gc, PyClassItemsIter,
+        assign_sequence_item, assign_sequence_item_from_mapping, get_sequence_item,
+        get_sequence_item_from_mapping, register_instance_counter, tp_dealloc, tp_dealloc_with_gc,
+        PyClassItemsIter,
     },
     impl_::{
         pymethods::{get_doc, get_name, Getter, Setter},
         trampoline::trampoline,
     },
     types::typeobject::PyTypeMethods,
-    types::PyType,
-    Py, PyClass, PyGetterDef, PyMethodDefType, PyResult, PySetterDef, PyTypeInfo, Python,
+    types::{PyTuple, PyType},
+    Bound, Py, PyAny, PyClass, PyGetterDef, PyMethodDefType, PyResult, PySetterDef, PyTypeInfo,
+    Python,
 };
 use std::{
     borrow::Cow,
@@ -39,6 +41,7 @@ where
     unsafe fn inner(
         py: Python<'_>,
         base: *mut ffi::PyTypeObject,
+        mixins: Vec<*mut ffi::PyTypeObject>,
         dealloc: unsafe extern "C" fn(*mut ffi::PyObject),
         dealloc_with_gc: unsafe extern "C" fn(*mut ffi::PyObject),
         is_mapping: bool,
@@ -58,6 +61,7 @@ where
             getset_builders: HashMap::new(),
             cleanup: Vec::new(),
             tp_base: base,
+            tp_mixins: mixins,
             tp_dealloc: dealloc,
             tp_dealloc_with_gc: dealloc_with_gc,
             is_mapping,
@@ -69,6 +73,7 @@ where
             has_traverse: false,
             has_clear: false,
             has_dict: false,
+            weaklist_offset: None,
             class_flags: 0,
             #[cfg(all(not(Py_3_9), not(Py_LIMITED_API)))]
             buffer_procs: Default::default(),
@@ -80,10 +85,11 @@ where
         .build(py, name, module, size_of)
     }
 
-    unsafe {
+    let type_object = unsafe {
         inner(
             py,
             T::BaseType::type_object_raw(py),
+            T::mixins(py),
             tp_dealloc::<T>,
             tp_dealloc_with_gc::<T>,
             T::IS_MAPPING,
@@ -96,8 +102,12 @@ where
             T::NAME,
             T::MODULE,
             std::mem::size_of::<PyClassObject<T>>(),
-        )
+        )?
+    };
+    if let Some(counter) = T::instance_counter() {
+        register_instance_counter(type_object.type_object.bind(py).as_type_ptr(), counter);
     }
+    Ok(type_object)
 }
 
 type PyTypeBuilderCleanup = Box<dyn Fn(&PyTypeBuilder, *mut ffi::PyTypeObject)>;
@@ -111,6 +121,8 @@ struct PyTypeBuilder {
     /// except for that it does and we have tests.
     cleanup: Vec<PyTypeBuilderCleanup>,
     tp_base: *mut ffi::PyTypeObject,
+    /// Additional bases after `tp_base`, only set for `#[pyclass(extends = (Base, Mixin, ...))]`.
+    tp_mixins: Vec<*mut ffi::PyTypeObject>,
     tp_dealloc: ffi::destructor,
     tp_dealloc_with_gc: ffi::destructor,
     is_mapping: bool,
@@ -122,6 +134,8 @@ struct PyTypeBuilder {
     has_traverse: bool,
     has_clear: bool,
     has_dict: bool,
+    #[cfg_attr(PyPy, allow(dead_code))]
+    weaklist_offset: Option<ffi::Py_ssize_t>,
     class_flags: c_ulong,
     // Before Python 3.9, need to patch in buffer methods manually (they don't work in slots)
     #[cfg(all(not(Py_3_9), not(Py_LIMITED_API)))]
@@ -229,6 +243,18 @@ impl PyTypeBuilder {
             });
         }
 
+        // PyType_FromSpec doesn't add a __weakref__ getter like the `class` statement does.
+        #[cfg(not(PyPy))]
+        if let Some(weaklist_offset) = self.weaklist_offset {
+            property_defs.push(ffi::PyGetSetDef {
+                name: "__weakref__\0".as_ptr().cast(),
+                get: Some(get_weaklist),
+                set: None,
+                doc: ptr::null(),
+                closure: weaklist_offset as *mut c_void,
+            });
+        }
+
         // Safety: Py_tp_members expects a raw vec of PyGetSetDef
         unsafe { self.push_raw_vec_slot(ffi::Py_tp_getset, property_defs) };
 
@@ -240,24 +266,27 @@ impl PyTypeBuilder {
 
         // Don't add these methods for "pure" mappings.
 
+        // Sequences do implement sq_length, so Python has already added the length to negative
+        // indices passed to sq_item; `__getitem__` must not add it a second time.
+
         if !self.is_mapping && self.has_getitem {
+            let sq_item = if self.is_sequence {
+                get_sequence_item as *mut c_void
+            } else {
+                get_sequence_item_from_mapping as *mut c_void
+            };
             // Safety: This is the correct slot type for Py_sq_item
-            unsafe {
-                self.push_slot(
-                    ffi::Py_sq_item,
-                    get_sequence_item_from_mapping as *mut c_void,
-                )
-            }
+            unsafe { self.push_slot(ffi::Py_sq_item, sq_item) }
         }
 
         if !self.is_mapping && self.has_setitem {
+            let sq_ass_item = if self.is_sequence {
+                assign_sequence_item as *mut c_void
+            } else {
+                assign_sequence_item_from_mapping as *mut c_void
+            };
             // Safety: This is the correct slot type for Py_sq_ass_item
-            unsafe {
-                self.push_slot(
-                    ffi::Py_sq_ass_item,
-                    assign_sequence_item_from_mapping as *mut c_void,
-                )
-            }
+            unsafe { self.push_slot(ffi::Py_sq_ass_item, sq_ass_item) }
         }
 
         Ok(getset_destructors)
@@ -311,9 +340,10 @@ impl PyTypeBuilder {
     fn offsets(
         mut self,
         dict_offset: Option<ffi::Py_ssize_t>,
-        #[allow(unused_variables)] weaklist_offset: Option<ffi::Py_ssize_t>,
+        weaklist_offset: Option<ffi::Py_ssize_t>,
     ) -> Self {
         self.has_dict = dict_offset.is_some();
+        self.weaklist_offset = weaklist_offset;
 
         #[cfg(Py_3_9)]
         {
@@ -429,8 +459,22 @@ impl PyTypeBuilder {
         };
 
         // Safety: We've correctly setup the PyType_Spec at this point
-        let type_object: Py<PyType> =
-            unsafe { Py::from_owned_ptr_or_err(py, ffi::PyType_FromSpec(&mut spec))? };
+        let type_object: Py<PyType> = if self.tp_mixins.is_empty() {
+            unsafe { Py::from_owned_ptr_or_err(py, ffi::PyType_FromSpec(&mut spec))? }
+        } else {
+            // Python checks that the bases have compatible layouts and computes the MRO.
+            let bases: Vec<Bound<'_, PyAny>> = std::iter::once(self.tp_base)
+                .chain(self.tp_mixins.iter().copied())
+                .map(|base| unsafe { Bound::from_borrowed_ptr(py, base.cast()) })
+                .collect();
+            let bases = PyTuple::new_bound(py, bases);
+            unsafe {
+                Py::from_owned_ptr_or_err(
+                    py,
+                    ffi::PyType_FromSpecWithBases(&mut spec, bases.as_ptr()),
+                )?
+            }
+        };
 
         #[cfg(not(Py_3_11))]
         bpo_45315_workaround(py, class_name);
@@ -492,6 +536,27 @@ unsafe extern "C" fn no_constructor_defined(
     })
 }
 
+/// Getter for `__weakref__`, which returns the head of the object's weak reference list.
+///
+/// `closure` holds the offset of the weak reference list in the object.
+#[cfg(not(PyPy))]
+unsafe extern "C" fn get_weaklist(
+    obj: *mut ffi::PyObject,
+    closure: *mut c_void,
+) -> *mut ffi::PyObject {
+    let weaklist = *obj
+        .cast::<u8>()
+        .offset(closure as ffi::Py_ssize_t)
+        .cast::<*mut ffi::PyObject>();
+    let result = if weaklist.is_null() {
+        ffi::Py_None()
+    } else {
+        weaklist
+    };
+    ffi::Py_INCREF(result);
+    result
+}
+
 #[derive(Default)]
 struct GetSetDefBuilder {
     doc: Option<&'static str>,
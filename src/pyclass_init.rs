@@ -1,7 +1,9 @@
 //! Contains initialization utilities for `#[pyclass]`.
 use crate::callback::IntoPyCallbackOutput;
 use crate::ffi_ptr_ext::FfiPtrExt;
-use crate::impl_::pyclass::{PyClassBaseType, PyClassDict, PyClassThreadChecker, PyClassWeakRef};
+use crate::impl_::pyclass::{
+    PyClassBaseType, PyClassDict, PyClassGetterCache, PyClassThreadChecker, PyClassWeakRef,
+};
 use crate::types::PyAnyMethods;
 use crate::{ffi, Bound, Py, PyClass, PyErr, PyResult, Python};
 use crate::{
@@ -238,6 +240,8 @@ impl<T: PyClass> PyClassInitializer<T> {
             PyClassInitializerImpl::New { init, super_init } => (init, super_init),
         };
 
+        T::on_first_init(py)?;
+
         let obj = super_init.into_new_object(py, target_type)?;
 
         let part_init: *mut PartiallyInitializedClassObject<T> = obj.cast();
@@ -249,9 +253,14 @@ impl<T: PyClass> PyClassInitializer<T> {
                 thread_checker: T::ThreadChecker::new(),
                 dict: T::Dict::INIT,
                 weakref: T::WeakRef::INIT,
+                getter_cache: T::GetterCache::INIT,
             },
         );
 
+        if let Some(counter) = T::instance_counter() {
+            counter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        }
+
         // Safety: obj is a valid pointer to an object of type `target_type`, which` is a known
         // subclass of `T`
         Ok(obj.assume_owned(py).downcast_into_unchecked())
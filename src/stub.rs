@@ -0,0 +1,273 @@
+//! Generation of `.pyi` type stubs for the classes and functions defined with PyO3's macros.
+//!
+//! With the `experimental-stubs` feature, every `#[pyclass]`, `#[pymethods]` method and
+//! `#[pyfunction]` records its Python signature, including the annotations derived from the
+//! Rust types and the defaults given in `#[pyo3(signature = (...))]`. [`stubs`] renders the items
+//! recorded in a Rust module and its submodules as the contents of a `.pyi` file.
+//!
+//! The stubs are usually written by a small binary in the extension's crate, e.g. at
+//! `src/bin/stubs.rs`:
+//!
+//! ```no_run
+//! fn main() -> std::io::Result<()> {
+//!     // linking the extension crate registers its classes and functions
+//!     // use my_extension as _;
+//!     pyo3::stub::write_stubs("my_extension", "my_extension.pyi")
+//! }
+//! ```
+//!
+//! Rust types without a known Python equivalent (notably `#[pyclass]` types) are left
+//! unannotated.
+
+use std::{fmt::Write, path::Path};
+
+/// What a [`StubItem`] describes.
+#[doc(hidden)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StubKind {
+    Class,
+    ClassAttribute,
+    New,
+    Getter,
+    Setter,
+    Method,
+    ClassMethod,
+    StaticMethod,
+    Function,
+}
+
+/// A class, class member or function recorded by PyO3's macros.
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct StubItem {
+    /// The Rust module path of the item, from `module_path!()`.
+    pub module: &'static str,
+    /// The Python name of the class, for classes and class members.
+    pub class: Option<&'static str>,
+    pub kind: StubKind,
+    pub name: &'static str,
+    /// The parameter list, e.g. `(self, a: int, b: int = 1)`.
+    pub parameters: &'static str,
+    /// The return annotation, if the Rust return type has a known Python equivalent.
+    pub returns: Option<&'static str>,
+}
+
+inventory::collect!(StubItem);
+
+/// Renders the stubs of the classes and functions defined in the Rust module `module` (a path
+/// such as `my_extension` or `my_extension::submodule`) and its submodules.
+///
+/// Items are sorted by name, so the output only changes when the Python API does.
+pub fn stubs(module: &str) -> String {
+    render(
+        inventory::iter::<StubItem>
+            .into_iter()
+            .filter(|item| in_module(item.module, module))
+            .collect(),
+    )
+}
+
+/// Writes the [`stubs`] of `module` to the file at `path`.
+pub fn write_stubs(module: &str, path: impl AsRef<Path>) -> std::io::Result<()> {
+    std::fs::write(path, stubs(module))
+}
+
+fn in_module(item_module: &str, module: &str) -> bool {
+    match item_module.strip_prefix(module) {
+        Some(rest) => rest.is_empty() || rest.starts_with("::"),
+        None => false,
+    }
+}
+
+fn render(mut items: Vec<&StubItem>) -> String {
+    items.sort_by_key(|item| (item.class, item.name, item.kind));
+
+    let mut output = String::from("import typing\n");
+    let mut functions = Vec::new();
+    let mut index = 0;
+    while index < items.len() {
+        let class = match items[index].class {
+            Some(class) => class,
+            None => {
+                functions.push(items[index]);
+                index += 1;
+                continue;
+            }
+        };
+        let members: Vec<_> = items[index..]
+            .iter()
+            .take_while(|item| item.class == Some(class))
+            .filter(|item| item.kind != StubKind::Class)
+            .copied()
+            .collect();
+        index += items[index..]
+            .iter()
+            .take_while(|item| item.class == Some(class))
+            .count();
+
+        write!(output, "\nclass {}:", class).unwrap();
+        if members.is_empty() {
+            output.push_str(" ...\n");
+            continue;
+        }
+        output.push('\n');
+        for member in &members {
+            let has_getter = members
+                .iter()
+                .any(|item| item.kind == StubKind::Getter && item.name == member.name);
+            render_member(&mut output, class, member, has_getter);
+        }
+    }
+
+    if !functions.is_empty() {
+        output.push('\n');
+    }
+    for function in functions {
+        render_def(&mut output, "", function.name, function, function.returns);
+    }
+    output
+}
+
+fn render_member(output: &mut String, class: &str, member: &StubItem, has_getter: bool) {
+    const INDENT: &str = "    ";
+    match member.kind {
+        StubKind::ClassAttribute => {
+            writeln!(
+                output,
+                "{}{}: typing.ClassVar[{}]",
+                INDENT,
+                member.name,
+                member.returns.unwrap_or("typing.Any")
+            )
+            .unwrap();
+            return;
+        }
+        StubKind::New => {
+            return render_def(output, INDENT, "__new__", member, Some(class));
+        }
+        StubKind::Getter => writeln!(output, "{}@property", INDENT).unwrap(),
+        StubKind::Setter if has_getter => {
+            writeln!(output, "{}@{}.setter", INDENT, member.name).unwrap()
+        }
+        StubKind::ClassMethod => writeln!(output, "{}@classmethod", INDENT).unwrap(),
+        StubKind::StaticMethod => writeln!(output, "{}@staticmethod", INDENT).unwrap(),
+        _ => {}
+    }
+    render_def(output, INDENT, member.name, member, member.returns);
+}
+
+fn render_def(
+    output: &mut String,
+    indent: &str,
+    name: &str,
+    item: &StubItem,
+    returns: Option<&str>,
+) {
+    write!(output, "{}def {}{}", indent, name, item.parameters).unwrap();
+    if let Some(returns) = returns {
+        write!(output, " -> {}", returns).unwrap();
+    }
+    output.push_str(": ...\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(
+        class: Option<&'static str>,
+        kind: StubKind,
+        name: &'static str,
+        parameters: &'static str,
+        returns: Option<&'static str>,
+    ) -> StubItem {
+        StubItem {
+            module: "stubs",
+            class,
+            kind,
+            name,
+            parameters,
+            returns,
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let items = [
+            item(
+                None,
+                StubKind::Function,
+                "make",
+                "(x: int = 1)",
+                Some("int"),
+            ),
+            item(
+                Some("Point"),
+                StubKind::Setter,
+                "x",
+                "(self, value: float)",
+                Some("None"),
+            ),
+            item(
+                Some("Point"),
+                StubKind::Getter,
+                "x",
+                "(self)",
+                Some("float"),
+            ),
+            item(
+                Some("Point"),
+                StubKind::New,
+                "__new__",
+                "(cls, x: float)",
+                None,
+            ),
+            item(Some("Point"), StubKind::Class, "Point", "", None),
+            item(Some("Point"), StubKind::StaticMethod, "origin", "()", None),
+            item(
+                Some("Point"),
+                StubKind::ClassAttribute,
+                "ZERO",
+                "",
+                Some("int"),
+            ),
+            item(Some("Empty"), StubKind::Class, "Empty", "", None),
+            item(
+                None,
+                StubKind::Function,
+                "apply",
+                "(f, *args, **kwargs)",
+                None,
+            ),
+        ];
+        assert_eq!(
+            render(items.iter().collect()),
+            "\
+import typing
+
+class Empty: ...
+
+class Point:
+    ZERO: typing.ClassVar[int]
+    def __new__(cls, x: float) -> Point: ...
+    @staticmethod
+    def origin(): ...
+    @property
+    def x(self) -> float: ...
+    @x.setter
+    def x(self, value: float) -> None: ...
+
+def apply(f, *args, **kwargs): ...
+def make(x: int = 1) -> int: ...
+"
+        );
+    }
+
+    #[test]
+    fn test_in_module() {
+        assert!(in_module("stubs", "stubs"));
+        assert!(in_module("stubs::inner", "stubs"));
+        assert!(!in_module("stubs_other", "stubs"));
+        assert!(!in_module("pyo3::coroutine", "stubs"));
+    }
+}
@@ -3,13 +3,14 @@ use crate::conversion::{AsPyPointer, FromPyObjectBound, IntoPy, ToPyObject};
 use crate::err::{DowncastError, DowncastIntoError, PyDowncastError, PyErr, PyResult};
 use crate::exceptions::{PyAttributeError, PyTypeError};
 use crate::ffi_ptr_ext::FfiPtrExt;
-use crate::instance::Bound;
+use crate::instance::{Borrowed, Bound};
 use crate::py_result_ext::PyResultExt;
 use crate::type_object::{HasPyGilRef, PyTypeCheck, PyTypeInfo};
 #[cfg(not(any(PyPy, GraalPy)))]
 use crate::types::PySuper;
-use crate::types::{PyDict, PyIterator, PyList, PyString, PyTuple, PyType};
+use crate::types::{PyDict, PyFloat, PyIterator, PyList, PyLong, PyString, PyTuple, PyType};
 use crate::{err, ffi, Py, PyNativeType, Python};
+use std::borrow::Cow;
 use std::cell::UnsafeCell;
 use std::cmp::Ordering;
 use std::os::raw::c_int;
@@ -78,7 +79,10 @@ impl PyAny {
 
     /// Determines whether this object has the given attribute.
     ///
-    /// This is equivalent to the Python expression `hasattr(self, attr_name)`.
+    /// This is equivalent to the Python expression `hasattr(self, attr_name)`: only an
+    /// `AttributeError` raised by the lookup means the attribute is missing, and any other
+    /// exception is returned as `Err` rather than being silently swallowed (unlike the C API's
+    /// `PyObject_HasAttr`).
     ///
     /// To avoid repeated temporary allocations of Python strings, the [`intern!`] macro can be used
     /// to intern `attr_name`.
@@ -136,6 +140,16 @@ impl PyAny {
             .map(Bound::into_gil_ref)
     }
 
+    /// Retrieves several attribute values, in the order of `attr_names`.
+    ///
+    /// This is equivalent to calling [`getattr`](PyAny::getattr) for each name, and fails with
+    /// the error of the first attribute which cannot be retrieved.
+    pub fn getattr_all(&self, attr_names: &[&str]) -> PyResult<Vec<&PyAny>> {
+        self.as_borrowed()
+            .getattr_all(attr_names)
+            .map(|values| values.into_iter().map(Bound::into_gil_ref).collect())
+    }
+
     /// Sets an attribute value.
     ///
     /// This is equivalent to the Python expression `self.attr_name = value`.
@@ -588,6 +602,16 @@ impl PyAny {
             .map(Bound::into_gil_ref)
     }
 
+    /// Looks up a method on the object once, returning a handle which can be called repeatedly.
+    ///
+    /// See [`PyAnyMethods::get_bound_method`] for details.
+    pub fn get_bound_method<N>(&self, name: N) -> PyResult<BoundMethod<'_>>
+    where
+        N: IntoPy<Py<PyString>>,
+    {
+        self.as_borrowed().get_bound_method(name)
+    }
+
     /// Returns whether the object is considered to be true.
     ///
     /// This is equivalent to the Python expression `bool(self)`.
@@ -611,6 +635,17 @@ impl PyAny {
         self.as_borrowed().is_none()
     }
 
+    /// Returns `Ok(None)` if the object is None, otherwise applies `f` to it.
+    ///
+    /// This is a shorthand for the common "convert the value unless it is None" pattern.
+    pub fn none_or<T>(&self, f: impl FnOnce(&PyAny) -> PyResult<T>) -> PyResult<Option<T>> {
+        if self.is_none() {
+            Ok(None)
+        } else {
+            f(self).map(Some)
+        }
+    }
+
     /// Returns whether the object is Ellipsis, e.g. `...`.
     ///
     /// This is equivalent to the Python expression `self is ...`.
@@ -670,6 +705,13 @@ impl PyAny {
         self.as_borrowed().get_type().into_gil_ref()
     }
 
+    /// Returns the name of this object's type.
+    ///
+    /// This is equivalent to the Python expression `type(self).__name__`.
+    pub fn get_type_name(&self) -> PyResult<Cow<'_, str>> {
+        self.get_type().as_borrowed().short_name()
+    }
+
     /// Returns the Python type pointer for this object.
     #[inline]
     pub fn get_type_ptr(&self) -> *mut ffi::PyTypeObject {
@@ -786,9 +828,13 @@ impl PyAny {
 
     /// Converts this `PyAny` to a concrete Python type without checking validity.
     ///
+    /// This skips the `isinstance` check done by [`downcast`](PyAny::downcast), for code which
+    /// has already established the type by other means.
+    ///
     /// # Safety
     ///
-    /// Callers must ensure that the type is valid or risk type confusion.
+    /// Callers must ensure that the object is an instance of `T`. Using the result if it is not
+    /// is undefined behavior.
     #[inline]
     pub unsafe fn downcast_unchecked<T>(&self) -> &T
     where
@@ -828,6 +874,22 @@ impl PyAny {
         self.as_borrowed().str().map(Bound::into_gil_ref)
     }
 
+    /// Converts self to a Python `int`, like [`str`](PyAny::str) does for `str`.
+    ///
+    /// This is equivalent to the Python expression `int(self)`. Together with
+    /// [`to_float`](PyAny::to_float) and [`str`](PyAny::str), this covers the Python coercion
+    /// built-ins.
+    pub fn to_int(&self) -> PyResult<&PyLong> {
+        self.as_borrowed().to_int().map(Bound::into_gil_ref)
+    }
+
+    /// Converts self to a Python `float`, like [`str`](PyAny::str) does for `str`.
+    ///
+    /// This is equivalent to the Python expression `float(self)`.
+    pub fn to_float(&self) -> PyResult<&PyFloat> {
+        self.as_borrowed().to_float().map(Bound::into_gil_ref)
+    }
+
     /// Retrieves the hash code of self.
     ///
     /// This is equivalent to the Python expression `hash(self)`.
@@ -949,7 +1011,10 @@ pub trait PyAnyMethods<'py>: crate::sealed::Sealed {
 
     /// Determines whether this object has the given attribute.
     ///
-    /// This is equivalent to the Python expression `hasattr(self, attr_name)`.
+    /// This is equivalent to the Python expression `hasattr(self, attr_name)`: only an
+    /// `AttributeError` raised by the lookup means the attribute is missing, and any other
+    /// exception is returned as `Err` rather than being silently swallowed (unlike the C API's
+    /// `PyObject_HasAttr`).
     ///
     /// To avoid repeated temporary allocations of Python strings, the [`intern!`] macro can be used
     /// to intern `attr_name`.
@@ -999,6 +1064,26 @@ pub trait PyAnyMethods<'py>: crate::sealed::Sealed {
     where
         N: IntoPy<Py<PyString>>;
 
+    /// Retrieves several attribute values, in the order of `attr_names`.
+    ///
+    /// This is equivalent to calling [`getattr`](PyAnyMethods::getattr) for each name, and fails
+    /// with the error of the first attribute which cannot be retrieved.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use pyo3::prelude::*;
+    ///
+    /// Python::with_gil(|py| -> PyResult<()> {
+    ///     let version = py.import_bound("sys")?.getattr("version_info")?;
+    ///     let attrs = version.getattr_all(&["major", "minor"])?;
+    ///     assert_eq!(attrs[0].extract::<u32>()?, 3);
+    ///     Ok(())
+    /// })
+    /// # .unwrap();
+    /// ```
+    fn getattr_all(&self, attr_names: &[&str]) -> PyResult<Vec<Bound<'py, PyAny>>>;
+
     /// Sets an attribute value.
     ///
     /// This is equivalent to the Python expression `self.attr_name = value`.
@@ -1451,6 +1536,47 @@ pub trait PyAnyMethods<'py>: crate::sealed::Sealed {
         N: IntoPy<Py<PyString>>,
         A: IntoPy<Py<PyTuple>>;
 
+    /// Looks up a method on the object once, returning a handle which can be called repeatedly.
+    ///
+    /// This is equivalent to the Python expression `self.name`. Calling the returned
+    /// [`BoundMethod`] avoids resolving the attribute again, which makes it cheaper than
+    /// [`call_method`](PyAnyMethods::call_method) when the same method is called many times,
+    /// e.g. a callback in a loop.
+    ///
+    /// Note that the method is resolved when this is called, so later changes to the attribute
+    /// on the object are not observed by the handle.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pyo3::prelude::*;
+    ///
+    /// const CODE: &str = r#"
+    /// class Counter:
+    ///     def __init__(self):
+    ///         self.count = 0
+    ///     def increment(self, by):
+    ///         self.count += by
+    /// counter = Counter()
+    /// "#;
+    ///
+    /// # fn main() -> PyResult<()> {
+    /// Python::with_gil(|py| {
+    ///     let module = PyModule::from_code_bound(py, CODE, "", "")?;
+    ///     let counter = module.getattr("counter")?;
+    ///     let increment = counter.get_bound_method("increment")?;
+    ///     for _ in 0..10 {
+    ///         increment.call1((2,))?;
+    ///     }
+    ///     assert_eq!(counter.getattr("count")?.extract::<i32>()?, 20);
+    ///     Ok(())
+    /// })
+    /// # }
+    /// ```
+    fn get_bound_method<N>(&self, name: N) -> PyResult<BoundMethod<'py>>
+    where
+        N: IntoPy<Py<PyString>>;
+
     /// Returns whether the object is considered to be true.
     ///
     /// This is equivalent to the Python expression `bool(self)`.
@@ -1461,6 +1587,27 @@ pub trait PyAnyMethods<'py>: crate::sealed::Sealed {
     /// This is equivalent to the Python expression `self is None`.
     fn is_none(&self) -> bool;
 
+    /// Returns `Ok(None)` if the object is None, otherwise applies `f` to it.
+    ///
+    /// This is a shorthand for the common "convert the value unless it is None" pattern.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use pyo3::prelude::*;
+    ///
+    /// Python::with_gil(|py| -> PyResult<()> {
+    ///     let none = py.None().into_bound(py);
+    ///     assert_eq!(none.none_or(|obj| obj.extract::<i32>())?, None);
+    ///
+    ///     let number = 42.to_object(py).into_bound(py);
+    ///     assert_eq!(number.none_or(|obj| obj.extract::<i32>())?, Some(42));
+    ///     Ok(())
+    /// })
+    /// # .unwrap();
+    /// ```
+    fn none_or<T>(&self, f: impl FnOnce(&Bound<'py, PyAny>) -> PyResult<T>) -> PyResult<Option<T>>;
+
     /// Returns whether the object is Ellipsis, e.g. `...`.
     ///
     /// This is equivalent to the Python expression `self is ...`.
@@ -1502,6 +1649,23 @@ pub trait PyAnyMethods<'py>: crate::sealed::Sealed {
     /// Returns the Python type object for this object's type.
     fn get_type(&self) -> Bound<'py, PyType>;
 
+    /// Returns the name of this object's type.
+    ///
+    /// This is equivalent to the Python expression `type(self).__name__`, and is useful for
+    /// error messages such as "expected X, got Y".
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pyo3::prelude::*;
+    ///
+    /// Python::with_gil(|py| {
+    ///     let obj = 42.to_object(py).into_bound(py);
+    ///     assert_eq!(obj.get_type_name().unwrap(), "int");
+    /// });
+    /// ```
+    fn get_type_name(&self) -> PyResult<Cow<'_, str>>;
+
     /// Returns the Python type pointer for this object.
     fn get_type_ptr(&self) -> *mut ffi::PyTypeObject;
 
@@ -1628,16 +1792,43 @@ pub trait PyAnyMethods<'py>: crate::sealed::Sealed {
 
     /// Converts this `PyAny` to a concrete Python type without checking validity.
     ///
+    /// This skips the `isinstance` check done by [`downcast`](PyAnyMethods::downcast), for code
+    /// which has already established the type by other means, such as a cheaper check of a tag.
+    ///
     /// # Safety
     ///
-    /// Callers must ensure that the type is valid or risk type confusion.
+    /// Callers must ensure that the object is an instance of `T`. Using the result if it is not
+    /// is undefined behavior.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pyo3::prelude::*;
+    /// use pyo3::types::{PyDict, PyList};
+    ///
+    /// /// `kind` is `"list"` if `value` is a list, or `"dict"` if it is a dict.
+    /// fn len_of(kind: &str, value: &Bound<'_, PyAny>) -> usize {
+    ///     match kind {
+    ///         // Safety: the caller guarantees the type of `value` matches `kind`
+    ///         "list" => unsafe { value.downcast_unchecked::<PyList>() }.len(),
+    ///         "dict" => unsafe { value.downcast_unchecked::<PyDict>() }.len(),
+    ///         _ => 0,
+    ///     }
+    /// }
+    ///
+    /// Python::with_gil(|py| {
+    ///     let list = PyList::new_bound(py, [1, 2, 3]);
+    ///     assert_eq!(len_of("list", list.as_any()), 3);
+    /// });
+    /// ```
     unsafe fn downcast_unchecked<T>(&self) -> &Bound<'py, T>;
 
     /// Like `downcast_unchecked` but takes ownership of `self`.
     ///
     /// # Safety
     ///
-    /// Callers must ensure that the type is valid or risk type confusion.
+    /// Callers must ensure that the object is an instance of `T`. Using the result if it is not
+    /// is undefined behavior.
     unsafe fn downcast_into_unchecked<T>(self) -> Bound<'py, T>;
 
     /// Extracts some type from the Python object.
@@ -1661,6 +1852,18 @@ pub trait PyAnyMethods<'py>: crate::sealed::Sealed {
     /// This is equivalent to the Python expression `str(self)`.
     fn str(&self) -> PyResult<Bound<'py, PyString>>;
 
+    /// Converts self to a Python `int`, like [`str`](PyAnyMethods::str) does for `str`.
+    ///
+    /// This is equivalent to the Python expression `int(self)`. Together with
+    /// [`to_float`](PyAnyMethods::to_float) and [`str`](PyAnyMethods::str), this covers the
+    /// Python coercion built-ins.
+    fn to_int(&self) -> PyResult<Bound<'py, PyLong>>;
+
+    /// Converts self to a Python `float`, like [`str`](PyAnyMethods::str) does for `str`.
+    ///
+    /// This is equivalent to the Python expression `float(self)`.
+    fn to_float(&self) -> PyResult<Bound<'py, PyFloat>>;
+
     /// Retrieves the hash code of self.
     ///
     /// This is equivalent to the Python expression `hash(self)`.
@@ -1773,6 +1976,13 @@ impl<'py> PyAnyMethods<'py> for Bound<'py, PyAny> {
         inner(self, attr_name.into_py(self.py()).into_bound(py))
     }
 
+    fn getattr_all(&self, attr_names: &[&str]) -> PyResult<Vec<Bound<'py, PyAny>>> {
+        attr_names
+            .iter()
+            .map(|attr_name| self.getattr(*attr_name))
+            .collect()
+    }
+
     fn setattr<N, V>(&self, attr_name: N, value: V) -> PyResult<()>
     where
         N: IntoPy<Py<PyString>>,
@@ -2033,6 +2243,13 @@ impl<'py> PyAnyMethods<'py> for Bound<'py, PyAny> {
         self.call_method(name, args, None)
     }
 
+    fn get_bound_method<N>(&self, name: N) -> PyResult<BoundMethod<'py>>
+    where
+        N: IntoPy<Py<PyString>>,
+    {
+        self.getattr(name).map(BoundMethod)
+    }
+
     fn is_truthy(&self) -> PyResult<bool> {
         let v = unsafe { ffi::PyObject_IsTrue(self.as_ptr()) };
         err::error_on_minusone(self.py(), v)?;
@@ -2044,6 +2261,14 @@ impl<'py> PyAnyMethods<'py> for Bound<'py, PyAny> {
         unsafe { ffi::Py_None() == self.as_ptr() }
     }
 
+    fn none_or<T>(&self, f: impl FnOnce(&Bound<'py, PyAny>) -> PyResult<T>) -> PyResult<Option<T>> {
+        if self.is_none() {
+            Ok(None)
+        } else {
+            f(self).map(Some)
+        }
+    }
+
     fn is_ellipsis(&self) -> bool {
         unsafe { ffi::Py_Ellipsis() == self.as_ptr() }
     }
@@ -2114,6 +2339,17 @@ impl<'py> PyAnyMethods<'py> for Bound<'py, PyAny> {
         unsafe { PyType::from_borrowed_type_ptr(self.py(), ffi::Py_TYPE(self.as_ptr())) }
     }
 
+    fn get_type_name(&self) -> PyResult<Cow<'_, str>> {
+        // Safety: the type of an object lives at least as long as the object
+        let ty: Borrowed<'_, 'py, PyType> = unsafe {
+            self.get_type_ptr()
+                .cast::<ffi::PyObject>()
+                .assume_borrowed(self.py())
+                .downcast_unchecked()
+        };
+        ty.short_name()
+    }
+
     #[inline]
     fn get_type_ptr(&self) -> *mut ffi::PyTypeObject {
         unsafe { ffi::Py_TYPE(self.as_ptr()) }
@@ -2208,6 +2444,22 @@ impl<'py> PyAnyMethods<'py> for Bound<'py, PyAny> {
         }
     }
 
+    fn to_int(&self) -> PyResult<Bound<'py, PyLong>> {
+        unsafe {
+            ffi::PyNumber_Long(self.as_ptr())
+                .assume_owned_or_err(self.py())
+                .downcast_into_unchecked()
+        }
+    }
+
+    fn to_float(&self) -> PyResult<Bound<'py, PyFloat>> {
+        unsafe {
+            ffi::PyNumber_Float(self.as_ptr())
+                .assume_owned_or_err(self.py())
+                .downcast_into_unchecked()
+        }
+    }
+
     fn hash(&self) -> PyResult<isize> {
         let v = unsafe { ffi::PyObject_Hash(self.as_ptr()) };
         crate::err::error_on_minusone(self.py(), v)?;
@@ -2323,11 +2575,56 @@ impl<'py> Bound<'py, PyAny> {
     }
 }
 
+/// A method handle returned by [`PyAnyMethods::get_bound_method`].
+///
+/// The method is resolved once, so calling the handle skips the attribute lookup performed by
+/// [`PyAnyMethods::call_method`].
+#[derive(Clone, Debug)]
+pub struct BoundMethod<'py>(Bound<'py, PyAny>);
+
+impl<'py> BoundMethod<'py> {
+    /// Calls the method with arguments and keyword arguments.
+    ///
+    /// This is equivalent to the Python expression `self.name(*args, **kwargs)`.
+    pub fn call(
+        &self,
+        args: impl IntoPy<Py<PyTuple>>,
+        kwargs: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.0.call(args, kwargs)
+    }
+
+    /// Calls the method without arguments.
+    ///
+    /// This is equivalent to the Python expression `self.name()`.
+    pub fn call0(&self) -> PyResult<Bound<'py, PyAny>> {
+        self.0.call0()
+    }
+
+    /// Calls the method with only positional arguments.
+    ///
+    /// This is equivalent to the Python expression `self.name(*args)`.
+    pub fn call1(&self, args: impl IntoPy<Py<PyTuple>>) -> PyResult<Bound<'py, PyAny>> {
+        self.0.call1(args)
+    }
+
+    /// Returns the resolved method object.
+    pub fn as_any(&self) -> &Bound<'py, PyAny> {
+        &self.0
+    }
+
+    /// Converts the handle into the resolved method object.
+    pub fn into_any(self) -> Bound<'py, PyAny> {
+        self.0
+    }
+}
+
 #[cfg(test)]
 #[cfg_attr(not(feature = "gil-refs"), allow(deprecated))]
 mod tests {
     use crate::{
         basic::CompareOp,
+        exceptions::{PyAttributeError, PyTypeError},
         types::{any::PyAnyMethods, IntoPyDict, PyAny, PyBool, PyList, PyLong, PyModule},
         Bound, PyNativeType, PyTypeInfo, Python, ToPyObject,
     };
@@ -2452,6 +2749,43 @@ class SimpleClass:
         })
     }
 
+    #[test]
+    fn test_get_bound_method() {
+        Python::with_gil(|py| {
+            let module = PyModule::from_code_bound(
+                py,
+                r#"
+class Accumulator:
+    def __init__(self):
+        self.total = 0
+    def add(self, value=1, *, times=1):
+        self.total += value * times
+        return self.total
+"#,
+                file!(),
+                "test_module",
+            )
+            .expect("module creation failed");
+
+            let acc = module.getattr("Accumulator").unwrap().call0().unwrap();
+            let add = acc.get_bound_method("add").unwrap();
+            assert_eq!(add.call0().unwrap().extract::<u32>().unwrap(), 1);
+            assert_eq!(add.call1((2,)).unwrap().extract::<u32>().unwrap(), 3);
+            let kwargs = [("times", 3)].into_py_dict_bound(py);
+            assert_eq!(
+                add.call((2,), Some(&kwargs))
+                    .unwrap()
+                    .extract::<u32>()
+                    .unwrap(),
+                9
+            );
+            assert!(add.as_any().getattr("__self__").unwrap().is(&acc));
+
+            let err = acc.get_bound_method("missing").unwrap_err();
+            assert!(err.is_instance_of::<PyAttributeError>(py));
+        })
+    }
+
     #[test]
     fn test_type() {
         Python::with_gil(|py| {
@@ -2690,6 +3024,31 @@ class SimpleClass:
         test_eq_methods_generic(&bools);
     }
 
+    #[test]
+    fn test_to_int_and_to_float() {
+        Python::with_gil(|py| {
+            let text = crate::types::PyString::new_bound(py, "42");
+            assert_eq!(text.to_int().unwrap().extract::<i64>().unwrap(), 42);
+            assert_eq!(text.to_float().unwrap().extract::<f64>().unwrap(), 42.0);
+
+            let float = 2.75f64.to_object(py).into_bound(py);
+            assert_eq!(float.to_int().unwrap().extract::<i64>().unwrap(), 2);
+            assert!(float.to_float().unwrap().is(&float));
+
+            let text = crate::types::PyString::new_bound(py, "forty-two");
+            assert!(text
+                .to_int()
+                .unwrap_err()
+                .is_instance_of::<crate::exceptions::PyValueError>(py));
+            assert!(py
+                .None()
+                .bind(py)
+                .to_float()
+                .unwrap_err()
+                .is_instance_of::<PyTypeError>(py));
+        });
+    }
+
     #[test]
     fn test_rich_compare_type_error() {
         Python::with_gil(|py| {
@@ -2721,6 +3080,43 @@ class SimpleClass:
         });
     }
 
+    #[test]
+    fn test_none_or() {
+        Python::with_gil(|py| {
+            let none = py.None().into_bound(py);
+            let result: Option<i32> = none
+                .none_or(|_| panic!("closure must not be called for None"))
+                .unwrap();
+            assert_eq!(result, None);
+
+            let value = "hello".to_object(py).into_bound(py);
+            assert_eq!(
+                value.none_or(|obj| obj.extract::<String>()).unwrap(),
+                Some("hello".to_owned())
+            );
+            assert!(value
+                .none_or(|obj| obj.extract::<i32>())
+                .unwrap_err()
+                .is_instance_of::<PyTypeError>(py));
+        });
+    }
+
+    #[test]
+    fn test_getattr_all() {
+        Python::with_gil(|py| {
+            let complex = py.eval_bound("3+4j", None, None).unwrap();
+            let parts = complex.getattr_all(&["real", "imag"]).unwrap();
+            let parts: Vec<f64> = parts.iter().map(|p| p.extract().unwrap()).collect();
+            assert_eq!(parts, [3.0, 4.0]);
+
+            assert!(complex
+                .getattr_all(&["real", "missing", "imag"])
+                .unwrap_err()
+                .is_instance_of::<PyAttributeError>(py));
+            assert!(complex.getattr_all(&[]).unwrap().is_empty());
+        });
+    }
+
     #[test]
     fn test_is_callable() {
         Python::with_gil(|py| {
@@ -2744,4 +3140,81 @@ class SimpleClass:
             assert!(not_container.is_empty().is_err());
         });
     }
+
+    #[test]
+    fn test_get_type_name() {
+        Python::with_gil(|py| {
+            let assert_type_name = |code: &str, expected: &str| {
+                let obj = py.eval_bound(code, None, None).unwrap();
+                assert_eq!(obj.get_type_name().unwrap(), expected);
+            };
+
+            assert_type_name("42", "int");
+            assert_type_name("None", "NoneType");
+            assert_type_name("__import__('collections').OrderedDict()", "OrderedDict");
+            assert_type_name("type('Custom', (), {})()", "Custom");
+            // heap types do not have a module prefix in `tp_name`, so dots are kept
+            assert_type_name("type('with.dots', (), {})()", "with.dots");
+
+            // the `tp_name` of `#[pyclass]` types includes the module
+            #[crate::pyclass(crate = "crate", module = "my_module")]
+            struct InModule {}
+
+            let obj = Bound::new(py, InModule {}).unwrap();
+            assert_eq!(
+                obj.get_type().getattr("__module__").unwrap().to_string(),
+                "my_module"
+            );
+            assert_eq!(obj.get_type_name().unwrap(), "InModule");
+
+            #[allow(deprecated)]
+            let gil_ref = py.eval("1.5", None, None).unwrap();
+            assert_eq!(gil_ref.get_type_name().unwrap(), "float");
+        });
+    }
+
+    #[test]
+    fn test_any_subscript() {
+        Python::with_gil(|py| {
+            let module = PyModule::from_code_bound(
+                py,
+                r#"
+class Subscriptable:
+    def __init__(self):
+        self.data = {}
+
+    def __getitem__(self, key):
+        return self.data[key]
+
+    def __setitem__(self, key, value):
+        self.data[key] = value
+
+    def __delitem__(self, key):
+        del self.data[key]
+"#,
+                "test.py",
+                "test",
+            )
+            .unwrap();
+
+            let obj = module.getattr("Subscriptable").unwrap().call0().unwrap();
+            obj.set_item("a", 1).unwrap();
+            obj.set_item((0, 1), "tuple").unwrap();
+            assert_eq!(obj.get_item("a").unwrap().extract::<i32>().unwrap(), 1);
+            assert_eq!(
+                obj.get_item((0, 1)).unwrap().extract::<String>().unwrap(),
+                "tuple"
+            );
+
+            obj.del_item("a").unwrap();
+            let err = obj.get_item("a").unwrap_err();
+            assert!(err.is_instance_of::<crate::exceptions::PyKeyError>(py));
+            assert!(obj.del_item("a").is_err());
+
+            let not_subscriptable = 5.to_object(py).into_bound(py);
+            assert!(not_subscriptable.get_item(0).is_err());
+            assert!(not_subscriptable.set_item(0, 1).is_err());
+            assert!(not_subscriptable.del_item(0).is_err());
+        });
+    }
 }
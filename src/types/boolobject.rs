@@ -163,8 +163,7 @@ mod tests {
     use crate::types::any::PyAnyMethods;
     use crate::types::boolobject::PyBoolMethods;
     use crate::types::PyBool;
-    use crate::Python;
-    use crate::ToPyObject;
+    use crate::{IntoPy, PyObject, Python, ToPyObject};
 
     #[test]
     fn test_true() {
@@ -185,4 +184,22 @@ mod tests {
             assert!(false.to_object(py).is(&*PyBool::new_bound(py, false)));
         });
     }
+
+    #[test]
+    fn test_singletons() {
+        Python::with_gil(|py| {
+            let py_true = py.eval_bound("True", None, None).unwrap();
+            let py_false = py.eval_bound("False", None, None).unwrap();
+
+            assert!(PyBool::new_bound(py, true).is(&py_true));
+            assert!(PyBool::new_bound(py, false).is(&py_false));
+            assert!(true.to_object(py).is(&py_true));
+            assert!(IntoPy::<PyObject>::into_py(false, py).is(&py_false));
+
+            #[allow(deprecated)]
+            let gil_ref_true = PyBool::new(py, true);
+            assert!(gil_ref_true.is(&py_true));
+            assert!(PyBool::new_bound(py, true).is(&*PyBool::new_bound(py, true)));
+        });
+    }
 }
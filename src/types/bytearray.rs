@@ -285,6 +285,17 @@ impl PyByteArray {
     pub fn resize(&self, len: usize) -> PyResult<()> {
         self.as_borrowed().resize(len)
     }
+
+    /// Appends `data` to the end of the bytearray.
+    ///
+    /// This resizes the bytearray and copies `data` into the new space, so the same caveats as for
+    /// [PyByteArray::resize] apply: pointers and slices obtained from this bytearray before the call
+    /// are invalidated.
+    /// `data` itself may be borrowed from this bytearray, in which case it is copied before the
+    /// resize.
+    pub fn extend(&self, data: &[u8]) -> PyResult<()> {
+        self.as_borrowed().extend(data)
+    }
 }
 
 /// Implementation of functionality for [`PyByteArray`].
@@ -439,6 +450,31 @@ pub trait PyByteArrayMethods<'py>: crate::sealed::Sealed {
     /// Note that this will invalidate any pointers obtained by [PyByteArray::data], as well as
     /// any (unsafe) slices obtained from [PyByteArray::as_bytes] and [PyByteArray::as_bytes_mut].
     fn resize(&self, len: usize) -> PyResult<()>;
+
+    /// Appends `data` to the end of the bytearray.
+    ///
+    /// This resizes the bytearray and copies `data` into the new space, so the same caveats as for
+    /// [PyByteArrayMethods::resize] apply: pointers and slices obtained from this bytearray before
+    /// the call are invalidated.
+    /// `data` itself may be borrowed from this bytearray, in which case it is copied before the
+    /// resize.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pyo3::prelude::*;
+    /// use pyo3::types::PyByteArray;
+    ///
+    /// Python::with_gil(|py| -> PyResult<()> {
+    ///     let buffer = PyByteArray::new_bound(py, b"Hello");
+    ///     buffer.extend(b", ")?;
+    ///     buffer.extend(b"World")?;
+    ///     assert_eq!(buffer.to_vec(), b"Hello, World");
+    ///     Ok(())
+    /// })
+    /// # .unwrap();
+    /// ```
+    fn extend(&self, data: &[u8]) -> PyResult<()>;
 }
 
 impl<'py> PyByteArrayMethods<'py> for Bound<'py, PyByteArray> {
@@ -479,6 +515,31 @@ impl<'py> PyByteArrayMethods<'py> for Bound<'py, PyByteArray> {
             }
         }
     }
+
+    fn extend(&self, data: &[u8]) -> PyResult<()> {
+        if data.is_empty() {
+            return Ok(());
+        }
+        let old_len = self.len();
+        // `data` may be borrowed from this bytearray, e.g. through `as_bytes`, and would be freed
+        // by the resize, so copy it first in that case
+        let start = self.data() as usize;
+        let data_start = data.as_ptr() as usize;
+        let copied;
+        let data = if data_start < start + old_len && start < data_start + data.len() {
+            copied = data.to_vec();
+            copied.as_slice()
+        } else {
+            data
+        };
+        self.resize(old_len + data.len())?;
+        // Safety: the bytearray was just resized to hold `data` after its first `old_len` bytes,
+        // and no Python code can run (and so modify it) while the GIL is held here.
+        unsafe {
+            std::ptr::copy_nonoverlapping(data.as_ptr(), self.data().add(old_len), data.len());
+        }
+        Ok(())
+    }
 }
 
 impl<'a> Borrowed<'a, '_, PyByteArray> {
@@ -623,6 +684,29 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_extend() {
+        Python::with_gil(|py| {
+            let bytearray = PyByteArray::new_bound(py, b"Hello");
+            bytearray.extend(b" ").unwrap();
+            bytearray.extend(b"").unwrap();
+            bytearray.extend(b"Python").unwrap();
+            assert_eq!(bytearray.to_vec(), b"Hello Python");
+
+            let empty = PyByteArray::new_bound(py, b"");
+            empty.extend(b"data").unwrap();
+            assert_eq!(empty.to_vec(), b"data");
+
+            // extending with a slice of the bytearray itself
+            let repeated = PyByteArray::new_bound(py, b"abc");
+            repeated.extend(unsafe { repeated.as_bytes() }).unwrap();
+            repeated
+                .extend(unsafe { &repeated.as_bytes()[1..3] })
+                .unwrap();
+            assert_eq!(repeated.to_vec(), b"abcabcbc");
+        });
+    }
+
     #[test]
     fn test_byte_array_new_with() -> super::PyResult<()> {
         Python::with_gil(|py| -> super::PyResult<()> {
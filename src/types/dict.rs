@@ -1,12 +1,14 @@
 use super::PyMapping;
 use crate::err::{self, PyErr, PyResult};
+use crate::exceptions::PyValueError;
 use crate::ffi::Py_ssize_t;
 use crate::ffi_ptr_ext::FfiPtrExt;
 use crate::instance::{Borrowed, Bound};
 use crate::py_result_ext::PyResultExt;
 use crate::types::any::PyAnyMethods;
 use crate::types::{PyAny, PyList};
-use crate::{ffi, PyNativeType, Python, ToPyObject};
+use crate::{ffi, FromPyObject, PyNativeType, Python, ToPyObject};
+use std::marker::PhantomData;
 
 /// Represents a Python `dict`.
 #[repr(transparent)]
@@ -105,6 +107,61 @@ impl PyDict {
         Ok(dict)
     }
 
+    /// Deprecated form of [`from_iter_unique_bound`][PyDict::from_iter_unique_bound].
+    #[cfg_attr(
+        not(feature = "gil-refs"),
+        deprecated(
+            since = "0.21.0",
+            note = "`PyDict::from_iter_unique` will be replaced by `PyDict::from_iter_unique_bound` in a future PyO3 version"
+        )
+    )]
+    #[inline]
+    pub fn from_iter_unique<I>(py: Python<'_>, iter: I) -> PyResult<&PyDict>
+    where
+        I: IntoIterator,
+        I::Item: PyDictItem,
+    {
+        Self::from_iter_unique_bound(py, iter).map(Bound::into_gil_ref)
+    }
+
+    /// Creates a new dictionary from an iterator of key-value pairs.
+    ///
+    /// Unlike [`IntoPyDict`], which keeps the last entry seen for each key, this raises a
+    /// `ValueError` on the first duplicate key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pyo3::prelude::*;
+    /// use pyo3::types::PyDict;
+    ///
+    /// Python::with_gil(|py| {
+    ///     let dict = PyDict::from_iter_unique_bound(py, [("a", 1), ("b", 2)]).unwrap();
+    ///     assert_eq!(dict.len(), 2);
+    ///
+    ///     let err = PyDict::from_iter_unique_bound(py, [("a", 1), ("a", 2)]).unwrap_err();
+    ///     assert_eq!(err.to_string(), "ValueError: duplicate key: 'a'");
+    /// });
+    /// ```
+    pub fn from_iter_unique_bound<I>(py: Python<'_>, iter: I) -> PyResult<Bound<'_, PyDict>>
+    where
+        I: IntoIterator,
+        I::Item: PyDictItem,
+    {
+        let dict = Self::new_bound(py);
+        for item in iter {
+            let key = item.key().to_object(py).into_bound(py);
+            if dict.contains(&key)? {
+                return Err(PyValueError::new_err(format!(
+                    "duplicate key: {}",
+                    key.repr()?
+                )));
+            }
+            dict.set_item(key, item.value())?;
+        }
+        Ok(dict)
+    }
+
     /// Returns a new dictionary that contains the same key-value pairs as self.
     ///
     /// This is equivalent to the Python expression `self.copy()`.
@@ -258,6 +315,34 @@ impl PyDict {
         PyDictIterator(self.as_borrowed().iter())
     }
 
+    /// Returns an iterator of `(key, value)` pairs in this dictionary, with each key and value
+    /// extracted into the Rust types `K` and `V`.
+    ///
+    /// # Panics
+    ///
+    /// If PyO3 detects that the dictionary is mutated during iteration, it will panic.
+    /// It is allowed to modify values as you iterate over the dictionary, but only
+    /// so long as the set of keys does not change.
+    pub fn iter_typed<'py, K, V>(&'py self) -> BoundDictTypedIterator<'py, K, V>
+    where
+        K: FromPyObject<'py>,
+        V: FromPyObject<'py>,
+    {
+        self.as_borrowed().iter_typed()
+    }
+
+    /// Extracts each `(key, value)` pair in this dictionary into the Rust types `K` and `V`,
+    /// collecting them in iteration (i.e. insertion) order.
+    ///
+    /// Returns an error if any key or value fails to extract.
+    pub fn extract_items<'py, K, V>(&'py self) -> PyResult<Vec<(K, V)>>
+    where
+        K: FromPyObject<'py>,
+        V: FromPyObject<'py>,
+    {
+        self.as_borrowed().extract_items()
+    }
+
     /// Returns `self` cast as a `PyMapping`.
     pub fn as_mapping(&self) -> &PyMapping {
         unsafe { self.downcast_unchecked() }
@@ -362,6 +447,65 @@ pub trait PyDictMethods<'py>: crate::sealed::Sealed {
     /// so long as the set of keys does not change.
     fn iter(&self) -> BoundDictIterator<'py>;
 
+    /// Returns an iterator of `(key, value)` pairs in this dictionary, with each key and value
+    /// extracted into the Rust types `K` and `V`.
+    ///
+    /// Each item is a `PyResult`, which is an error if either the key or the value failed to
+    /// extract. This makes it straightforward to collect the dictionary into a Rust collection:
+    ///
+    /// ```rust
+    /// use pyo3::prelude::*;
+    /// use pyo3::types::PyDict;
+    /// use std::collections::HashMap;
+    ///
+    /// # fn main() -> PyResult<()> {
+    /// Python::with_gil(|py| -> PyResult<()> {
+    ///     let dict = py.eval_bound("{'a': 1, 'b': 2}", None, None)?.downcast_into::<PyDict>()?;
+    ///     let map = dict.iter_typed::<String, u32>().collect::<PyResult<HashMap<_, _>>>()?;
+    ///     assert_eq!(map["b"], 2);
+    ///     Ok(())
+    /// })
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If PyO3 detects that the dictionary is mutated during iteration, it will panic.
+    /// It is allowed to modify values as you iterate over the dictionary, but only
+    /// so long as the set of keys does not change.
+    fn iter_typed<K, V>(&self) -> BoundDictTypedIterator<'py, K, V>
+    where
+        K: FromPyObject<'py>,
+        V: FromPyObject<'py>;
+
+    /// Extracts each `(key, value)` pair in this dictionary into the Rust types `K` and `V`,
+    /// collecting them in iteration (i.e. insertion) order.
+    ///
+    /// Returns an error if any key or value fails to extract. This is an eager version of
+    /// [`iter_typed`](PyDictMethods::iter_typed):
+    ///
+    /// ```rust
+    /// use pyo3::prelude::*;
+    /// use pyo3::types::PyDict;
+    ///
+    /// # fn main() -> PyResult<()> {
+    /// Python::with_gil(|py| -> PyResult<()> {
+    ///     let dict = py.eval_bound("{'b': 2, 'a': 1}", None, None)?.downcast_into::<PyDict>()?;
+    ///     let items: Vec<(String, u32)> = dict.extract_items()?;
+    ///     assert_eq!(items, [("b".to_owned(), 2), ("a".to_owned(), 1)]);
+    ///     Ok(())
+    /// })
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If PyO3 detects that the dictionary is mutated during extraction, it will panic.
+    fn extract_items<K, V>(&self) -> PyResult<Vec<(K, V)>>
+    where
+        K: FromPyObject<'py>,
+        V: FromPyObject<'py>;
+
     /// Returns `self` cast as a `PyMapping`.
     fn as_mapping(&self) -> &Bound<'py, PyMapping>;
 
@@ -510,6 +654,25 @@ impl<'py> PyDictMethods<'py> for Bound<'py, PyDict> {
         BoundDictIterator::new(self.clone())
     }
 
+    fn iter_typed<K, V>(&self) -> BoundDictTypedIterator<'py, K, V>
+    where
+        K: FromPyObject<'py>,
+        V: FromPyObject<'py>,
+    {
+        BoundDictTypedIterator {
+            inner: self.iter(),
+            _types: PhantomData,
+        }
+    }
+
+    fn extract_items<K, V>(&self) -> PyResult<Vec<(K, V)>>
+    where
+        K: FromPyObject<'py>,
+        V: FromPyObject<'py>,
+    {
+        self.iter_typed().collect()
+    }
+
     fn as_mapping(&self) -> &Bound<'py, PyMapping> {
         unsafe { self.downcast_unchecked() }
     }
@@ -688,6 +851,44 @@ impl<'py> IntoIterator for &Bound<'py, PyDict> {
     }
 }
 
+/// PyO3 implementation of an iterator for a Python `dict` object which extracts its items into
+/// Rust types.
+///
+/// Created by [`PyDictMethods::iter_typed`].
+pub struct BoundDictTypedIterator<'py, K, V> {
+    inner: BoundDictIterator<'py>,
+    _types: PhantomData<fn() -> (K, V)>,
+}
+
+impl<'py, K, V> Iterator for BoundDictTypedIterator<'py, K, V>
+where
+    K: FromPyObject<'py>,
+    V: FromPyObject<'py>,
+{
+    type Item = PyResult<(K, V)>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, value) = self.inner.next()?;
+        Some(key.extract().and_then(|key| Ok((key, value.extract()?))))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'py, K, V> ExactSizeIterator for BoundDictTypedIterator<'py, K, V>
+where
+    K: FromPyObject<'py>,
+    V: FromPyObject<'py>,
+{
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
 mod borrowed_iter {
     use super::*;
 
@@ -849,6 +1050,27 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_from_iter_unique() {
+        Python::with_gil(|py| {
+            let dict = PyDict::from_iter_unique_bound(py, vec![(1, "a"), (2, "b")]).unwrap();
+            assert_eq!(dict.len(), 2);
+            assert_eq!(
+                dict.get_item(2)
+                    .unwrap()
+                    .unwrap()
+                    .extract::<String>()
+                    .unwrap(),
+                "b"
+            );
+
+            let err =
+                PyDict::from_iter_unique_bound(py, vec![(1, "a"), (2, "b"), (1, "c")]).unwrap_err();
+            assert!(err.is_instance_of::<crate::exceptions::PyValueError>(py));
+            assert_eq!(err.value_bound(py).to_string(), "duplicate key: 1");
+        });
+    }
+
     #[test]
     #[cfg(not(any(PyPy, GraalPy)))]
     fn test_from_sequence() {
@@ -1159,6 +1381,64 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_iter_typed() {
+        Python::with_gil(|py| {
+            let mut v = HashMap::new();
+            v.insert(7, 32);
+            v.insert(8, 42);
+            v.insert(9, 123);
+            let ob = v.to_object(py);
+            let dict: &Bound<'_, PyDict> = ob.downcast_bound(py).unwrap();
+            let iter = dict.iter_typed::<i32, i32>();
+            assert_eq!(iter.len(), 3);
+            let extracted: HashMap<i32, i32> = iter.collect::<PyResult<_>>().unwrap();
+            assert_eq!(extracted, v);
+
+            let gil_ref: &PyDict = ob.downcast(py).unwrap();
+            let key_sum: i32 = gil_ref
+                .iter_typed::<i32, i32>()
+                .map(|item| item.unwrap().0)
+                .sum();
+            assert_eq!(7 + 8 + 9, key_sum);
+        });
+    }
+
+    #[test]
+    fn test_iter_typed_extract_error() {
+        Python::with_gil(|py| {
+            let dict = [("a", 1)].into_py_dict_bound(py);
+            let err = dict
+                .iter_typed::<String, String>()
+                .next()
+                .unwrap()
+                .unwrap_err();
+            assert!(err.is_instance_of::<crate::exceptions::PyTypeError>(py));
+        });
+    }
+
+    #[test]
+    fn test_extract_items() {
+        Python::with_gil(|py| {
+            let dict = [("z", 1), ("a", 2), ("m", 3)].into_py_dict_bound(py);
+            let items: Vec<(String, i32)> = dict.extract_items().unwrap();
+            assert_eq!(
+                items,
+                [
+                    ("z".to_owned(), 1),
+                    ("a".to_owned(), 2),
+                    ("m".to_owned(), 3)
+                ]
+            );
+
+            let empty: Vec<(String, i32)> = PyDict::new_bound(py).extract_items().unwrap();
+            assert!(empty.is_empty());
+
+            let err = dict.extract_items::<String, String>().unwrap_err();
+            assert!(err.is_instance_of::<crate::exceptions::PyTypeError>(py));
+        });
+    }
+
     #[test]
     fn test_iter_value_mutated() {
         Python::with_gil(|py| {
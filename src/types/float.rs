@@ -4,7 +4,8 @@ use crate::{
     ffi, ffi_ptr_ext::FfiPtrExt, instance::Bound, FromPyObject, IntoPy, PyAny, PyErr, PyNativeType,
     PyObject, PyResult, Python, ToPyObject,
 };
-use std::os::raw::c_double;
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_double};
 
 use super::any::PyAnyMethods;
 
@@ -50,6 +51,21 @@ impl PyFloat {
     pub fn value(&self) -> c_double {
         self.as_borrowed().value()
     }
+
+    /// Returns `true` if this float is NaN.
+    pub fn is_nan(&self) -> bool {
+        self.as_borrowed().is_nan()
+    }
+
+    /// Returns `true` if this float is positive or negative infinity.
+    pub fn is_infinite(&self) -> bool {
+        self.as_borrowed().is_infinite()
+    }
+
+    /// Formats this float exactly as Python's `repr(float)` does.
+    pub fn repr_str(&self) -> String {
+        self.as_borrowed().repr_str()
+    }
 }
 
 /// Implementation of functionality for [`PyFloat`].
@@ -61,6 +77,22 @@ impl PyFloat {
 pub trait PyFloatMethods<'py>: crate::sealed::Sealed {
     /// Gets the value of this float.
     fn value(&self) -> c_double;
+
+    /// Returns `true` if this float is NaN.
+    fn is_nan(&self) -> bool;
+
+    /// Returns `true` if this float is positive or negative infinity.
+    fn is_infinite(&self) -> bool;
+
+    /// Formats this float exactly as Python's `repr(float)` does.
+    ///
+    /// This is the shortest string which round-trips to the same value, e.g. `0.1`, `1e+16`,
+    /// `1.0` and `nan`, which differs from Rust's `Display` and `Debug` output for many values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if Python fails to allocate the string.
+    fn repr_str(&self) -> String;
 }
 
 impl<'py> PyFloatMethods<'py> for Bound<'py, PyFloat> {
@@ -76,6 +108,39 @@ impl<'py> PyFloatMethods<'py> for Bound<'py, PyFloat> {
             ffi::PyFloat_AsDouble(self.as_ptr())
         }
     }
+
+    #[inline]
+    fn is_nan(&self) -> bool {
+        self.value().is_nan()
+    }
+
+    #[inline]
+    fn is_infinite(&self) -> bool {
+        self.value().is_infinite()
+    }
+
+    fn repr_str(&self) -> String {
+        // the same call CPython's `float.__repr__` makes
+        let ptr = unsafe {
+            ffi::PyOS_double_to_string(
+                self.value(),
+                b'r' as c_char,
+                0,
+                ffi::Py_DTSF_ADD_DOT_0,
+                std::ptr::null_mut(),
+            )
+        };
+        assert!(
+            !ptr.is_null(),
+            "failed to format float: {}",
+            PyErr::fetch(self.py())
+        );
+        let repr = unsafe { CStr::from_ptr(ptr) }
+            .to_string_lossy()
+            .into_owned();
+        unsafe { ffi::PyMem_Free(ptr.cast()) };
+        repr
+    }
 }
 
 impl ToPyObject for f64 {
@@ -188,4 +253,53 @@ mod tests {
             assert_approx_eq!(v, obj.value());
         });
     }
+
+    #[test]
+    fn test_float_special_values() {
+        use crate::types::float::PyFloatMethods;
+
+        Python::with_gil(|py| {
+            let nan = PyFloat::new_bound(py, f64::NAN);
+            assert!(nan.is_nan());
+            assert!(!nan.is_infinite());
+
+            for value in [f64::INFINITY, f64::NEG_INFINITY] {
+                let inf = PyFloat::new_bound(py, value);
+                assert!(inf.is_infinite());
+                assert!(!inf.is_nan());
+            }
+
+            let finite = PyFloat::new(py, 1.5);
+            assert!(!finite.is_nan());
+            assert!(!finite.is_infinite());
+        });
+    }
+
+    #[test]
+    fn test_float_repr_str() {
+        use crate::types::{any::PyAnyMethods, float::PyFloatMethods, string::PyStringMethods};
+
+        Python::with_gil(|py| {
+            for value in [
+                0.1,
+                1.0,
+                -0.0,
+                1e16,
+                1e-7,
+                123456789.125,
+                0.1 + 0.2,
+                f64::MAX,
+                f64::MIN_POSITIVE,
+                f64::NAN,
+                f64::INFINITY,
+                f64::NEG_INFINITY,
+            ] {
+                let float = PyFloat::new_bound(py, value);
+                let expected = float.repr().unwrap();
+                assert_eq!(float.repr_str(), expected.to_str().unwrap());
+            }
+            assert_eq!(PyFloat::new_bound(py, 1e16).repr_str(), "1e+16");
+            assert_eq!(PyFloat::new(py, 2.0).repr_str(), "2.0");
+        });
+    }
 }
@@ -5,10 +5,11 @@ use crate::ffi::{self, Py_ssize_t};
 use crate::ffi_ptr_ext::FfiPtrExt;
 use crate::instance::Borrowed;
 use crate::internal_tricks::get_ssize_index;
-use crate::types::{PySequence, PyTuple};
+use crate::types::{PyDict, PySequence, PyTuple};
 use crate::{Bound, PyAny, PyNativeType, PyObject, Python, ToPyObject};
 
 use crate::types::any::PyAnyMethods;
+use crate::types::dict::PyDictMethods;
 use crate::types::sequence::PySequenceMethods;
 
 /// Represents a Python `list`.
@@ -264,11 +265,34 @@ impl PyList {
         self.as_borrowed().sort()
     }
 
+    /// Sorts the list in-place by the result of calling `key` on each item, in descending order
+    /// if `reverse` is true. Equivalent to the Python expression `l.sort(key=key, reverse=reverse)`.
+    ///
+    /// The sort is stable: items whose keys compare equal keep their original relative order.
+    pub fn sort_with_key(&self, key: &PyAny, reverse: bool) -> PyResult<()> {
+        self.as_borrowed()
+            .sort_with_key(&key.as_borrowed(), reverse)
+    }
+
     /// Reverses the list in-place. Equivalent to the Python expression `l.reverse()`.
     pub fn reverse(&self) -> PyResult<()> {
         self.as_borrowed().reverse()
     }
 
+    /// Removes all items from the list. Equivalent to the Python expression `l.clear()`.
+    pub fn clear(&self) -> PyResult<()> {
+        self.as_borrowed().clear()
+    }
+
+    /// Removes and returns the item at `index`, or the last item if `index` is `None`.
+    /// Equivalent to the Python expression `l.pop(index)`.
+    ///
+    /// Raises `IndexError` if the list is empty or `index` is out of range. Like in Python,
+    /// negative indices count from the end of the list.
+    pub fn pop(&self, index: Option<isize>) -> PyResult<&PyAny> {
+        self.as_borrowed().pop(index).map(Bound::into_gil_ref)
+    }
+
     /// Return a new tuple containing the contents of the list; equivalent to the Python expression `tuple(list)`.
     ///
     /// This method is equivalent to `self.as_sequence().to_tuple()` and faster than `PyTuple::new(py, this_list)`.
@@ -378,9 +402,25 @@ pub trait PyListMethods<'py>: crate::sealed::Sealed {
     /// Sorts the list in-place. Equivalent to the Python expression `l.sort()`.
     fn sort(&self) -> PyResult<()>;
 
+    /// Sorts the list in-place by the result of calling `key` on each item, in descending order
+    /// if `reverse` is true. Equivalent to the Python expression `l.sort(key=key, reverse=reverse)`.
+    ///
+    /// The sort is stable: items whose keys compare equal keep their original relative order.
+    fn sort_with_key(&self, key: &Bound<'py, PyAny>, reverse: bool) -> PyResult<()>;
+
     /// Reverses the list in-place. Equivalent to the Python expression `l.reverse()`.
     fn reverse(&self) -> PyResult<()>;
 
+    /// Removes all items from the list. Equivalent to the Python expression `l.clear()`.
+    fn clear(&self) -> PyResult<()>;
+
+    /// Removes and returns the item at `index`, or the last item if `index` is `None`.
+    /// Equivalent to the Python expression `l.pop(index)`.
+    ///
+    /// Raises `IndexError` if the list is empty or `index` is out of range. Like in Python,
+    /// negative indices count from the end of the list.
+    fn pop(&self, index: Option<isize>) -> PyResult<Bound<'py, PyAny>>;
+
     /// Return a new tuple containing the contents of the list; equivalent to the Python expression `tuple(list)`.
     ///
     /// This method is equivalent to `self.as_sequence().to_tuple()` and faster than `PyTuple::new(py, this_list)`.
@@ -572,11 +612,36 @@ impl<'py> PyListMethods<'py> for Bound<'py, PyList> {
         err::error_on_minusone(self.py(), unsafe { ffi::PyList_Sort(self.as_ptr()) })
     }
 
+    /// Sorts the list in-place by `key`. Equivalent to the Python expression `l.sort(key=key, reverse=reverse)`.
+    fn sort_with_key(&self, key: &Bound<'py, PyAny>, reverse: bool) -> PyResult<()> {
+        let py = self.py();
+        let kwargs = PyDict::new_bound(py);
+        kwargs.set_item(crate::intern!(py, "key"), key)?;
+        kwargs.set_item(crate::intern!(py, "reverse"), reverse)?;
+        self.call_method(crate::intern!(py, "sort"), (), Some(&kwargs))
+            .map(drop)
+    }
+
     /// Reverses the list in-place. Equivalent to the Python expression `l.reverse()`.
     fn reverse(&self) -> PyResult<()> {
         err::error_on_minusone(self.py(), unsafe { ffi::PyList_Reverse(self.as_ptr()) })
     }
 
+    /// Removes all items from the list. Equivalent to the Python expression `l.clear()`.
+    fn clear(&self) -> PyResult<()> {
+        err::error_on_minusone(self.py(), unsafe {
+            ffi::PyList_SetSlice(self.as_ptr(), 0, ffi::PY_SSIZE_T_MAX, std::ptr::null_mut())
+        })
+    }
+
+    fn pop(&self, index: Option<isize>) -> PyResult<Bound<'py, PyAny>> {
+        let pop = crate::intern!(self.py(), "pop");
+        match index {
+            Some(index) => self.call_method1(pop, (index,)),
+            None => self.call_method0(pop),
+        }
+    }
+
     /// Return a new tuple containing the contents of the list; equivalent to the Python expression `tuple(list)`.
     ///
     /// This method is equivalent to `self.as_sequence().to_tuple()` and faster than `PyTuple::new(py, this_list)`.
@@ -1001,6 +1066,29 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_sort_with_key() {
+        Python::with_gil(|py| {
+            let list = PyList::new_bound(py, ["bb", "a", "ccc", "dd", "e"]);
+            let len = py.eval_bound("len", None, None).unwrap();
+            list.sort_with_key(&len, false).unwrap();
+            assert_eq!(
+                list.extract::<Vec<String>>().unwrap(),
+                ["a", "e", "bb", "dd", "ccc"]
+            );
+            list.sort_with_key(&len, true).unwrap();
+            assert_eq!(
+                list.extract::<Vec<String>>().unwrap(),
+                ["ccc", "bb", "dd", "a", "e"]
+            );
+
+            let err = list
+                .sort_with_key(&PyList::empty_bound(py).into_any(), false)
+                .unwrap_err();
+            assert!(err.is_instance_of::<crate::exceptions::PyTypeError>(py));
+        });
+    }
+
     #[test]
     fn test_reverse() {
         Python::with_gil(|py| {
@@ -1018,6 +1106,41 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_clear() {
+        Python::with_gil(|py| {
+            let list = PyList::new_bound(py, [2, 3, 5, 7]);
+            let alias = list.clone();
+            list.clear().unwrap();
+            assert!(list.is_empty());
+            assert!(alias.is_empty());
+
+            list.append(11).unwrap();
+            assert_eq!(list.len(), 1);
+
+            let empty = PyList::empty_bound(py);
+            empty.clear().unwrap();
+            assert!(empty.is_empty());
+        });
+    }
+
+    #[test]
+    fn test_pop() {
+        Python::with_gil(|py| {
+            let list = PyList::new_bound(py, [2, 3, 5, 7]);
+            assert_eq!(list.pop(None).unwrap().extract::<i32>().unwrap(), 7);
+            assert_eq!(list.pop(Some(0)).unwrap().extract::<i32>().unwrap(), 2);
+            assert_eq!(list.pop(Some(-1)).unwrap().extract::<i32>().unwrap(), 5);
+            assert_eq!(list.len(), 1);
+
+            let err = list.pop(Some(1)).unwrap_err();
+            assert!(err.is_instance_of::<crate::exceptions::PyIndexError>(py));
+            assert_eq!(list.pop(None).unwrap().extract::<i32>().unwrap(), 3);
+            let err = list.pop(None).unwrap_err();
+            assert!(err.is_instance_of::<crate::exceptions::PyIndexError>(py));
+        });
+    }
+
     #[test]
     fn test_array_into_py() {
         Python::with_gil(|py| {
@@ -1,4 +1,5 @@
-use crate::err::{PyDowncastError, PyResult};
+use crate::err::{DowncastError, PyDowncastError, PyResult};
+use crate::exceptions::PyKeyError;
 use crate::ffi_ptr_ext::FfiPtrExt;
 use crate::instance::Bound;
 use crate::py_result_ext::PyResultExt;
@@ -15,6 +16,20 @@ pyobject_native_type_named!(PyMapping);
 pyobject_native_type_extract!(PyMapping);
 
 impl PyMapping {
+    /// Views an object supporting the mapping protocol as a `PyMapping`, raising `TypeError` for
+    /// any other object.
+    ///
+    /// This only checks `PyMapping_Check`, i.e. that the type supports subscripting, so unlike
+    /// [`downcast`][PyAnyMethods::downcast] it accepts objects which are not registered with
+    /// `collections.abc.Mapping`, including sequences such as `list`.
+    pub fn from_bound_object<'py>(obj: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyMapping>> {
+        if unsafe { ffi::PyMapping_Check(obj.as_ptr()) } != 0 {
+            Ok(unsafe { obj.downcast_unchecked::<PyMapping>() }.clone())
+        } else {
+            Err(DowncastError::new(obj, "Mapping").into())
+        }
+    }
+
     /// Returns the number of objects in the mapping.
     ///
     /// This is equivalent to the Python expression `len(self)`.
@@ -52,6 +67,20 @@ impl PyMapping {
         self.as_borrowed().get_item(key).map(Bound::into_gil_ref)
     }
 
+    /// Gets the item in self with key `key`, returning `None` if the key is not present.
+    ///
+    /// Unlike [`get_item`](PyMapping::get_item), a `KeyError` raised by the lookup is converted to
+    /// `Ok(None)`; any other error is returned as `Err`.
+    #[inline]
+    pub fn get_item_opt<K>(&self, key: K) -> PyResult<Option<&PyAny>>
+    where
+        K: ToPyObject,
+    {
+        self.as_borrowed()
+            .get_item_opt(key)
+            .map(|item| item.map(Bound::into_gil_ref))
+    }
+
     /// Sets the item in self with key `key`.
     ///
     /// This is equivalent to the Python expression `self[key] = value`.
@@ -134,6 +163,14 @@ pub trait PyMappingMethods<'py>: crate::sealed::Sealed {
     where
         K: ToPyObject;
 
+    /// Gets the item in self with key `key`, returning `None` if the key is not present.
+    ///
+    /// Unlike [`get_item`](PyMappingMethods::get_item), a `KeyError` raised by the lookup is
+    /// converted to `Ok(None)`; any other error is returned as `Err`.
+    fn get_item_opt<K>(&self, key: K) -> PyResult<Option<Bound<'py, PyAny>>>
+    where
+        K: ToPyObject;
+
     /// Sets the item in self with key `key`.
     ///
     /// This is equivalent to the Python expression `self[key] = value`.
@@ -187,6 +224,17 @@ impl<'py> PyMappingMethods<'py> for Bound<'py, PyMapping> {
         PyAnyMethods::get_item(&**self, key)
     }
 
+    fn get_item_opt<K>(&self, key: K) -> PyResult<Option<Bound<'py, PyAny>>>
+    where
+        K: ToPyObject,
+    {
+        match PyAnyMethods::get_item(&**self, key) {
+            Ok(item) => Ok(Some(item)),
+            Err(err) if err.is_instance_of::<PyKeyError>(self.py()) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
     #[inline]
     fn set_item<K, V>(&self, key: K, value: V) -> PyResult<()>
     where
@@ -286,7 +334,10 @@ impl<'v> crate::PyTryFrom<'v> for PyMapping {
 mod tests {
     use std::collections::HashMap;
 
-    use crate::{exceptions::PyKeyError, types::PyTuple};
+    use crate::{
+        exceptions::PyTypeError,
+        types::{PyList, PyTuple},
+    };
 
     use super::*;
 
@@ -340,6 +391,33 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_get_item_opt() {
+        Python::with_gil(|py| {
+            let mut v = HashMap::new();
+            v.insert(7, 32);
+            let ob = v.to_object(py);
+            let mapping = ob.downcast_bound::<PyMapping>(py).unwrap();
+            assert_eq!(
+                32,
+                mapping
+                    .get_item_opt(7i32)
+                    .unwrap()
+                    .unwrap()
+                    .extract::<i32>()
+                    .unwrap()
+            );
+            assert!(mapping.get_item_opt(8i32).unwrap().is_none());
+
+            // errors other than `KeyError` are still raised
+            let unhashable = PyList::empty_bound(py);
+            assert!(mapping
+                .get_item_opt(unhashable)
+                .unwrap_err()
+                .is_instance_of::<PyTypeError>(py));
+        });
+    }
+
     #[test]
     fn test_set_item() {
         Python::with_gil(|py| {
@@ -434,6 +512,26 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_from_bound_object() {
+        Python::with_gil(|py| {
+            let dict = PyDict::new_bound(py);
+            dict.set_item(1, "one").unwrap();
+            let mapping = PyMapping::from_bound_object(dict.as_any()).unwrap();
+            assert_eq!(
+                mapping.get_item(1).unwrap().extract::<&str>().unwrap(),
+                "one"
+            );
+
+            let err = PyMapping::from_bound_object(&5i32.to_object(py).into_bound(py)).unwrap_err();
+            assert!(err.is_instance_of::<PyTypeError>(py));
+            assert_eq!(
+                err.value_bound(py).to_string(),
+                "'int' object cannot be converted to 'Mapping'"
+            );
+        });
+    }
+
     #[test]
     #[allow(deprecated)]
     fn test_mapping_try_from() {
@@ -1,6 +1,7 @@
 use crate::callback::IntoPyCallbackOutput;
 use crate::err::{PyErr, PyResult};
 use crate::ffi_ptr_ext::FfiPtrExt;
+use crate::impl_::pymodule::get_module_state;
 use crate::py_result_ext::PyResultExt;
 use crate::pyclass::PyClass;
 use crate::types::{
@@ -598,6 +599,50 @@ pub trait PyModuleMethods<'py>: crate::sealed::Sealed {
     /// [1]: crate::prelude::pyfunction
     /// [2]: crate::wrap_pyfunction
     fn add_function(&self, fun: Bound<'_, PyCFunction>) -> PyResult<()>;
+
+    /// Stores `state` in the per-module state of this module.
+    ///
+    /// This is intended to be called from a [`#[pymodule]`][1] initializer, so that functions
+    /// receiving the module through `#[pyo3(pass_module)]` can later access it with
+    /// [`state`](PyModuleMethods::state) or through a [`ModuleState`] argument. Because the
+    /// state is owned by the module object, each interpreter importing the module gets its own.
+    ///
+    /// Fails if the module was not created by PyO3 or if state has already been set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pyo3::prelude::*;
+    ///
+    /// struct Config {
+    ///     verbose: bool,
+    /// }
+    ///
+    /// #[pyfunction]
+    /// #[pyo3(pass_module)]
+    /// fn is_verbose(m: &Bound<'_, PyModule>) -> PyResult<bool> {
+    ///     Ok(m.state::<Config>()?.verbose)
+    /// }
+    ///
+    /// #[pymodule]
+    /// fn my_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    ///     m.set_state(Config { verbose: true })?;
+    ///     m.add_function(wrap_pyfunction!(is_verbose, m)?)
+    /// }
+    /// ```
+    ///
+    /// [1]: crate::prelude::pymodule
+    fn set_state<T>(&self, state: T) -> PyResult<()>
+    where
+        T: Send + Sync + 'static;
+
+    /// Returns a reference to the state previously stored with
+    /// [`set_state`](PyModuleMethods::set_state).
+    ///
+    /// Fails if no state has been set or if it is not of type `T`.
+    fn state<T>(&self) -> PyResult<&T>
+    where
+        T: Send + Sync + 'static;
 }
 
 impl<'py> PyModuleMethods<'py> for Bound<'py, PyModule> {
@@ -711,6 +756,114 @@ impl<'py> PyModuleMethods<'py> for Bound<'py, PyModule> {
         let name = fun.getattr(__name__(self.py()))?;
         self.add(name.downcast_into::<PyString>()?, fun)
     }
+
+    fn set_state<T>(&self, state: T) -> PyResult<()>
+    where
+        T: Send + Sync + 'static,
+    {
+        let slot = unsafe { get_module_state(self.as_ptr()) };
+        if slot.is_null() {
+            return Err(exceptions::PyRuntimeError::new_err(
+                "module state is only available for modules created by PyO3",
+            ));
+        }
+        // Safety: the GIL is held and the state is never replaced once set, so no references
+        // handed out by `state` can be invalidated here.
+        let value = unsafe { &mut (*slot).value };
+        if value.is_some() {
+            return Err(exceptions::PyRuntimeError::new_err(
+                "module state has already been set",
+            ));
+        }
+        *value = Some(Box::new(state));
+        Ok(())
+    }
+
+    fn state<T>(&self) -> PyResult<&T>
+    where
+        T: Send + Sync + 'static,
+    {
+        let slot = unsafe { get_module_state(self.as_ptr()) };
+        // Safety: the state lives as long as the module, which `self` keeps alive.
+        let value = unsafe { slot.as_ref() }.and_then(|slot| slot.value.as_deref());
+        match value {
+            Some(value) => value.downcast_ref::<T>().ok_or_else(|| {
+                exceptions::PyRuntimeError::new_err(format!(
+                    "module state is not of type `{}`",
+                    std::any::type_name::<T>()
+                ))
+            }),
+            None => Err(exceptions::PyRuntimeError::new_err(
+                "module state has not been set",
+            )),
+        }
+    }
+}
+
+/// A `#[pyo3(pass_module)]` argument which also gives access to the module's state of type `T`.
+///
+/// Extracting this argument fails with `RuntimeError` if the module has no state of type `T`, see
+/// [`PyModuleMethods::set_state`]. It dereferences to the state; the module itself is available
+/// through [`module`](ModuleState::module).
+///
+/// # Example
+///
+/// ```rust
+/// use pyo3::prelude::*;
+/// use pyo3::types::ModuleState;
+///
+/// struct Config {
+///     greeting: String,
+/// }
+///
+/// #[pyfunction]
+/// #[pyo3(pass_module)]
+/// fn greet(config: ModuleState<'_, '_, Config>, name: &str) -> String {
+///     format!("{}, {}!", config.greeting, name)
+/// }
+///
+/// #[pymodule]
+/// fn my_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+///     m.set_state(Config {
+///         greeting: "Hello".to_owned(),
+///     })?;
+///     m.add_function(wrap_pyfunction!(greet, m)?)
+/// }
+/// ```
+pub struct ModuleState<'a, 'py, T> {
+    module: &'a Bound<'py, PyModule>,
+    state: &'a T,
+}
+
+impl<'a, 'py, T> ModuleState<'a, 'py, T>
+where
+    T: Send + Sync + 'static,
+{
+    /// Looks up the state of type `T` stored in `module`.
+    pub fn new(module: &'a Bound<'py, PyModule>) -> PyResult<Self> {
+        Ok(ModuleState {
+            module,
+            state: module.state()?,
+        })
+    }
+
+    /// Returns the module this state belongs to.
+    pub fn module(&self) -> &'a Bound<'py, PyModule> {
+        self.module
+    }
+
+    /// Returns the state stored in the module.
+    pub fn state(&self) -> &'a T {
+        self.state
+    }
+}
+
+impl<T> std::ops::Deref for ModuleState<'_, '_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.state
+    }
 }
 
 fn __all__(py: Python<'_>) -> &Bound<'_, PyString> {
@@ -725,7 +878,10 @@ fn __name__(py: Python<'_>) -> &Bound<'_, PyString> {
 #[cfg_attr(not(feature = "gil-refs"), allow(deprecated))]
 mod tests {
     use crate::{
-        types::{module::PyModuleMethods, string::PyStringMethods, PyModule},
+        types::{
+            any::PyAnyMethods, dict::PyDictMethods, module::PyModuleMethods,
+            string::PyStringMethods, PyModule,
+        },
         Python,
     };
 
@@ -753,4 +909,17 @@ mod tests {
                 .ends_with("site.py"));
         })
     }
+
+    #[test]
+    fn module_dict_is_namespace() {
+        Python::with_gil(|py| {
+            let module = PyModule::new_bound(py, "namespace").unwrap();
+            let dict = module.dict();
+            assert!(dict.is(&module.getattr("__dict__").unwrap()));
+
+            dict.set_item("answer", 42).unwrap();
+            let answer: i32 = module.getattr("answer").unwrap().extract().unwrap();
+            assert_eq!(answer, 42);
+        })
+    }
 }
@@ -119,6 +119,8 @@ impl PySet {
     }
 
     /// Removes and returns an arbitrary element from the set.
+    ///
+    /// Returns `None` if the set is empty.
     pub fn pop(&self) -> Option<PyObject> {
         self.as_borrowed().pop().map(Bound::unbind)
     }
@@ -173,6 +175,8 @@ pub trait PySetMethods<'py>: crate::sealed::Sealed {
         K: ToPyObject;
 
     /// Removes and returns an arbitrary element from the set.
+    ///
+    /// Returns `None` if the set is empty.
     fn pop(&self) -> Option<Bound<'py, PyAny>>;
 
     /// Returns an iterator of values in this set.
@@ -392,7 +396,8 @@ pub(crate) fn new_from_iter<T: ToPyObject>(
 #[cfg_attr(not(feature = "gil-refs"), allow(deprecated))]
 mod tests {
     use super::PySet;
-    use crate::{Python, ToPyObject};
+    use crate::types::{PyAnyMethods, PySetMethods};
+    use crate::{PyErr, Python, ToPyObject};
     use std::collections::HashSet;
 
     #[test]
@@ -485,6 +490,21 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_set_discard_and_pop_bound() {
+        Python::with_gil(|py| {
+            let set = PySet::new_bound(py, &[1, 2]).unwrap();
+            assert!(set.discard(1).unwrap());
+            assert!(!set.discard(1).unwrap());
+
+            let val = set.pop().unwrap();
+            assert_eq!(val.extract::<i32>().unwrap(), 2);
+            assert!(set.is_empty());
+            assert!(set.pop().is_none());
+            assert!(PyErr::take(py).is_none());
+        });
+    }
+
     #[test]
     fn test_set_iter() {
         Python::with_gil(|py| {
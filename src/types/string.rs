@@ -5,7 +5,7 @@ use crate::instance::Borrowed;
 use crate::py_result_ext::PyResultExt;
 use crate::types::any::PyAnyMethods;
 use crate::types::bytes::PyBytesMethods;
-use crate::types::PyBytes;
+use crate::types::{PyBytes, PyList};
 use crate::{ffi, Bound, IntoPy, Py, PyAny, PyNativeType, PyResult, Python};
 use std::borrow::Cow;
 use std::os::raw::c_char;
@@ -240,6 +240,15 @@ impl PyString {
         }
     }
 
+    /// Gets the UTF-8 encoding of the Python string as a byte slice, without copying.
+    ///
+    /// Returns a `UnicodeEncodeError` if the input is not valid unicode
+    /// (containing unpaired surrogates).
+    #[cfg(any(Py_3_10, not(Py_LIMITED_API)))]
+    pub fn as_utf8_bytes(&self) -> PyResult<&[u8]> {
+        self.as_borrowed().as_utf8_bytes()
+    }
+
     /// Converts the `PyString` into a Rust string, avoiding copying when possible.
     ///
     /// Returns a `UnicodeEncodeError` if the input is not valid unicode
@@ -256,6 +265,31 @@ impl PyString {
         self.as_borrowed().to_string_lossy()
     }
 
+    /// Returns whether this string is equal to the Rust string `other`.
+    ///
+    /// This compares the Unicode data with `PyUnicode_CompareWithASCIIString` or
+    /// `PyUnicode_Compare`, without decoding the string into UTF-8. Strings containing unpaired
+    /// surrogates are never equal to a Rust `&str`.
+    pub fn eq_str(&self, other: &str) -> bool {
+        self.as_borrowed().eq_str(other)
+    }
+
+    /// Splits the string at line boundaries, like Python's `str.splitlines()`.
+    ///
+    /// Line boundaries are those of Python rather than Rust's [`str::lines`], so they include
+    /// e.g. `\r`, `\x0b` and `\u2028`. The line breaks are not included in the lines.
+    pub fn splitlines(&self) -> PyResult<&PyList> {
+        self.as_borrowed().splitlines().map(Bound::into_gil_ref)
+    }
+
+    /// Splits the string at `sep`, like Python's `str.split(sep)`.
+    ///
+    /// If `sep` is `None`, the string is split at runs of whitespace and empty pieces are
+    /// discarded. An empty `sep` raises `ValueError`.
+    pub fn split(&self, sep: Option<&str>) -> PyResult<&PyList> {
+        self.as_borrowed().split(sep).map(Bound::into_gil_ref)
+    }
+
     /// Obtains the raw data backing the Python string.
     ///
     /// If the Python string object was created through legacy APIs, its internal storage format
@@ -290,6 +324,18 @@ pub trait PyStringMethods<'py>: crate::sealed::Sealed {
     #[cfg(any(Py_3_10, not(Py_LIMITED_API)))]
     fn to_str(&self) -> PyResult<&str>;
 
+    /// Gets the UTF-8 encoding of the Python string as a byte slice, without copying.
+    ///
+    /// The bytes are the string's internal UTF-8 buffer (as used by [`to_str`]), which Python
+    /// creates on first use and keeps for the lifetime of the string.
+    ///
+    /// Returns a `UnicodeEncodeError` if the input is not valid unicode
+    /// (containing unpaired surrogates).
+    ///
+    /// [`to_str`]: PyStringMethods::to_str
+    #[cfg(any(Py_3_10, not(Py_LIMITED_API)))]
+    fn as_utf8_bytes(&self) -> PyResult<&[u8]>;
+
     /// Converts the `PyString` into a Rust string, avoiding copying when possible.
     ///
     /// Returns a `UnicodeEncodeError` if the input is not valid unicode
@@ -305,6 +351,25 @@ pub trait PyStringMethods<'py>: crate::sealed::Sealed {
     /// Encodes this string as a Python `bytes` object, using UTF-8 encoding.
     fn encode_utf8(&self) -> PyResult<Bound<'py, PyBytes>>;
 
+    /// Returns whether this string is equal to the Rust string `other`.
+    ///
+    /// This compares the Unicode data with `PyUnicode_CompareWithASCIIString` or
+    /// `PyUnicode_Compare`, without decoding the string into UTF-8. Strings containing unpaired
+    /// surrogates are never equal to a Rust `&str`.
+    fn eq_str(&self, other: &str) -> bool;
+
+    /// Splits the string at line boundaries, like Python's `str.splitlines()`.
+    ///
+    /// Line boundaries are those of Python rather than Rust's [`str::lines`], so they include
+    /// e.g. `\r`, `\x0b` and `\u2028`. The line breaks are not included in the lines.
+    fn splitlines(&self) -> PyResult<Bound<'py, PyList>>;
+
+    /// Splits the string at `sep`, like Python's `str.split(sep)`.
+    ///
+    /// If `sep` is `None`, the string is split at runs of whitespace and empty pieces are
+    /// discarded. An empty `sep` raises `ValueError`.
+    fn split(&self, sep: Option<&str>) -> PyResult<Bound<'py, PyList>>;
+
     /// Obtains the raw data backing the Python string.
     ///
     /// If the Python string object was created through legacy APIs, its internal storage format
@@ -329,6 +394,11 @@ impl<'py> PyStringMethods<'py> for Bound<'py, PyString> {
         self.as_borrowed().to_str()
     }
 
+    #[cfg(any(Py_3_10, not(Py_LIMITED_API)))]
+    fn as_utf8_bytes(&self) -> PyResult<&[u8]> {
+        self.as_borrowed().as_utf8_bytes()
+    }
+
     fn to_cow(&self) -> PyResult<Cow<'_, str>> {
         self.as_borrowed().to_cow()
     }
@@ -345,6 +415,32 @@ impl<'py> PyStringMethods<'py> for Bound<'py, PyString> {
         }
     }
 
+    fn eq_str(&self, other: &str) -> bool {
+        self.as_borrowed().eq_str(other)
+    }
+
+    fn splitlines(&self) -> PyResult<Bound<'py, PyList>> {
+        unsafe {
+            ffi::PyUnicode_Splitlines(self.as_ptr(), 0)
+                .assume_owned_or_err(self.py())
+                .downcast_into_unchecked::<PyList>()
+        }
+    }
+
+    fn split(&self, sep: Option<&str>) -> PyResult<Bound<'py, PyList>> {
+        let sep = sep.map(|sep| PyString::new_bound(self.py(), sep));
+        unsafe {
+            ffi::PyUnicode_Split(
+                self.as_ptr(),
+                sep.as_ref()
+                    .map_or(std::ptr::null_mut(), |sep| sep.as_ptr()),
+                -1,
+            )
+            .assume_owned_or_err(self.py())
+            .downcast_into_unchecked::<PyList>()
+        }
+    }
+
     #[cfg(not(any(Py_LIMITED_API, GraalPy)))]
     unsafe fn data(&self) -> PyResult<PyStringData<'_>> {
         self.as_borrowed().data()
@@ -355,6 +451,13 @@ impl<'a> Borrowed<'a, '_, PyString> {
     #[cfg(any(Py_3_10, not(Py_LIMITED_API)))]
     #[allow(clippy::wrong_self_convention)]
     pub(crate) fn to_str(self) -> PyResult<&'a str> {
+        // Safety: Python guarantees that the UTF-8 representation is valid UTF-8.
+        self.as_utf8_bytes()
+            .map(|bytes| unsafe { std::str::from_utf8_unchecked(bytes) })
+    }
+
+    #[cfg(any(Py_3_10, not(Py_LIMITED_API)))]
+    pub(crate) fn as_utf8_bytes(self) -> PyResult<&'a [u8]> {
         // PyUnicode_AsUTF8AndSize only available on limited API starting with 3.10.
         let mut size: ffi::Py_ssize_t = 0;
         let data: *const u8 =
@@ -362,9 +465,7 @@ impl<'a> Borrowed<'a, '_, PyString> {
         if data.is_null() {
             Err(crate::PyErr::fetch(self.py()))
         } else {
-            Ok(unsafe {
-                std::str::from_utf8_unchecked(std::slice::from_raw_parts(data, size as usize))
-            })
+            Ok(unsafe { std::slice::from_raw_parts(data, size as usize) })
         }
     }
 
@@ -386,6 +487,21 @@ impl<'a> Borrowed<'a, '_, PyString> {
         }
     }
 
+    fn eq_str(self, other: &str) -> bool {
+        // Short ASCII strings are compared through a NUL-terminated copy on the stack
+        const MAX_ASCII_LEN: usize = 64;
+        if other.len() <= MAX_ASCII_LEN && other.bytes().all(|b| b.is_ascii() && b != 0) {
+            let mut buffer = [0u8; MAX_ASCII_LEN + 1];
+            buffer[..other.len()].copy_from_slice(other.as_bytes());
+            return unsafe {
+                ffi::PyUnicode_CompareWithASCIIString(self.as_ptr(), buffer.as_ptr().cast()) == 0
+            };
+        }
+        // Comparing two `str` objects cannot fail
+        let other = PyString::new_bound(self.py(), other);
+        unsafe { ffi::PyUnicode_Compare(self.as_ptr(), other.as_ptr()) == 0 }
+    }
+
     #[allow(clippy::wrong_self_convention)]
     fn to_string_lossy(self) -> Cow<'a, str> {
         let ptr = self.as_ptr();
@@ -505,7 +621,7 @@ impl IntoPy<Py<PyString>> for &'_ Py<PyString> {
 #[cfg_attr(not(feature = "gil-refs"), allow(deprecated))]
 mod tests {
     use super::*;
-    use crate::{PyObject, ToPyObject};
+    use crate::{PyErr, PyObject, ToPyObject};
 
     #[test]
     fn test_to_str_utf8() {
@@ -536,6 +652,21 @@ mod tests {
         })
     }
 
+    #[test]
+    #[cfg(any(Py_3_10, not(Py_LIMITED_API)))]
+    fn test_as_utf8_bytes() {
+        Python::with_gil(|py| {
+            let s = "哈哈🐈";
+            let py_string = PyString::new_bound(py, s);
+            assert_eq!(py_string.as_utf8_bytes().unwrap(), s.as_bytes());
+
+            let surrogate = py.eval_bound(r"'\ud800'", None, None).unwrap();
+            let surrogate = surrogate.downcast::<PyString>().unwrap();
+            let err = surrogate.as_utf8_bytes().unwrap_err();
+            assert!(err.is_instance_of::<crate::exceptions::PyUnicodeEncodeError>(py));
+        })
+    }
+
     #[test]
     fn test_encode_utf8_unicode() {
         Python::with_gil(|py| {
@@ -708,6 +839,64 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_eq_str() {
+        Python::with_gil(|py| {
+            let s = PyString::new_bound(py, "hello");
+            assert!(s.eq_str("hello"));
+            assert!(!s.eq_str("hell"));
+            assert!(!s.eq_str("hello!"));
+
+            let s = PyString::new_bound(py, "哈哈🐈");
+            assert!(s.eq_str("哈哈🐈"));
+            assert!(!s.eq_str("哈哈"));
+
+            let s = PyString::new_bound(py, "hello\0");
+            assert!(s.eq_str("hello\0"));
+            assert!(!s.eq_str("hello"));
+
+            let long = "a".repeat(100);
+            let s = PyString::new_bound(py, &long);
+            assert!(s.eq_str(&long));
+            assert!(!s.eq_str(&long[..99]));
+
+            let s = py.eval_bound(r"'\ud800'", None, None).unwrap();
+            let s = s.downcast::<PyString>().unwrap();
+            assert!(!s.eq_str("\u{fffd}"));
+            assert!(!s.eq_str("a"));
+            assert!(PyErr::take(py).is_none());
+        })
+    }
+
+    #[test]
+    fn test_splitlines() {
+        Python::with_gil(|py| {
+            let s = PyString::new_bound(py, "one\ntwo\r\nthree\u{2028}four\n");
+            let lines: Vec<String> = s.splitlines().unwrap().extract().unwrap();
+            assert_eq!(lines, ["one", "two", "three", "four"]);
+
+            let s = PyString::new_bound(py, "");
+            let lines: Vec<String> = s.splitlines().unwrap().extract().unwrap();
+            assert!(lines.is_empty());
+        })
+    }
+
+    #[test]
+    fn test_split() {
+        Python::with_gil(|py| {
+            let s = PyString::new_bound(py, "a,b,,c");
+            let pieces: Vec<String> = s.split(Some(",")).unwrap().extract().unwrap();
+            assert_eq!(pieces, ["a", "b", "", "c"]);
+
+            let s = PyString::new_bound(py, "  a \t b\n");
+            let pieces: Vec<String> = s.split(None).unwrap().extract().unwrap();
+            assert_eq!(pieces, ["a", "b"]);
+
+            let err = s.split(Some("")).unwrap_err();
+            assert!(err.is_instance_of::<crate::exceptions::PyValueError>(py));
+        })
+    }
+
     #[test]
     fn test_intern_string() {
         Python::with_gil(|py| {
@@ -129,6 +129,9 @@ impl PyTuple {
     }
 
     /// Constructs an empty tuple (on the Python side, a singleton object).
+    ///
+    /// On CPython this does not allocate: every call returns the interpreter's cached empty
+    /// tuple, which is the same object as the Python expression `()`.
     pub fn empty_bound(py: Python<'_>) -> Bound<'_, PyTuple> {
         unsafe {
             ffi::PyTuple_New(0)
@@ -874,6 +877,16 @@ mod tests {
         });
     }
 
+    #[test]
+    #[cfg(not(any(PyPy, GraalPy)))]
+    fn test_empty_is_singleton() {
+        Python::with_gil(|py| {
+            let empty = PyTuple::empty_bound(py);
+            assert!(empty.is(&PyTuple::empty_bound(py)));
+            assert!(empty.is(&py.eval_bound("()", None, None).unwrap()));
+        });
+    }
+
     #[test]
     fn test_slice() {
         Python::with_gil(|py| {
@@ -99,6 +99,22 @@ impl PyType {
     {
         self.as_borrowed().is_subclass_of::<T>()
     }
+
+    /// Returns the number of live instances of a `#[pyclass(track_instances)]` type.
+    ///
+    /// Instances of subclasses (including subclasses defined in Python) are counted by the
+    /// `#[pyclass]` they derive from. Returns `None` if this type object is not a
+    /// `#[pyclass(track_instances)]` type itself.
+    pub fn instance_count(&self) -> Option<usize> {
+        self.as_borrowed().instance_count()
+    }
+
+    /// Checks whether `self` or one of its bases defines the attribute `name`.
+    ///
+    /// See [`PyTypeMethods::has_method`].
+    pub fn has_method(&self, name: &str) -> bool {
+        self.as_borrowed().has_method(name)
+    }
 }
 
 /// Implementation of functionality for [`PyType`].
@@ -129,6 +145,24 @@ pub trait PyTypeMethods<'py>: crate::sealed::Sealed {
     fn is_subclass_of<T>(&self) -> PyResult<bool>
     where
         T: PyTypeInfo;
+
+    /// Returns the number of live instances of a `#[pyclass(track_instances)]` type.
+    ///
+    /// Instances of subclasses (including subclasses defined in Python) are counted by the
+    /// `#[pyclass]` they derive from. Returns `None` if this type object is not a
+    /// `#[pyclass(track_instances)]` type itself.
+    fn instance_count(&self) -> Option<usize>;
+
+    /// Checks whether `self` or one of its bases defines the attribute `name`.
+    ///
+    /// Only the `__dict__` of each class in the type's `__mro__` is searched, so unlike
+    /// `hasattr(self, name)` this ignores attributes of the metaclass and never calls a
+    /// `__getattr__`. This makes it suitable to detect protocols, e.g. whether instances of the
+    /// type are iterable because it defines `__iter__`.
+    ///
+    /// A name which is defined but set to `None` (as is conventional to disable a protocol, e.g.
+    /// `__hash__ = None`) is not considered a method.
+    fn has_method(&self, name: &str) -> bool;
 }
 
 impl<'py> PyTypeMethods<'py> for Bound<'py, PyType> {
@@ -179,9 +213,39 @@ impl<'py> PyTypeMethods<'py> for Bound<'py, PyType> {
     {
         self.is_subclass(&T::type_object_bound(self.py()))
     }
+
+    fn instance_count(&self) -> Option<usize> {
+        self.as_borrowed().instance_count()
+    }
+
+    fn has_method(&self, name: &str) -> bool {
+        self.as_borrowed().has_method(name)
+    }
 }
 
 impl<'a> Borrowed<'a, '_, PyType> {
+    fn instance_count(self) -> Option<usize> {
+        crate::impl_::pyclass::instance_counter_of(self.as_type_ptr())
+            .map(|counter| counter.load(std::sync::atomic::Ordering::Relaxed))
+    }
+
+    fn has_method(self, name: &str) -> bool {
+        let py = self.py();
+        let lookup = || -> PyResult<bool> {
+            let mro = self.getattr(intern!(py, "__mro__"))?;
+            for base in mro.iter()? {
+                let dict = base?.getattr(intern!(py, "__dict__"))?;
+                if let Ok(value) = dict.get_item(name) {
+                    return Ok(!value.is_none());
+                }
+            }
+            Ok(false)
+        };
+        // `__mro__` and `__dict__` always exist on type objects, so the lookup only fails in
+        // pathological cases (e.g. a metaclass overriding them), where no method is reported.
+        lookup().unwrap_or(false)
+    }
+
     fn name(self) -> PyResult<Cow<'a, str>> {
         #[cfg(not(any(Py_LIMITED_API, PyPy)))]
         {
@@ -213,12 +277,60 @@ impl<'a> Borrowed<'a, '_, PyType> {
             Ok(Cow::Owned(format!("{}.{}", module, name)))
         }
     }
+
+    /// Equivalent to `self.__name__`, i.e. the name without the module.
+    pub(crate) fn short_name(self) -> PyResult<Cow<'a, str>> {
+        #[cfg(not(any(Py_LIMITED_API, PyPy)))]
+        {
+            let ptr = self.as_type_ptr();
+
+            // Like `type.__name__` in CPython: `__name__` of heap types is kept in `ht_name`,
+            // because the `tp_name` of types created with `PyType_FromSpec`, such as
+            // `#[pyclass]` types, includes the module.
+            if unsafe { ffi::PyType_HasFeature(ptr, ffi::Py_TPFLAGS_HEAPTYPE) } != 0 {
+                use crate::ffi_ptr_ext::FfiPtrExt;
+                use crate::types::PyString;
+                let name = unsafe {
+                    (*ptr.cast::<ffi::PyHeapTypeObject>())
+                        .ht_name
+                        .assume_borrowed(self.py())
+                        .downcast_unchecked::<PyString>()
+                };
+                return Ok(Cow::Owned(name.to_cow()?.into_owned()));
+            }
+
+            // Static types prefix `__name__` with the module name
+            let tp_name = unsafe { CStr::from_ptr((*ptr).tp_name) }.to_str()?;
+            let name = tp_name.rsplit('.').next().unwrap_or(tp_name);
+
+            #[cfg(Py_3_10)]
+            if unsafe { ffi::PyType_HasFeature(ptr, ffi::Py_TPFLAGS_IMMUTABLETYPE) } != 0 {
+                return Ok(Cow::Borrowed(name));
+            }
+
+            Ok(Cow::Owned(name.to_owned()))
+        }
+
+        #[cfg(any(Py_LIMITED_API, PyPy))]
+        {
+            #[cfg(not(Py_3_11))]
+            let name = self.getattr(intern!(self.py(), "__name__"))?;
+
+            #[cfg(Py_3_11)]
+            let name = {
+                use crate::ffi_ptr_ext::FfiPtrExt;
+                unsafe { ffi::PyType_GetName(self.as_type_ptr()).assume_owned_or_err(self.py())? }
+            };
+
+            Ok(Cow::Owned(name.extract()?))
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::types::typeobject::PyTypeMethods;
-    use crate::types::{PyBool, PyLong};
+    use crate::types::{PyBool, PyList, PyLong};
     use crate::Python;
 
     #[test]
@@ -239,4 +351,24 @@ mod tests {
                 .unwrap());
         });
     }
+
+    #[test]
+    fn test_type_has_method() {
+        Python::with_gil(|py| {
+            let bool_type = py.get_type_bound::<PyBool>();
+            // defined by `bool` itself
+            assert!(bool_type.has_method("__and__"));
+            // inherited from `int` and `object`
+            assert!(bool_type.has_method("bit_length"));
+            assert!(bool_type.has_method("__repr__"));
+            assert!(!bool_type.has_method("__iter__"));
+            // `type.mro` is an attribute of the metaclass, not of the type
+            assert!(!bool_type.has_method("mro"));
+
+            let list_type = py.get_type_bound::<PyList>();
+            assert!(list_type.has_method("__iter__"));
+            // `list.__hash__` is `None`
+            assert!(!list_type.has_method("__hash__"));
+        });
+    }
 }
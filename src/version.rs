@@ -72,6 +72,22 @@ impl<'py> PythonVersionInfo<'py> {
     }
 }
 
+/// Converts to a `(major, minor, patch)` tuple, dropping the suffix.
+///
+/// ```rust
+/// # use pyo3::Python;
+/// Python::with_gil(|py| {
+///     let (major, minor, _patch): (u8, u8, u8) = py.version_info().into();
+///     assert_eq!(major, 3);
+///     assert!(minor >= 7);
+/// });
+/// ```
+impl From<PythonVersionInfo<'_>> for (u8, u8, u8) {
+    fn from(version: PythonVersionInfo<'_>) -> Self {
+        (version.major, version.minor, version.patch)
+    }
+}
+
 impl PartialEq<(u8, u8)> for PythonVersionInfo<'_> {
     fn eq(&self, other: &(u8, u8)) -> bool {
         self.major == other.0 && self.minor == other.1
@@ -140,4 +156,16 @@ mod test {
         assert!(PythonVersionInfo::from_str("3.5.2a1+").unwrap() < (3, 6));
         assert!(PythonVersionInfo::from_str("3.5.2a1+").unwrap() > (3, 4));
     }
+
+    #[test]
+    fn test_python_version_info_into_tuple() {
+        let version: (u8, u8, u8) = PythonVersionInfo::from_str("3.12.1rc1").unwrap().into();
+        assert_eq!(version, (3, 12, 1));
+        Python::with_gil(|py| {
+            let (major, minor, patch) = py.version_info().into();
+            assert!(py
+                .version()
+                .starts_with(&format!("{}.{}.{}", major, minor, patch)));
+        });
+    }
 }
@@ -52,6 +52,42 @@ fn unary_arithmetic() {
     });
 }
 
+#[pyclass]
+struct Rounding(f64);
+
+#[pymethods]
+impl Rounding {
+    #[pyo3(signature = (ndigits = None))]
+    fn __round__(&self, ndigits: Option<i32>) -> f64 {
+        let factor = 10f64.powi(ndigits.unwrap_or(0));
+        (self.0 * factor).round() / factor
+    }
+
+    fn __trunc__(&self) -> i64 {
+        self.0.trunc() as i64
+    }
+
+    fn __floor__(&self) -> i64 {
+        self.0.floor() as i64
+    }
+
+    fn __ceil__(&self) -> i64 {
+        self.0.ceil() as i64
+    }
+}
+
+#[test]
+fn rounding() {
+    Python::with_gil(|py| {
+        let r = Py::new(py, Rounding(-2.56)).unwrap();
+        py_run!(py, r, "assert round(r) == -3.0");
+        py_run!(py, r, "assert round(r, 1) == -2.6");
+        py_run!(py, r, "import math; assert math.trunc(r) == -2");
+        py_run!(py, r, "import math; assert math.floor(r) == -3");
+        py_run!(py, r, "import math; assert math.ceil(r) == -2");
+    });
+}
+
 #[pyclass]
 struct Indexable(i32);
 
@@ -5,6 +5,7 @@ use pyo3::buffer::PyBuffer;
 use pyo3::exceptions::PyBufferError;
 use pyo3::ffi;
 use pyo3::prelude::*;
+use pyo3::py_run;
 use pyo3::types::IntoPyDict;
 use std::ffi::CString;
 use std::os::raw::{c_int, c_void};
@@ -136,6 +137,163 @@ fn test_releasebuffer_unraisable_error() {
     });
 }
 
+#[pyclass(buffer)]
+struct Samples {
+    data: Vec<f64>,
+}
+
+impl pyo3::buffer::PyClassBuffer for Samples {
+    type Item = f64;
+    const FORMAT: &'static str = "d";
+
+    fn buffer(&self) -> &[f64] {
+        &self.data
+    }
+
+    fn buffer_mut(&mut self) -> Option<&mut [f64]> {
+        Some(&mut self.data)
+    }
+}
+
+#[test]
+fn test_pyclass_buffer() {
+    Python::with_gil(|py| {
+        let samples = Py::new(
+            py,
+            Samples {
+                data: vec![0.5, 1.5, 2.5],
+            },
+        )
+        .unwrap();
+
+        let buffer = PyBuffer::<f64>::get_bound(samples.bind(py)).unwrap();
+        assert_eq!(buffer.shape(), [3]);
+        assert_eq!(buffer.item_size(), 8);
+        assert_eq!(buffer.to_vec(py).unwrap(), [0.5, 1.5, 2.5]);
+        assert_eq!(
+            buffer.buf_ptr() as *const f64,
+            samples.borrow(py).data.as_ptr()
+        );
+        // the data can't be mutated or reallocated from Rust while it is exported
+        assert!(samples.bind(py).try_borrow_mut().is_err());
+        drop(buffer);
+        samples.bind(py).borrow_mut().data.push(3.5);
+
+        py_run!(
+            py,
+            samples,
+            r#"
+        view = memoryview(samples)
+        assert (view.format, view.itemsize, view.shape) == ("d", 8, (4,))
+        assert view.tolist() == [0.5, 1.5, 2.5, 3.5]
+        assert view.readonly
+        "#
+        );
+
+        // writable buffers are exported to consumers which request them, e.g. `readinto`
+        py_run!(
+            py,
+            samples,
+            r#"
+        import io, struct
+        assert io.BytesIO(struct.pack("2d", 0.5, -2.0)).readinto(samples) == 16
+        "#
+        );
+        assert_eq!(samples.borrow(py).data, [0.5, -2.0, 2.5, 3.5]);
+
+        unsafe {
+            let mut view = std::mem::MaybeUninit::<ffi::Py_buffer>::uninit();
+            let flags = ffi::PyBUF_WRITABLE | ffi::PyBUF_ND;
+            assert_eq!(
+                ffi::PyObject_GetBuffer(samples.as_ptr(), view.as_mut_ptr(), flags),
+                0
+            );
+            let mut view = view.assume_init();
+            assert_eq!(view.readonly, 0);
+            assert_eq!(*view.shape, 4);
+            *view.buf.cast::<f64>() = -1.0;
+            // no other borrow is possible while a writable buffer exists
+            assert!(samples.bind(py).try_borrow().is_err());
+            ffi::PyBuffer_Release(&mut view);
+        }
+        assert_eq!(samples.borrow(py).data, [-1.0, -2.0, 2.5, 3.5]);
+    });
+}
+
+#[pyclass(buffer, frozen)]
+struct FrozenSamples {
+    data: Vec<i32>,
+}
+
+impl pyo3::buffer::PyClassBuffer for FrozenSamples {
+    type Item = i32;
+    const FORMAT: &'static str = "i";
+
+    fn buffer(&self) -> &[i32] {
+        &self.data
+    }
+}
+
+#[test]
+fn test_pyclass_buffer_readonly() {
+    Python::with_gil(|py| {
+        let samples = Py::new(
+            py,
+            FrozenSamples {
+                data: vec![1, 2, 3],
+            },
+        )
+        .unwrap();
+        py_run!(
+            py,
+            samples,
+            r#"
+        view = memoryview(samples)
+        assert view.readonly
+        assert view.tolist() == [1, 2, 3]
+        "#
+        );
+
+        let mut view = std::mem::MaybeUninit::<ffi::Py_buffer>::uninit();
+        let result = unsafe {
+            ffi::PyObject_GetBuffer(samples.as_ptr(), view.as_mut_ptr(), ffi::PyBUF_WRITABLE)
+        };
+        assert_eq!(result, -1);
+        let err = PyErr::fetch(py);
+        assert!(err.is_instance_of::<PyBufferError>(py));
+        assert_eq!(err.value_bound(py).to_string(), "Object is not writable");
+        assert_eq!(samples.get().data, [1, 2, 3]);
+    });
+}
+
+#[pyclass(buffer, frozen)]
+struct MismatchedSamples {
+    data: Vec<f64>,
+}
+
+impl pyo3::buffer::PyClassBuffer for MismatchedSamples {
+    type Item = f64;
+    const FORMAT: &'static str = "i";
+
+    fn buffer(&self) -> &[f64] {
+        &self.data
+    }
+}
+
+#[test]
+fn test_pyclass_buffer_format_mismatch() {
+    Python::with_gil(|py| {
+        let samples = Py::new(py, MismatchedSamples { data: vec![1.0] }).unwrap();
+        py_expect_exception!(
+            py,
+            samples,
+            "memoryview(samples)",
+            PyBufferError,
+            "buffer format \"i\" does not describe items of type `f64`"
+        );
+    });
+}
+
 /// # Safety
 ///
 /// `view` must be a valid pointer to ffi::Py_buffer, or null
@@ -1,6 +1,7 @@
 #![cfg(feature = "macros")]
 
 use pyo3::prelude::*;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
 #[path = "../src/tests/common.rs"]
 mod common;
@@ -96,6 +97,86 @@ fn recursive_class_attributes() {
     });
 }
 
+static LAZY_CALLS: AtomicUsize = AtomicUsize::new(0);
+static LAZY_READ: AtomicBool = AtomicBool::new(false);
+
+#[pyclass]
+struct Lazy {}
+
+#[pymethods]
+impl Lazy {
+    #[new]
+    fn new() -> Self {
+        Lazy {}
+    }
+
+    #[classattr(lazy)]
+    fn table() -> Vec<u32> {
+        assert!(
+            LAZY_READ.load(Ordering::SeqCst),
+            "computed before being read"
+        );
+        LAZY_CALLS.fetch_add(1, Ordering::SeqCst);
+        (0..4).map(|i| i * i).collect()
+    }
+
+    #[classattr]
+    fn eager() -> u32 {
+        1
+    }
+}
+
+#[test]
+fn lazy_class_attribute() {
+    Python::with_gil(|py| {
+        let lazy = py.get_type_bound::<Lazy>();
+        // creating the type object, instances and reading other attributes does not compute it
+        py_assert!(py, lazy, "lazy().eager == lazy.eager == 1");
+        py_assert!(py, lazy, "'table' in lazy.__dict__");
+        assert_eq!(LAZY_CALLS.load(Ordering::SeqCst), 0);
+
+        LAZY_READ.store(true, Ordering::SeqCst);
+        py_assert!(py, lazy, "lazy.table == [0, 1, 4, 9]");
+        assert_eq!(LAZY_CALLS.load(Ordering::SeqCst), 1);
+
+        py_assert!(py, lazy, "lazy.table is lazy.table");
+        py_assert!(py, lazy, "lazy().table is lazy.table");
+        assert_eq!(LAZY_CALLS.load(Ordering::SeqCst), 1);
+    });
+}
+
+static SLOW_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+#[pyclass]
+struct SlowLazy {}
+
+#[pymethods]
+impl SlowLazy {
+    #[classattr(lazy)]
+    fn value(py: Python<'_>) -> usize {
+        let calls = SLOW_CALLS.fetch_add(1, Ordering::SeqCst) + 1;
+        // let the other thread try to read the attribute meanwhile
+        py.allow_threads(|| std::thread::sleep(std::time::Duration::from_millis(100)));
+        calls
+    }
+}
+
+#[test]
+#[cfg(not(target_arch = "wasm32"))]
+fn lazy_class_attribute_computed_once_across_threads() {
+    let read = || {
+        Python::with_gil(|py| {
+            let slow = py.get_type_bound::<SlowLazy>();
+            slow.getattr("value").unwrap().extract::<usize>().unwrap()
+        })
+    };
+    let threads: Vec<_> = (0..2).map(|_| std::thread::spawn(read)).collect();
+    for thread in threads {
+        assert_eq!(thread.join().unwrap(), 1);
+    }
+    assert_eq!(SLOW_CALLS.load(Ordering::SeqCst), 1);
+}
+
 #[test]
 fn test_fallible_class_attribute() {
     use pyo3::{exceptions::PyValueError, types::PyString};
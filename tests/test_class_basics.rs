@@ -1,7 +1,8 @@
 #![cfg(feature = "macros")]
 
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use pyo3::types::PyType;
+use pyo3::types::{IntoPyDict, PyType};
 use pyo3::{py_run, PyClass};
 
 #[path = "../src/tests/common.rs"]
@@ -501,6 +502,92 @@ fn weakref_support() {
     });
 }
 
+#[pyclass(weakref, subclass)]
+struct WeakRefTarget {}
+
+#[pymethods]
+impl WeakRefTarget {
+    #[new]
+    fn new() -> Self {
+        WeakRefTarget {}
+    }
+}
+
+#[test]
+#[cfg_attr(all(Py_LIMITED_API, not(Py_3_9)), ignore)]
+fn weakref_callbacks_and_value_dictionary() {
+    Python::with_gil(|py| {
+        let cls = py.get_type_bound::<WeakRefTarget>();
+        py_run!(
+            py,
+            cls,
+            r#"
+import gc, weakref
+
+inst = cls()
+assert inst.__weakref__ is None
+called = []
+r = weakref.ref(inst, called.append)
+assert inst.__weakref__ is r
+
+d = weakref.WeakValueDictionary()
+d["a"] = inst
+assert d["a"] is inst
+del inst
+gc.collect()
+assert "a" not in d
+assert called == [r]
+
+class Sub(cls):
+    pass
+
+sub = Sub()
+d["b"] = sub
+assert weakref.ref(sub)() is sub
+del sub
+gc.collect()
+assert "b" not in d
+"#
+        );
+    });
+}
+
+#[pyclass(weakref, dict)]
+struct WeakRefDictTarget {}
+
+#[pymethods]
+impl WeakRefDictTarget {
+    #[new]
+    fn new() -> Self {
+        WeakRefDictTarget {}
+    }
+}
+
+#[test]
+#[cfg_attr(all(Py_LIMITED_API, not(Py_3_9)), ignore)]
+fn weakref_callback_with_dunder_dict() {
+    Python::with_gil(|py| {
+        let cls = py.get_type_bound::<WeakRefDictTarget>();
+        py_run!(
+            py,
+            cls,
+            r#"
+import gc, weakref
+
+called = []
+inst = cls()
+inst.payload = [1, 2, 3]
+r = weakref.ref(inst, called.append)
+assert inst.__weakref__ is r
+del inst
+gc.collect()
+assert r() is None
+assert called == [r]
+"#
+        );
+    });
+}
+
 // If the base class has weakref support, child class also has weakref.
 #[pyclass(extends=WeakRefSupport)]
 struct InheritWeakRef {
@@ -607,3 +694,779 @@ fn drop_unsendable_elsewhere() {
         capture.borrow_mut(py).uninstall(py);
     });
 }
+
+#[pyclass(subclass, track_instances)]
+struct Tracked {}
+
+#[pymethods]
+impl Tracked {
+    #[new]
+    fn new() -> Self {
+        Tracked {}
+    }
+}
+
+#[test]
+fn track_instances() {
+    Python::with_gil(|py| {
+        let ty = py.get_type_bound::<Tracked>();
+        assert_eq!(ty.instance_count(), Some(0));
+
+        let first = Py::new(py, Tracked {}).unwrap();
+        let second = ty.call0().unwrap();
+        assert_eq!(ty.instance_count(), Some(2));
+
+        // instances of Python subclasses are counted by the base `#[pyclass]`
+        let locals = [("ty", &ty)].into_py_dict_bound(py);
+        py.run_bound(
+            "class SubTracked(ty): pass\nsub = SubTracked()",
+            None,
+            Some(&locals),
+        )
+        .unwrap();
+        assert_eq!(ty.instance_count(), Some(3));
+        let sub_type = locals.get_item("SubTracked").unwrap().unwrap();
+        assert_eq!(
+            sub_type.downcast::<PyType>().unwrap().instance_count(),
+            None
+        );
+        locals.del_item("sub").unwrap();
+        assert_eq!(ty.instance_count(), Some(2));
+
+        drop(first);
+        drop(second);
+        assert_eq!(ty.instance_count(), Some(0));
+
+        assert_eq!(py.get_type_bound::<EmptyClass>().instance_count(), None);
+    });
+}
+
+#[pyclass(clone)]
+#[derive(Clone)]
+struct Cloneable {
+    #[pyo3(get, set)]
+    values: Vec<i32>,
+}
+
+#[test]
+fn copy_uses_clone() {
+    Python::with_gil(|py| {
+        let original = Py::new(py, Cloneable { values: vec![1, 2] }).unwrap();
+        py_run!(
+            py,
+            original,
+            r#"
+            import copy
+
+            copied = copy.copy(original)
+            assert type(copied) is type(original)
+            assert copied is not original
+            assert copied.values == [1, 2]
+
+            copied.values = [3]
+            assert original.values == [1, 2]
+            assert copied.values == [3]
+
+            cloned = original.clone()
+            assert type(cloned) is type(original)
+            assert cloned is not original
+            cloned.values = [4]
+            assert original.values == [1, 2]
+            assert cloned.values == [4]
+            "#
+        );
+    });
+}
+
+#[pyclass(frozen, replace, subclass)]
+struct Release {
+    #[pyo3(get)]
+    major: u32,
+    #[pyo3(get)]
+    minor: u32,
+    #[pyo3(get, name = "tag")]
+    pre_release: Option<String>,
+    build: u32,
+}
+
+#[pymethods]
+impl Release {
+    #[new]
+    #[pyo3(signature = (major, minor = 0, tag = None))]
+    fn new(major: u32, minor: u32, tag: Option<String>) -> PyResult<Self> {
+        if tag.as_deref() == Some("") {
+            return Err(PyValueError::new_err("empty tag"));
+        }
+        Ok(Self {
+            major,
+            minor,
+            pre_release: tag,
+            build: 0,
+        })
+    }
+
+    fn build_number(&self) -> u32 {
+        self.build
+    }
+}
+
+#[test]
+fn replace_overrides_given_fields() {
+    Python::with_gil(|py| {
+        let release = Py::new(
+            py,
+            Release {
+                major: 1,
+                minor: 2,
+                pre_release: Some("rc1".to_owned()),
+                build: 7,
+            },
+        )
+        .unwrap();
+        py_run!(
+            py,
+            release,
+            r#"
+            replaced = release.__replace__(minor=3, tag=None)
+            assert type(replaced) is type(release)
+            assert (replaced.major, replaced.minor, replaced.tag) == (1, 3, None)
+            assert (release.major, release.minor, release.tag) == (1, 2, "rc1")
+            assert (release.build_number(), replaced.build_number()) == (7, 0)
+
+            unchanged = release.__replace__()
+            assert unchanged is not release
+            assert (unchanged.major, unchanged.minor, unchanged.tag) == (1, 2, "rc1")
+            "#
+        );
+        #[cfg(Py_3_13)]
+        py_run!(
+            py,
+            release,
+            r#"
+            import copy
+
+            assert copy.replace(release, major=2).major == 2
+            "#
+        );
+        // `build` has no getter, so it cannot be replaced
+        py_expect_exception!(
+            py,
+            release,
+            "release.__replace__(build=3)",
+            PyTypeError,
+            "Release.__replace__() got an unexpected keyword argument 'build'"
+        );
+        py_expect_exception!(
+            py,
+            release,
+            "release.__replace__(pre_release='rc2')",
+            PyTypeError,
+            "Release.__replace__() got an unexpected keyword argument 'pre_release'"
+        );
+        py_expect_exception!(py, release, "release.__replace__(major='x')", PyTypeError);
+        // the constructor validates the changes
+        py_expect_exception!(py, release, "release.__replace__(tag='')", PyValueError);
+    });
+}
+
+#[test]
+fn replace_preserves_subclasses() {
+    Python::with_gil(|py| {
+        let base = py.get_type_bound::<Release>();
+        py_run!(
+            py,
+            base,
+            r#"
+            class Candidate(base):
+                pass
+
+            replaced = Candidate(1, tag="rc1").__replace__(minor=1)
+            assert type(replaced) is Candidate
+            assert (replaced.major, replaced.minor, replaced.tag) == (1, 1, "rc1")
+            "#
+        );
+    });
+}
+
+#[pyclass(replace)]
+struct Limits {
+    #[pyo3(get, set = validate_low)]
+    low: i32,
+    #[pyo3(get)]
+    high: i32,
+}
+
+#[pymethods]
+impl Limits {
+    #[new]
+    fn new(low: i32, high: i32) -> Self {
+        Self { low, high }
+    }
+
+    fn validate_low(&self, low: i32) -> PyResult<i32> {
+        if low > self.high {
+            return Err(PyValueError::new_err("low is above high"));
+        }
+        Ok(low)
+    }
+}
+
+#[test]
+fn replace_assigns_settable_fields_through_setters() {
+    Python::with_gil(|py| {
+        let limits = Py::new(py, Limits { low: 1, high: 5 }).unwrap();
+        py_run!(
+            py,
+            limits,
+            r#"
+            replaced = limits.__replace__(low=3, high=4)
+            assert (replaced.low, replaced.high) == (3, 4)
+            "#
+        );
+        py_expect_exception!(py, limits, "limits.__replace__(low=6)", PyValueError);
+    });
+}
+
+#[pyclass(frozen, getnewargs)]
+struct Interval {
+    #[pyo3(get)]
+    start: i64,
+    #[pyo3(get)]
+    end: i64,
+    #[pyo3(get)]
+    closed: bool,
+}
+
+#[pymethods]
+impl Interval {
+    #[new]
+    #[pyo3(signature = (start, end, closed = false))]
+    fn new(start: i64, end: i64, closed: bool) -> Self {
+        Self { start, end, closed }
+    }
+}
+
+#[test]
+fn getnewargs_returns_positional_constructor_arguments() {
+    Python::with_gil(|py| {
+        let interval = Py::new(py, Interval::new(1, 5, true)).unwrap();
+        py_run!(
+            py,
+            interval,
+            r#"
+            import copy
+
+            assert interval.__getnewargs__() == (1, 5, True)
+
+            copied = copy.deepcopy(interval)
+            assert type(copied) is type(interval)
+            assert copied is not interval
+            assert (copied.start, copied.end, copied.closed) == (1, 5, True)
+            "#
+        );
+    });
+}
+
+#[pyclass(subscriptable)]
+struct Registry {}
+
+#[test]
+fn subscriptable_class_supports_generic_aliases() {
+    Python::with_gil(|py| {
+        let registry = py.get_type_bound::<Registry>();
+        py_run!(
+            py,
+            registry,
+            r#"
+            registry[int]
+            registry[int, str]
+
+            def lookup(entries: registry[str, int]) -> registry[int]:
+                pass
+
+            import sys
+            if sys.version_info >= (3, 9):
+                import typing
+                assert typing.get_origin(registry[int, str]) is registry
+                assert typing.get_args(registry[int, str]) == (int, str)
+                assert typing.get_args(registry[int]) == (int,)
+            "#
+        );
+    });
+}
+
+#[pyclass(context_manager)]
+struct Resource {
+    #[pyo3(get)]
+    closed: bool,
+    #[pyo3(get)]
+    exit_error: Option<String>,
+}
+
+impl pyo3::pyclass::PyContextManager for Resource {
+    fn exit(
+        slf: &Bound<'_, Self>,
+        exception: Option<&Bound<'_, pyo3::exceptions::PyBaseException>>,
+    ) -> PyResult<bool> {
+        let mut resource = slf.borrow_mut();
+        resource.closed = true;
+        resource.exit_error = exception.map(|exception| exception.to_string());
+        // swallow `KeyError`s only
+        Ok(exception.map_or(false, |exception| {
+            exception.is_instance_of::<pyo3::exceptions::PyKeyError>()
+        }))
+    }
+}
+
+#[test]
+fn context_manager_calls_exit() {
+    Python::with_gil(|py| {
+        let resource = || {
+            Py::new(
+                py,
+                Resource {
+                    closed: false,
+                    exit_error: None,
+                },
+            )
+            .unwrap()
+        };
+
+        let ok = resource();
+        py_run!(
+            py,
+            ok,
+            r#"
+            with ok as entered:
+                assert entered is ok
+                assert not ok.closed
+            assert ok.closed
+            assert ok.exit_error is None
+            "#
+        );
+
+        let suppressed = resource();
+        py_run!(
+            py,
+            suppressed,
+            r#"
+            with suppressed:
+                raise KeyError("missing")
+            assert suppressed.closed
+            assert suppressed.exit_error == "'missing'"
+            "#
+        );
+
+        let raised = resource();
+        py_expect_exception!(
+            py,
+            raised,
+            "with raised: raise ValueError('failed')",
+            PyValueError,
+            "failed"
+        );
+        let raised = raised.borrow(py);
+        assert!(raised.closed);
+        assert_eq!(raised.exit_error.as_deref(), Some("failed"));
+    });
+}
+
+#[pyclass(frozen)]
+struct Point {
+    #[pyo3(get, eq_hash)]
+    x: i32,
+    #[pyo3(get, eq_hash)]
+    y: i32,
+    #[pyo3(get)]
+    label: String,
+}
+
+#[pyclass(frozen)]
+struct Id(#[pyo3(eq_hash)] u64, #[pyo3(get, name = "name")] String);
+
+#[test]
+fn eq_hash_fields() {
+    Python::with_gil(|py| {
+        let point = |x, y, label: &str| {
+            Py::new(
+                py,
+                Point {
+                    x,
+                    y,
+                    label: label.to_owned(),
+                },
+            )
+            .unwrap()
+        };
+        let a = point(1, 2, "a");
+        let b = point(1, 2, "b");
+        let c = point(2, 1, "c");
+        py_run!(
+            py,
+            a b c,
+            r#"
+            assert a == b
+            assert not a != b
+            assert a != c
+            assert hash(a) == hash(b)
+            assert a != (1, 2)
+            assert len({a, b, c}) == 2
+            assert {p.label for p in {a, c}} == {"a", "c"}
+            "#
+        );
+
+        let first = Py::new(py, Id(7, "first".to_owned())).unwrap();
+        let second = Py::new(py, Id(7, "second".to_owned())).unwrap();
+        py_run!(py, first second, "assert first == second and len({first, second}) == 1");
+    });
+}
+
+#[pyclass(str = "({x}, {y:.1})")]
+struct Coordinate {
+    x: i32,
+    y: f64,
+}
+
+#[pyclass(str = "{label:$>width$}|{value:.precision$}")]
+struct Padded {
+    label: String,
+    width: usize,
+    value: f64,
+    precision: usize,
+}
+
+#[pyclass(str)]
+struct Celsius(f64);
+
+impl std::fmt::Display for Celsius {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}°C", self.0)
+    }
+}
+
+#[pyclass(str)]
+#[derive(Debug)]
+enum Direction {
+    North,
+    South,
+}
+
+impl std::fmt::Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self, f)
+    }
+}
+
+#[test]
+fn str_differs_from_repr() {
+    Python::with_gil(|py| {
+        let coordinate = Py::new(py, Coordinate { x: 1, y: 2.25 }).unwrap();
+        let celsius = Py::new(py, Celsius(21.5)).unwrap();
+        let direction = Py::new(py, Direction::South).unwrap();
+        let north = Py::new(py, Direction::North).unwrap();
+        let padded = Py::new(
+            py,
+            Padded {
+                label: "ab".to_owned(),
+                width: 4,
+                value: 0.125,
+                precision: 2,
+            },
+        )
+        .unwrap();
+        py_run!(
+            py,
+            coordinate celsius direction north padded,
+            r#"
+            assert str(coordinate) == "(1, 2.2)"
+            assert repr(coordinate).startswith("<builtins.Coordinate object at")
+            assert f"{coordinate}" == "(1, 2.2)"
+            assert str(padded) == "$$ab|0.12"
+
+            assert str(celsius) == "21.5°C"
+            assert repr(celsius) != str(celsius)
+
+            assert str(direction) == "South"
+            assert repr(direction) == "Direction.South"
+            assert str(north) == "North"
+            "#
+        );
+    });
+}
+
+#[pyclass(repr, rename_all = "camelCase")]
+struct Config {
+    #[pyo3(get)]
+    name: String,
+    #[pyo3(get, name = "size")]
+    max_size: Option<u32>,
+    #[pyo3(repr)]
+    verbose: bool,
+    #[pyo3(get, set)]
+    next: Option<Py<Config>>,
+    #[allow(dead_code)]
+    secret: String,
+}
+
+#[pyclass(repr)]
+struct Pair(#[pyo3(get, name = "first")] i32, #[pyo3(repr)] String);
+
+#[test]
+fn repr_from_fields() {
+    Python::with_gil(|py| {
+        let config = Py::new(
+            py,
+            Config {
+                name: "main".to_owned(),
+                max_size: None,
+                verbose: true,
+                next: None,
+                secret: "hunter2".to_owned(),
+            },
+        )
+        .unwrap();
+        let pair = Py::new(py, Pair(1, "one".to_owned())).unwrap();
+        py_run!(
+            py,
+            config pair,
+            r#"
+            assert repr(config) == "Config(name='main', size=None, verbose=True, next=None)"
+            assert str(config) == repr(config)
+            assert repr(pair) == "Pair(first=1, 'one')"
+            "#
+        );
+    });
+}
+
+#[test]
+fn repr_from_fields_recursive() {
+    Python::with_gil(|py| {
+        let config = Py::new(
+            py,
+            Config {
+                name: "loop".to_owned(),
+                max_size: Some(3),
+                verbose: false,
+                next: None,
+                secret: String::new(),
+            },
+        )
+        .unwrap();
+        py_run!(
+            py,
+            config,
+            r#"
+            config.next = config
+            assert repr(config) == "Config(name='loop', size=3, verbose=False, next=Config(...))"
+            "#
+        );
+    });
+}
+
+#[pyclass(repr)]
+struct Token {
+    #[pyo3(repr)]
+    value: String,
+}
+
+#[test]
+fn repr_from_fields_while_mutably_borrowed() {
+    Python::with_gil(|py| {
+        let token = Bound::new(
+            py,
+            Token {
+                value: "abc".to_owned(),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            token.repr().unwrap().to_cow().unwrap(),
+            "Token(value='abc')"
+        );
+
+        let guard = token.borrow_mut();
+        let err = token.repr().unwrap_err();
+        assert!(err.is_instance_of::<pyo3::exceptions::PyRuntimeError>(py));
+        assert_eq!(err.to_string(), "RuntimeError: Already mutably borrowed");
+        drop(guard);
+    });
+}
+
+#[pyclass(match_args)]
+struct Segment {
+    #[pyo3(get)]
+    start: i32,
+    #[pyo3(get)]
+    end: i32,
+    #[pyo3(get)]
+    label: String,
+}
+
+#[pymethods]
+impl Segment {
+    #[new]
+    #[pyo3(signature = (start, /, end, *, label = String::new()))]
+    fn new(_py: Python<'_>, start: i32, end: i32, label: String) -> Self {
+        Segment { start, end, label }
+    }
+}
+
+#[pyclass(match_args)]
+struct NoConstructor {}
+
+#[test]
+fn match_args_from_new_signature() {
+    Python::with_gil(|py| {
+        let segment = py.get_type_bound::<Segment>();
+        let no_constructor = py.get_type_bound::<NoConstructor>();
+        py_assert!(py, segment, "segment.__match_args__ == ('start', 'end')");
+        py_assert!(py, no_constructor, "no_constructor.__match_args__ == ()");
+    });
+}
+
+#[test]
+#[cfg(Py_3_10)]
+fn match_args_pattern_matching() {
+    Python::with_gil(|py| {
+        let segment = py.get_type_bound::<Segment>();
+        py_run!(
+            py,
+            segment,
+            r#"
+            match segment(1, 5, label="a"):
+                case segment(start, end, label=label):
+                    assert (start, end, label) == (1, 5, "a")
+                case _:
+                    assert False
+            "#
+        );
+    });
+}
+
+#[pyclass(ord, get_all)]
+#[derive(PartialEq, PartialOrd)]
+struct Version {
+    major: u32,
+    minor: u32,
+}
+
+#[pyclass(eq)]
+#[derive(PartialEq)]
+struct Color(#[pyo3(get, name = "red")] u8, u8, u8);
+
+#[test]
+fn richcmp_from_rust_traits() {
+    Python::with_gil(|py| {
+        let version = |major, minor| Py::new(py, Version { major, minor }).unwrap();
+        let versions = vec![version(1, 2), version(0, 9), version(1, 0)];
+        let (v1_0, v1_0_again) = (version(1, 0), version(1, 0));
+        let red = Py::new(py, Color(255, 0, 0)).unwrap();
+        let red_again = Py::new(py, Color(255, 0, 0)).unwrap();
+        let blue = Py::new(py, Color(0, 0, 255)).unwrap();
+        py_run!(
+            py,
+            versions v1_0 v1_0_again red red_again blue,
+            r#"
+            assert [(v.major, v.minor) for v in sorted(versions)] == [(0, 9), (1, 0), (1, 2)]
+            assert v1_0 == v1_0_again and not v1_0 != v1_0_again
+            assert v1_0 <= v1_0_again and v1_0 >= v1_0_again
+            assert versions[1] < v1_0 < versions[0]
+            assert max(versions) is versions[0]
+
+            assert red == red_again and red != blue
+
+            # comparisons with other types are not implemented
+            assert v1_0.__lt__(1) is NotImplemented
+            assert v1_0 != 1 and not (red == v1_0)
+            try:
+                v1_0 < 1
+            except TypeError:
+                pass
+            else:
+                assert False
+            try:
+                red < blue
+            except TypeError:
+                pass
+            else:
+                assert False
+
+            # like in Python, defining equality without `__hash__` makes instances unhashable
+            assert type(red).__hash__ is None
+            "#
+        );
+    });
+}
+
+#[pyclass(ord, compare_with = f64)]
+#[derive(PartialEq, PartialOrd)]
+struct Meters(f64);
+
+impl PartialEq<f64> for Meters {
+    fn eq(&self, other: &f64) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialOrd<f64> for Meters {
+    fn partial_cmp(&self, other: &f64) -> Option<std::cmp::Ordering> {
+        self.0.partial_cmp(other)
+    }
+}
+
+#[test]
+fn richcmp_compare_with_converted_operand() {
+    Python::with_gil(|py| {
+        let five = Py::new(py, Meters(5.0)).unwrap();
+        let six = Py::new(py, Meters(6.0)).unwrap();
+        py_run!(
+            py,
+            five six,
+            r#"
+            assert five == 5.0 and five == 5 and 5.0 == five
+            assert five != 6.0 and not (five != 5.0)
+            assert five < 6.0 and five <= 5 and 4.5 < five and five > 4
+            assert five < six and five != six
+
+            # operands which cannot be extracted as `f64` are still not implemented
+            assert five.__eq__("5") is NotImplemented
+            assert five != "5"
+            try:
+                five < "5"
+            except TypeError:
+                pass
+            else:
+                assert False
+            "#
+        );
+    });
+}
+
+#[pyclass(frozen, eq, hash)]
+#[derive(PartialEq, Eq, Hash)]
+struct GridCell {
+    #[pyo3(get)]
+    x: i64,
+    #[pyo3(get)]
+    y: i64,
+}
+
+#[test]
+fn hash_from_rust_trait() {
+    Python::with_gil(|py| {
+        let cell = |x, y| Py::new(py, GridCell { x, y }).unwrap();
+        let (a, a_again, b) = (cell(1, 2), cell(1, 2), cell(2, 1));
+        py_run!(
+            py,
+            a a_again b,
+            r#"
+            assert hash(a) == hash(a_again)
+            assert len({a, a_again, b}) == 2
+
+            lookup = {a: "a", b: "b"}
+            assert lookup[a_again] == "a"
+            assert lookup[b] == "b"
+            "#
+        );
+    });
+}
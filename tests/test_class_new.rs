@@ -258,3 +258,59 @@ fn test_new_existing() {
         assert!(!obj5.is(&obj6));
     });
 }
+
+static FIRST_INIT_CALLS: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+fn register_first_init(ty: &Bound<'_, pyo3::types::PyType>) -> PyResult<()> {
+    assert_eq!(ty.qualname()?, "FirstInit");
+    FIRST_INIT_CALLS.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    Ok(())
+}
+
+#[pyclass(on_first_init = register_first_init)]
+struct FirstInit {}
+
+#[pymethods]
+impl FirstInit {
+    #[new]
+    fn new() -> Self {
+        FirstInit {}
+    }
+}
+
+#[test]
+fn test_on_first_init() {
+    Python::with_gil(|py| {
+        assert_eq!(
+            FIRST_INIT_CALLS.load(std::sync::atomic::Ordering::SeqCst),
+            0
+        );
+
+        let typeobj = py.get_type_bound::<FirstInit>();
+        typeobj.call0().unwrap();
+        typeobj.call0().unwrap();
+        Py::new(py, FirstInit {}).unwrap();
+
+        assert_eq!(
+            FIRST_INIT_CALLS.load(std::sync::atomic::Ordering::SeqCst),
+            1
+        );
+    });
+}
+
+fn failing_first_init(_ty: &Bound<'_, pyo3::types::PyType>) -> PyResult<()> {
+    Err(PyValueError::new_err("registration failed"))
+}
+
+#[pyclass]
+#[pyo3(on_first_init = failing_first_init)]
+struct FailingFirstInit {}
+
+#[test]
+fn test_on_first_init_error() {
+    Python::with_gil(|py| {
+        let err = Py::new(py, FailingFirstInit {}).unwrap_err();
+        assert!(err.is_instance_of::<PyValueError>(py));
+        assert_eq!(err.to_string(), "ValueError: registration failed");
+    });
+}
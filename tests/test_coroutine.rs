@@ -308,3 +308,54 @@ fn test_async_method_receiver() {
 
     assert!(IS_DROPPED.load(Ordering::SeqCst));
 }
+
+#[test]
+fn test_coroutine_runner() {
+    thread_local! {
+        static IN_RUNNER: std::cell::Cell<bool> = std::cell::Cell::new(false);
+    }
+
+    fn runner(poll: &mut dyn FnMut()) {
+        IN_RUNNER.with(|in_runner| in_runner.set(true));
+        poll();
+        IN_RUNNER.with(|in_runner| in_runner.set(false));
+    }
+
+    #[pyclass]
+    struct Client;
+    #[pymethods]
+    impl Client {
+        async fn fetch(&self, url: String) -> PyResult<String> {
+            sleep(0.01).await;
+            if IN_RUNNER.with(|in_runner| in_runner.get()) {
+                Ok(url)
+            } else {
+                Err(pyo3::exceptions::PyRuntimeError::new_err(
+                    "not polled by runner",
+                ))
+            }
+        }
+    }
+
+    // restore the previous runner even if the test fails, for the other tests
+    struct RestoreRunner(Option<pyo3::coroutine::Runner>);
+    impl Drop for RestoreRunner {
+        fn drop(&mut self) {
+            pyo3::coroutine::set_runner(self.0);
+        }
+    }
+
+    let previous = pyo3::coroutine::set_runner(Some(runner));
+    assert!(previous.is_none());
+    let _restore = RestoreRunner(previous);
+    Python::with_gil(|gil| {
+        let client = Py::new(gil, Client).unwrap();
+        let test = r#"
+        import asyncio
+        async def main(client):
+            return await client.fetch("https://example.com")
+        assert asyncio.run(main(client)) == "https://example.com"
+        "#;
+        py_run!(gil, client, &handle_windows(test));
+    });
+}
@@ -52,6 +52,55 @@ fn test_enum_arg() {
     })
 }
 
+#[pyclass]
+#[derive(Clone)]
+enum Marker {
+    Dot { size: u32 },
+    Tagged(MyEnum),
+}
+
+#[pyclass]
+struct EnumFields {
+    #[pyo3(get, set)]
+    simple: MyEnum,
+    #[pyo3(get)]
+    optional: Option<MyEnum>,
+    #[pyo3(get)]
+    many: Vec<MyEnum>,
+    #[pyo3(get)]
+    complex: Marker,
+}
+
+#[test]
+fn test_enum_field_getters() {
+    Python::with_gil(|py| {
+        let fields = Py::new(
+            py,
+            EnumFields {
+                simple: MyEnum::Variant,
+                optional: Some(MyEnum::OtherVariant),
+                many: vec![MyEnum::OtherVariant, MyEnum::Variant],
+                complex: Marker::Tagged(MyEnum::OtherVariant),
+            },
+        )
+        .unwrap();
+        let mynum = py.get_type_bound::<MyEnum>();
+        let marker = py.get_type_bound::<Marker>();
+        py_run!(py, fields mynum marker, r#"
+        assert type(fields.simple) is mynum
+        assert fields.simple == mynum.Variant
+        assert fields.optional == mynum.OtherVariant
+        assert fields.many == [mynum.OtherVariant, mynum.Variant]
+        assert isinstance(fields.complex, marker.Tagged)
+        assert fields.complex._0 == mynum.OtherVariant
+
+        fields.simple = mynum.OtherVariant
+        assert fields.simple == mynum.OtherVariant
+        "#);
+        assert_eq!(fields.borrow(py).simple, MyEnum::OtherVariant);
+    })
+}
+
 #[test]
 fn test_enum_eq_enum() {
     Python::with_gil(|py| {
@@ -214,3 +263,93 @@ fn test_renaming_all_enum_variants() {
         );
     });
 }
+
+#[pyclass]
+enum TupleVariantEnum {
+    Point(i32, i32),
+    Label(String),
+}
+
+#[test]
+fn test_tuple_variant_payload() {
+    Python::with_gil(|py| {
+        let cls = py.get_type_bound::<TupleVariantEnum>();
+        let point = TupleVariantEnum::Point(1, 2).into_py(py);
+        py_run!(py, cls point, r#"
+        assert isinstance(point, cls)
+        assert isinstance(point, cls.Point)
+        assert not isinstance(point, cls.Label)
+        assert (point._0, point._1) == (1, 2)
+
+        label = cls.Label("hello")
+        assert isinstance(label, cls.Label)
+        assert label._0 == "hello"
+
+        # variants which are not renamed keep their class name
+        assert cls.Point.__name__ == "TupleVariantEnum_Point"
+        assert cls.Point.__module__ == "builtins"
+        "#);
+
+        let label = cls.getattr("Label").unwrap().call1(("world",)).unwrap();
+        match &*label.extract::<PyRef<'_, TupleVariantEnum>>().unwrap() {
+            TupleVariantEnum::Label(s) => assert_eq!(s, "world"),
+            TupleVariantEnum::Point(..) => panic!("expected a `Label` variant"),
+        }
+    })
+}
+
+#[pyclass(str)]
+enum Shape {
+    Circle { radius: f64 },
+    Square(f64),
+}
+
+impl std::fmt::Display for Shape {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Shape::Circle { radius } => write!(f, "circle of radius {}", radius),
+            Shape::Square(side) => write!(f, "square of side {}", side),
+        }
+    }
+}
+
+#[test]
+fn test_complex_enum_str() {
+    Python::with_gil(|py| {
+        let circle = Shape::Circle { radius: 1.5 }.into_py(py);
+        let square = Shape::Square(2.0).into_py(py);
+        py_run!(py, circle square, r#"
+        assert str(circle) == "circle of radius 1.5"
+        assert str(square) == "square of side 2"
+        "#);
+    })
+}
+
+#[pyclass(rename_all = "SCREAMING_SNAKE_CASE", module = "legacy")]
+enum RenamedComplexEnum {
+    #[pyo3(name = "LEGACY_NAME")]
+    Current {
+        value: i32,
+    },
+    OtherVariant(String),
+}
+
+#[test]
+fn test_rename_complex_enum_variants() {
+    Python::with_gil(|py| {
+        let cls = py.get_type_bound::<RenamedComplexEnum>();
+        let current = RenamedComplexEnum::Current { value: 1 }.into_py(py);
+        let other = RenamedComplexEnum::OtherVariant("x".into()).into_py(py);
+        py_run!(py, cls current other, r#"
+        assert not hasattr(cls, "Current")
+        assert isinstance(current, cls.LEGACY_NAME)
+        assert current.value == 1
+        assert isinstance(other, cls.OTHER_VARIANT)
+        assert cls.LEGACY_NAME(value=2).value == 2
+
+        assert cls.LEGACY_NAME.__name__ == "LEGACY_NAME"
+        assert cls.LEGACY_NAME.__module__ == "legacy"
+        assert repr(other).startswith("<legacy.OTHER_VARIANT object at ")
+        "#);
+    })
+}
@@ -7,6 +7,7 @@ use std::fmt;
 #[cfg(not(target_os = "windows"))]
 use std::fs::File;
 
+#[macro_use]
 #[path = "../src/tests/common.rs"]
 mod common;
 
@@ -83,6 +84,76 @@ fn test_custom_error() {
     });
 }
 
+#[pyfunction]
+fn fail_with_cause(py: Python<'_>, cause: &Bound<'_, PyAny>) -> PyResult<()> {
+    let cause = PyErr::from_value_bound(cause.clone());
+    Err(exceptions::PyRuntimeError::new_err("wrapped").with_cause(py, cause))
+}
+
+#[test]
+fn test_error_with_cause() {
+    Python::with_gil(|py| {
+        let fail_with_cause = wrap_pyfunction_bound!(fail_with_cause)(py).unwrap();
+
+        py_run!(
+            py,
+            fail_with_cause,
+            r#"
+        cause = KeyError("missing")
+        try:
+            fail_with_cause(cause)
+        except RuntimeError as e:
+            assert e.__cause__ is cause
+        else:
+            assert False, "expected a RuntimeError"
+        "#
+        );
+    });
+}
+
+/// An error type without `From<ForeignError> for PyErr`, like one from another crate.
+#[derive(Debug)]
+struct ForeignError(u32);
+
+impl fmt::Display for ForeignError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "foreign error {}", self.0)
+    }
+}
+
+#[derive(Debug)]
+struct UnmappedError;
+
+impl fmt::Display for UnmappedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "nobody mapped me")
+    }
+}
+
+#[pyfunction]
+fn fail_with_foreign_error(code: u32) -> Result<(), ForeignError> {
+    Err(ForeignError(code))
+}
+
+#[pyfunction]
+fn fail_with_unmapped_error() -> Result<(), UnmappedError> {
+    Err(UnmappedError)
+}
+
+#[test]
+fn test_registered_error_mapper() {
+    exceptions::register_mapper(|e: ForeignError| {
+        exceptions::PyKeyError::new_err(format!("mapped {}", e))
+    });
+    Python::with_gil(|py| {
+        let f = wrap_pyfunction_bound!(fail_with_foreign_error)(py).unwrap();
+        py_expect_exception!(py, f, "f(3)", PyKeyError, "'mapped foreign error 3'");
+
+        let f = wrap_pyfunction_bound!(fail_with_unmapped_error)(py).unwrap();
+        py_expect_exception!(py, f, "f()", PyRuntimeError, "nobody mapped me");
+    });
+}
+
 #[test]
 fn test_exception_nosegfault() {
     use std::net::TcpListener;
@@ -127,3 +198,34 @@ fn test_write_unraisable() {
         capture.borrow_mut(py).uninstall(py);
     });
 }
+
+#[test]
+#[cfg(Py_3_8)]
+fn test_write_unraisable_bound_from_drop() {
+    use common::UnraisableCapture;
+    use pyo3::exceptions::PyRuntimeError;
+
+    struct ReportOnDrop(PyObject);
+
+    impl Drop for ReportOnDrop {
+        fn drop(&mut self) {
+            Python::with_gil(|py| {
+                PyRuntimeError::new_err("dropped")
+                    .write_unraisable_bound(py, Some(self.0.bind(py)));
+            })
+        }
+    }
+
+    Python::with_gil(|py| {
+        let capture = UnraisableCapture::install(py);
+
+        let context = pyo3::types::PyString::new_bound(py, "context");
+        drop(ReportOnDrop(context.clone().into_any().unbind()));
+
+        let (err, object) = capture.borrow_mut(py).capture.take().unwrap();
+        assert_eq!(err.to_string(), "RuntimeError: dropped");
+        assert!(object.bind(py).is(&context));
+
+        capture.borrow_mut(py).uninstall(py);
+    });
+}
@@ -441,6 +441,56 @@ TypeError: failed to extract enum Foo ('TupleVar | StructVar | TransparentTuple
     });
 }
 
+#[derive(Debug, FromPyObject, PartialEq)]
+#[pyo3(tag = "type")]
+enum Shape {
+    #[pyo3(tag_value = "circle", from_item_all)]
+    Circle { radius: f64 },
+    #[pyo3(tag_value = "square", from_item_all)]
+    Square { side: f64 },
+}
+
+#[test]
+fn test_tagged_enum() {
+    Python::with_gil(|py| {
+        let circle = py
+            .eval_bound("{'type': 'circle', 'radius': 1.0}", None, None)
+            .unwrap();
+        assert_eq!(
+            circle.extract::<Shape>().unwrap(),
+            Shape::Circle { radius: 1.0 }
+        );
+        let square = py
+            .eval_bound("{'type': 'square', 'side': 2.0}", None, None)
+            .unwrap();
+        assert_eq!(
+            square.extract::<Shape>().unwrap(),
+            Shape::Square { side: 2.0 }
+        );
+
+        // only the variant selected by the tag is tried
+        let bad_square = py
+            .eval_bound("{'type': 'square', 'radius': 1.0}", None, None)
+            .unwrap();
+        assert_eq!(
+            extract_traceback(py, bad_square.extract::<Shape>().unwrap_err()),
+            "KeyError: 'side'"
+        );
+
+        let triangle = py.eval_bound("{'type': 'triangle'}", None, None).unwrap();
+        assert_eq!(
+            triangle.extract::<Shape>().unwrap_err().to_string(),
+            "TypeError: failed to extract enum Shape: unknown tag 'type' value 'triangle', expected one of: 'circle', 'square'"
+        );
+
+        let untagged = PyDict::new_bound(py);
+        assert_eq!(
+            extract_traceback(py, untagged.extract::<Shape>().unwrap_err()),
+            "TypeError: failed to extract enum Shape: missing tag 'type': KeyError: 'type'"
+        );
+    });
+}
+
 #[derive(Debug, FromPyObject)]
 enum EnumWithCatchAll<'py> {
     #[allow(dead_code)]
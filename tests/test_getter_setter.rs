@@ -249,3 +249,158 @@ fn borrowed_value_with_lifetime_of_self() {
         py_run!(py, inst, "assert inst.value == 'value'");
     });
 }
+
+#[pyclass]
+struct Node {
+    #[pyo3(get)]
+    value: i32,
+}
+
+#[pyclass]
+struct Link {
+    #[pyo3(get, set)]
+    target: Py<Node>,
+}
+
+#[test]
+fn py_field_setter_checks_type() {
+    Python::with_gil(|py| {
+        let node = Py::new(py, Node { value: 1 }).unwrap();
+        let inst = Py::new(py, Link { target: node }).unwrap();
+        let other = Py::new(py, Node { value: 2 }).unwrap();
+
+        py_run!(py, inst other, "inst.target = other; assert inst.target.value == 2");
+        py_expect_exception!(py, inst, "inst.target = 'not a node'", PyTypeError);
+        py_expect_exception!(py, inst, "inst.target = None", PyTypeError);
+        py_assert!(py, inst, "inst.target.value == 2");
+    });
+}
+
+#[pyclass]
+struct CachedGetters {
+    #[pyo3(get, set, cache)]
+    items: Vec<i32>,
+    #[pyo3(get, cache)]
+    name: String,
+    #[pyo3(get)]
+    uncached: Vec<i32>,
+}
+
+#[test]
+fn cached_getters() {
+    Python::with_gil(|py| {
+        let inst = Py::new(
+            py,
+            CachedGetters {
+                items: vec![1, 2, 3],
+                name: "cached".to_string(),
+                uncached: vec![4],
+            },
+        )
+        .unwrap();
+
+        py_assert!(py, inst, "inst.items is inst.items");
+        py_assert!(py, inst, "inst.name is inst.name");
+        py_assert!(py, inst, "inst.uncached is not inst.uncached");
+
+        py_run!(
+            py,
+            inst,
+            r#"
+            items = inst.items
+            inst.items = [4, 5]
+            assert inst.items == [4, 5]
+            assert items == [1, 2, 3]
+            "#
+        );
+
+        let name = inst.getattr(py, "name").unwrap();
+        inst.borrow_mut(py).name.push_str(" again");
+        let renamed = inst.getattr(py, "name").unwrap();
+        assert!(!renamed.is(&name));
+        assert_eq!(renamed.extract::<String>(py).unwrap(), "cached again");
+    });
+}
+
+#[pyclass(frozen)]
+struct FrozenCachedGetter {
+    #[pyo3(get, cache)]
+    items: Vec<i32>,
+}
+
+#[test]
+fn frozen_cached_getter() {
+    Python::with_gil(|py| {
+        let inst = Py::new(py, FrozenCachedGetter { items: vec![1] }).unwrap();
+        py_assert!(py, inst, "inst.items is inst.items");
+    });
+}
+
+fn normalize_name(_person: &Person, name: String) -> PyResult<String> {
+    match name.trim() {
+        "" => Err(pyo3::exceptions::PyValueError::new_err("empty name")),
+        name => Ok(name.to_owned()),
+    }
+}
+
+#[pyclass(get_all, set_all)]
+struct Person {
+    #[pyo3(set = validate_age)]
+    age: i32,
+    #[pyo3(set = self::normalize_name)]
+    name: String,
+    nickname: String,
+}
+
+#[pymethods]
+impl Person {
+    fn validate_age(&self, age: i32) -> PyResult<i32> {
+        if age < 0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "{} cannot have a negative age",
+                self.name
+            )));
+        }
+        Ok(age)
+    }
+}
+
+#[test]
+fn setter_with_validator() {
+    Python::with_gil(|py| {
+        let inst = Py::new(
+            py,
+            Person {
+                age: 30,
+                name: "Alice".to_owned(),
+                nickname: String::new(),
+            },
+        )
+        .unwrap();
+        py_run!(
+            py,
+            inst,
+            r#"
+            inst.age = 31
+            assert inst.age == 31
+            try:
+                inst.age = -1
+            except ValueError as e:
+                assert str(e) == "Alice cannot have a negative age"
+            else:
+                assert False
+            assert inst.age == 31
+
+            inst.name = "  Bob "
+            assert inst.name == "Bob"
+            try:
+                inst.name = " "
+            except ValueError:
+                pass
+            assert inst.name == "Bob"
+            inst.nickname = "  B "
+            assert inst.nickname == "  B "
+            "#
+        );
+    });
+}
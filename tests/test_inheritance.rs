@@ -367,3 +367,164 @@ fn module_add_class_inherit_bool_fails() {
         );
     })
 }
+
+#[pyclass(subclass)]
+struct SolidBase {
+    #[pyo3(get)]
+    value: usize,
+}
+
+#[pyclass(frozen, subclass)]
+struct DescribeMixin;
+
+#[pymethods]
+impl DescribeMixin {
+    fn describe(slf: &Bound<'_, Self>) -> PyResult<String> {
+        Ok(format!("value={}", slf.getattr("value")?))
+    }
+}
+
+#[pyclass(frozen, subclass)]
+struct TagMixin;
+
+#[pymethods]
+impl TagMixin {
+    #[classattr]
+    fn tag() -> &'static str {
+        "tagged"
+    }
+}
+
+#[pyclass(extends = (SolidBase, DescribeMixin, TagMixin))]
+struct Mixed;
+
+#[pymethods]
+impl Mixed {
+    #[new]
+    fn new(value: usize) -> (Self, SolidBase) {
+        (Mixed, SolidBase { value })
+    }
+}
+
+#[test]
+fn inherit_from_mixins() {
+    Python::with_gil(|py| {
+        let mixed = py.get_type_bound::<Mixed>();
+        let base = py.get_type_bound::<SolidBase>();
+        let describe = py.get_type_bound::<DescribeMixin>();
+        let tag = py.get_type_bound::<TagMixin>();
+        py_run!(
+            py,
+            mixed base describe tag,
+            r#"
+            assert mixed.__mro__ == (mixed, base, describe, tag, object)
+            m = mixed(3)
+            assert m.value == 3
+            assert m.describe() == "value=3"
+            assert m.tag == "tagged"
+            assert isinstance(m, describe) and isinstance(m, tag)
+            "#
+        );
+    })
+}
+
+#[pyclass(subclass)]
+struct StatefulMixin {
+    _state: usize,
+}
+
+#[pyclass(extends = (SolidBase, StatefulMixin))]
+struct LayoutConflict;
+
+#[test]
+fn inherit_from_stateful_mixin_fails() {
+    Python::with_gil(|py| {
+        let m = PyModule::new_bound(py, "test_module").unwrap();
+
+        let err = m.add_class::<LayoutConflict>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "RuntimeError: An error occurred while initializing class LayoutConflict"
+        );
+        assert_eq!(
+            err.cause(py).unwrap().to_string(),
+            "TypeError: multiple bases have instance lay-out conflict"
+        );
+    })
+}
+
+#[pyclass(subclass)]
+struct Configurable;
+
+#[pymethods]
+impl Configurable {
+    #[classmethod]
+    #[pyo3(signature = (**kwargs))]
+    fn __init_subclass__(
+        cls: &Bound<'_, pyo3::types::PyType>,
+        kwargs: Option<&Bound<'_, pyo3::types::PyDict>>,
+    ) -> PyResult<()> {
+        cls.setattr("config", kwargs.map(|kwargs| kwargs.copy()).transpose()?)
+    }
+}
+
+#[test]
+fn init_subclass_receives_class_keywords() {
+    Python::with_gil(|py| {
+        let base = py.get_type_bound::<Configurable>();
+        py_run!(
+            py,
+            base,
+            r#"
+            class Plain(base):
+                pass
+
+            class Configured(base, mode="fast", retries=3):
+                pass
+
+            assert Plain.config is None
+            assert Configured.config == {"mode": "fast", "retries": 3}
+            "#
+        );
+    })
+}
+
+#[pyclass(subclass)]
+struct Registered;
+
+#[pymethods]
+impl Registered {
+    #[classattr]
+    fn registry(py: Python<'_>) -> PyObject {
+        pyo3::types::PyList::empty_bound(py).into()
+    }
+
+    #[classmethod]
+    fn __init_subclass__(cls: &Bound<'_, pyo3::types::PyType>) -> PyResult<()> {
+        cls.getattr("registry")?.call_method1("append", (cls,))?;
+        Ok(())
+    }
+}
+
+#[test]
+fn init_subclass_receives_the_subclass() {
+    Python::with_gil(|py| {
+        let base = py.get_type_bound::<Registered>();
+        py_run!(
+            py,
+            base,
+            r#"
+            class Child(base):
+                pass
+
+            assert base.registry[-1] is Child
+
+            class GrandChild(Child):
+                pass
+
+            assert base.registry == [Child, GrandChild]
+            assert base.registry[-1] is GrandChild
+            "#
+        );
+    })
+}
@@ -0,0 +1,63 @@
+#![cfg(feature = "macros")]
+
+use pyo3::prelude::*;
+
+#[macro_use]
+#[path = "../src/tests/common.rs"]
+mod common;
+
+#[derive(IntoPy)]
+enum Outcome {
+    Count(usize),
+    Label(String),
+    Pair(i32, String),
+    Point { x: f64, y: f64 },
+    Missing,
+}
+
+#[pyfunction]
+fn outcome(kind: &str) -> Outcome {
+    match kind {
+        "count" => Outcome::Count(3),
+        "label" => Outcome::Label("three".to_owned()),
+        "pair" => Outcome::Pair(3, "three".to_owned()),
+        "point" => Outcome::Point { x: 1.0, y: 2.0 },
+        _ => Outcome::Missing,
+    }
+}
+
+#[test]
+fn test_enum_variants_convert_to_different_types() {
+    Python::with_gil(|py| {
+        let f = wrap_pyfunction_bound!(outcome, py).unwrap();
+        py_assert!(py, f, "f('count') == 3");
+        py_assert!(py, f, "f('label') == 'three'");
+        py_assert!(py, f, "f('pair') == (3, 'three')");
+        py_assert!(py, f, "f('point') == {'x': 1.0, 'y': 2.0}");
+        py_assert!(py, f, "f('other') is None");
+    });
+}
+
+#[derive(IntoPy)]
+struct Wrapper<T>(T);
+
+#[derive(IntoPy)]
+struct Pair<T> {
+    first: T,
+    second: Wrapper<T>,
+}
+
+#[test]
+fn test_generic_structs() {
+    Python::with_gil(|py| {
+        let wrapper = Wrapper(5).into_py(py);
+        assert_eq!(wrapper.extract::<i32>(py).unwrap(), 5);
+
+        let pair = Pair {
+            first: "a",
+            second: Wrapper("b"),
+        }
+        .into_py(py);
+        py_assert!(py, pair, "pair == {'first': 'a', 'second': 'b'}");
+    });
+}
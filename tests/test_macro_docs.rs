@@ -36,3 +36,30 @@ fn meth_doc() {
         );
     });
 }
+
+/// These Rust docs are replaced by the `doc` option.
+#[pyclass]
+#[pyo3(doc = include_str!("docs/external_class_doc.md"))]
+struct ExternalDocs {}
+
+#[pyclass(doc = "Docs from a string literal.")]
+enum LiteralDocs {
+    A,
+}
+
+#[test]
+fn class_doc_option() {
+    Python::with_gil(|py| {
+        let d = [
+            ("C", py.get_type_bound::<ExternalDocs>()),
+            ("E", py.get_type_bound::<LiteralDocs>()),
+        ]
+        .into_py_dict_bound(py);
+        py_assert!(
+            py,
+            *d,
+            "C.__doc__ == 'Documentation maintained in a separate file.'"
+        );
+        py_assert!(py, *d, "E.__doc__ == 'Docs from a string literal.'");
+    });
+}
@@ -1158,3 +1158,107 @@ fn test_issue_2988() {
     ) {
     }
 }
+
+pyo3::shared_signature!(point_signature = (x, y = 0));
+
+macro_rules! origin {
+    () => {
+        (1, 1)
+    };
+}
+pyo3::shared_signature!(scale_signature = (factor = 2, /));
+
+#[pyclass]
+struct SharedSignatures {
+    x: i32,
+    y: i32,
+}
+
+#[pymethods]
+impl SharedSignatures {
+    #[new]
+    #[pyo3(signature = point_signature!())]
+    fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    #[pyo3(signature = point_signature!())]
+    fn offset(&self, x: i32, y: i32) -> (i32, i32) {
+        (self.x + x, self.y + y)
+    }
+
+    #[pyo3(signature = scale_signature!())]
+    fn scale(&self, factor: i32) -> (i32, i32) {
+        (self.x * factor, self.y * factor)
+    }
+
+    /// `signature = name!()` in the method body is not a shared signature.
+    fn relative_to_origin(&self) -> (i32, i32) {
+        let signature = origin!();
+        (self.x - signature.0, self.y - signature.1)
+    }
+}
+
+#[test]
+fn test_shared_signatures() {
+    Python::with_gil(|py| {
+        let cls = py.get_type_bound::<SharedSignatures>();
+        py_run!(
+            py,
+            cls,
+            r#"
+p = cls(1)
+assert p.offset(2) == (3, 0)
+assert p.offset(x=2, y=5) == (3, 5)
+assert cls(x=1, y=2).scale() == (2, 4)
+assert p.scale(3) == (3, 0)
+assert cls(1, 2).relative_to_origin() == (0, 1)
+"#
+        );
+        py_expect_exception!(py, cls, "cls(1).scale(factor=3)", PyTypeError);
+    });
+}
+
+#[pyclass]
+struct SignatureObjectMethods {
+    base: i64,
+}
+
+#[pymethods]
+impl SignatureObjectMethods {
+    #[new]
+    fn new(base: i64) -> Self {
+        Self { base }
+    }
+
+    /// Adds to the base.
+    #[pyo3(signature_object, signature = (x, *, scale = 2))]
+    fn add(&self, x: i64, scale: i64) -> i64 {
+        self.base + x * scale
+    }
+}
+
+#[test]
+fn test_signature_object_method() {
+    Python::with_gil(|py| {
+        let cls = py.get_type_bound::<SignatureObjectMethods>();
+        py_run!(
+            py,
+            cls,
+            r#"
+import inspect
+obj = cls(1)
+assert obj.add(2) == 5
+assert obj.add(2, scale=3) == 7
+assert cls.add(obj, 2) == 5
+assert str(inspect.signature(obj.add)) == "(x: int, *, scale: int = 2) -> int", str(inspect.signature(obj.add))
+assert str(inspect.signature(cls.add)) == "(self, /, x: int, *, scale: int = 2) -> int", str(inspect.signature(cls.add))
+assert cls.add.__name__ == "add"
+assert cls.add.__qualname__ == "SignatureObjectMethods.add"
+assert cls.add.__doc__ == "Adds to the base."
+"#
+        );
+        py_expect_exception!(py, cls, "cls.add(5)", PyTypeError);
+        py_expect_exception!(py, cls, "cls.add()", PyTypeError);
+    });
+}
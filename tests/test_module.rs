@@ -4,7 +4,7 @@ use pyo3::prelude::*;
 
 use pyo3::py_run;
 use pyo3::types::PyString;
-use pyo3::types::{IntoPyDict, PyDict, PyTuple};
+use pyo3::types::{IntoPyDict, ModuleState, PyDict, PyTuple};
 
 #[path = "../src/tests/common.rs"]
 mod common;
@@ -225,6 +225,24 @@ fn test_custom_names() {
     });
 }
 
+#[test]
+fn test_module_gil_used() {
+    #[pymodule]
+    #[pyo3(gil_used = false)]
+    fn gil_not_used(m: &Bound<'_, PyModule>) -> PyResult<()> {
+        m.add_function(wrap_pyfunction!(double, m)?)?;
+        Ok(())
+    }
+
+    Python::with_gil(|py| {
+        let module = pyo3::wrap_pymodule!(gil_not_used)(py);
+
+        py_assert!(py, module, "module.double(3) == 6");
+        #[cfg(py_sys_config = "Py_GIL_DISABLED")]
+        py_assert!(py, module, "not __import__('sys')._is_gil_enabled()");
+    });
+}
+
 #[test]
 fn test_module_dict() {
     #[pymodule]
@@ -314,6 +332,45 @@ fn test_module_nesting() {
     });
 }
 
+#[pymodule]
+#[pyo3(submodules(submodule_with_init_fn, self::declared_child))]
+fn declarative_supermodule(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    // submodules are added before the body runs
+    assert!(module.hasattr("declared_child")?);
+    module.add_function(wrap_pyfunction!(superfunction, module)?)?;
+    Ok(())
+}
+
+#[pymodule]
+fn declared_child(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add("answer", 42)?;
+    Ok(())
+}
+
+#[test]
+fn test_module_submodules_option() {
+    Python::with_gil(|py| {
+        let supermodule = pyo3::wrap_pymodule!(declarative_supermodule)(py);
+
+        py_assert!(
+            py,
+            supermodule,
+            "supermodule.superfunction() == 'Superfunction'"
+        );
+        py_assert!(
+            py,
+            supermodule,
+            "supermodule.submodule_with_init_fn.subfunction() == 'Subfunction'"
+        );
+        py_assert!(py, supermodule, "supermodule.declared_child.answer == 42");
+        py_assert!(
+            py,
+            supermodule,
+            "supermodule.declared_child.__name__ == 'declared_child'"
+        );
+    });
+}
+
 // Test that argument parsing specification works for pyfunctions
 
 #[pyfunction(signature = (a=5, *args))]
@@ -345,6 +402,32 @@ fn test_vararg_module() {
     });
 }
 
+#[pymodule]
+fn signature_object_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    #[pyfn(m)]
+    #[pyo3(signature_object, signature = (a, b = 2))]
+    fn int_add(a: i32, b: i32) -> i32 {
+        a + b
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_signature_object_module() {
+    Python::with_gil(|py| {
+        let m = pyo3::wrap_pymodule!(signature_object_module)(py);
+
+        py_assert!(py, m, "m.int_add(1) == 3");
+        py_assert!(
+            py,
+            m,
+            "str(__import__('inspect').signature(m.int_add)) == '(a: int, b: int = 2) -> int'"
+        );
+        py_assert!(py, m, "m.int_add.__module__ == 'signature_object_module'");
+    });
+}
+
 #[test]
 fn test_module_with_constant() {
     // Regression test for #1102
@@ -498,6 +581,74 @@ fn test_module_functions_with_module() {
     });
 }
 
+struct Counter {
+    start: usize,
+}
+
+#[pyfunction]
+#[pyo3(pass_module)]
+fn counter_start(module: &Bound<'_, PyModule>) -> PyResult<usize> {
+    module.state::<Counter>().map(|counter| counter.start)
+}
+
+#[pyfunction]
+#[pyo3(pass_module)]
+fn counter_start_typed(
+    counter: ModuleState<'_, '_, Counter>,
+    offset: usize,
+) -> PyResult<(String, usize)> {
+    Ok((counter.module().name()?.to_string(), counter.start + offset))
+}
+
+#[pyfunction]
+#[pyo3(pass_module)]
+fn wrong_state(state: ModuleState<'_, '_, String>) -> String {
+    state.state().clone()
+}
+
+#[pymodule]
+fn module_with_state(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.set_state(Counter { start: 10 })?;
+    m.add_function(wrap_pyfunction!(counter_start, m)?)?;
+    m.add_function(wrap_pyfunction!(counter_start_typed, m)?)?;
+    m.add_function(wrap_pyfunction!(wrong_state, m)?)?;
+    Ok(())
+}
+
+#[test]
+fn test_module_state() {
+    Python::with_gil(|py| {
+        let m = pyo3::wrap_pymodule!(module_with_state)(py).into_bound(py);
+        py_assert!(py, m, "m.counter_start() == 10");
+        py_assert!(
+            py,
+            m,
+            "m.counter_start_typed(5) == ('module_with_state', 15)"
+        );
+        py_expect_exception!(py, m, "m.wrong_state()", PyRuntimeError);
+
+        let err = m.set_state(Counter { start: 0 }).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "RuntimeError: module state has already been set"
+        );
+        assert_eq!(m.state::<Counter>().unwrap().start, 10);
+    });
+}
+
+#[test]
+fn test_module_state_requires_pyo3_module() {
+    Python::with_gil(|py| {
+        let m = PyModule::new_bound(py, "plain").unwrap();
+        let err = m.set_state(Counter { start: 0 }).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "RuntimeError: module state is only available for modules created by PyO3"
+        );
+        assert!(m.state::<Counter>().is_err());
+    });
+}
+
 #[test]
 fn test_module_doc_hidden() {
     #[doc(hidden)]
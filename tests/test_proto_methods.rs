@@ -1,7 +1,7 @@
 #![cfg(feature = "macros")]
 
 use pyo3::exceptions::{PyAttributeError, PyIndexError, PyValueError};
-use pyo3::types::{PyDict, PyList, PyMapping, PySequence, PySlice, PyType};
+use pyo3::types::{PyBytes, PyDict, PyList, PyMapping, PySequence, PySlice, PyType};
 use pyo3::{prelude::*, py_run};
 use std::{isize, iter};
 
@@ -393,6 +393,29 @@ fn iterator() {
     });
 }
 
+#[pyclass]
+struct IterField {
+    #[pyo3(iter)]
+    words: std::vec::IntoIter<String>,
+}
+
+#[test]
+fn iterator_from_field() {
+    Python::with_gil(|py| {
+        let words = vec!["a".to_string(), "b".to_string()];
+        let inst = Py::new(
+            py,
+            IterField {
+                words: words.into_iter(),
+            },
+        )
+        .unwrap();
+        py_assert!(py, inst, "iter(inst) is inst");
+        py_assert!(py, inst, "[w for w in inst] == ['a', 'b']");
+        py_expect_exception!(py, inst, "next(inst)", PyStopIteration);
+    });
+}
+
 #[pyclass]
 struct Callable;
 
@@ -418,6 +441,33 @@ fn callable() {
     });
 }
 
+#[pyclass]
+struct Packet {
+    payload: Vec<u8>,
+}
+
+#[pymethods]
+impl Packet {
+    #[allow(clippy::unnecessary_wraps)]
+    fn __bytes__(&self, py: Python<'_>) -> PyResult<Py<PyBytes>> {
+        Ok(PyBytes::new_bound(py, &self.payload).unbind())
+    }
+}
+
+#[test]
+fn bytes() {
+    Python::with_gil(|py| {
+        let p = Py::new(
+            py,
+            Packet {
+                payload: vec![0, 1, 255],
+            },
+        )
+        .unwrap();
+        py_assert!(py, p, "bytes(p) == b'\\x00\\x01\\xff'");
+    });
+}
+
 #[pyclass]
 #[derive(Debug)]
 struct SetItem {
@@ -523,6 +573,36 @@ fn contains() {
     });
 }
 
+#[pyclass]
+struct ContainsWithIter {
+    items: Vec<i32>,
+}
+
+#[pymethods]
+impl ContainsWithIter {
+    fn __contains__(&self, item: i32) -> PyResult<bool> {
+        if item == 0 {
+            return Err(PyValueError::new_err("zero"));
+        }
+        Ok(self.items.contains(&item))
+    }
+
+    fn __iter__(&self) -> PyResult<()> {
+        Err(PyValueError::new_err("membership must not iterate"))
+    }
+}
+
+#[test]
+fn contains_uses_slot() {
+    Python::with_gil(|py| {
+        let c = Py::new(py, ContainsWithIter { items: vec![1, 2] }).unwrap();
+        py_assert!(py, c, "1 in c");
+        py_assert!(py, c, "3 not in c");
+        py_expect_exception!(py, c, "0 in c", PyValueError);
+        py_assert!(py, c, "type(c).__contains__(c, 2)");
+    });
+}
+
 #[pyclass]
 struct GetItem {}
 
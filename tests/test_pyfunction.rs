@@ -9,7 +9,7 @@ use pyo3::prelude::*;
 use pyo3::types::PyDateTime;
 #[cfg(not(any(Py_LIMITED_API, PyPy)))]
 use pyo3::types::PyFunction;
-use pyo3::types::{self, PyCFunction};
+use pyo3::types::{self, IntoPyDict, PyCFunction};
 
 #[path = "../src/tests/common.rs"]
 mod common;
@@ -555,3 +555,916 @@ fn test_reference_to_bound_arguments() {
         py_assert!(py, function, "function(1, 2) == 3");
     })
 }
+
+#[test]
+fn test_unit_return_is_none() {
+    #[pyfunction]
+    fn fallible_unit(fail: bool) -> PyResult<()> {
+        if fail {
+            Err(pyo3::exceptions::PyValueError::new_err("failed"))
+        } else {
+            Ok(())
+        }
+    }
+
+    #[pyfunction]
+    fn implicit_unit() {}
+
+    #[pyfunction]
+    #[allow(clippy::unused_unit)]
+    fn explicit_unit() -> () {}
+
+    Python::with_gil(|py| {
+        let fallible_unit = wrap_pyfunction_bound!(fallible_unit, py).unwrap();
+        py_assert!(py, fallible_unit, "fallible_unit(False) is None");
+        py_expect_exception!(py, fallible_unit, "fallible_unit(True)", PyValueError);
+
+        let implicit_unit = wrap_pyfunction_bound!(implicit_unit, py).unwrap();
+        py_assert!(py, implicit_unit, "implicit_unit() is None");
+
+        let explicit_unit = wrap_pyfunction_bound!(explicit_unit, py).unwrap();
+        py_assert!(py, explicit_unit, "explicit_unit() is None");
+    })
+}
+
+#[derive(FromPyObject)]
+#[pyo3(from_item_all)]
+struct ScaleOptions {
+    factor: i32,
+    offset: i32,
+}
+
+#[pyfunction]
+fn scale(value: i32, #[pyo3(kwargs_struct)] options: ScaleOptions) -> i32 {
+    value * options.factor + options.offset
+}
+
+#[pyfunction]
+#[pyo3(signature = (value, offset = 0, **options))]
+fn scale_with_signature(
+    value: i32,
+    offset: i32,
+    #[pyo3(kwargs_struct)] options: HashMap<String, i32>,
+) -> i32 {
+    value * options.get("factor").copied().unwrap_or(1) + offset
+}
+
+#[test]
+fn test_kwargs_struct() {
+    Python::with_gil(|py| {
+        let f = wrap_pyfunction_bound!(scale_with_signature, py).unwrap();
+        py_assert!(py, f, "f(2) == 2");
+        py_assert!(py, f, "f(2, 1, factor=3) == 7");
+        py_assert!(py, f, "f(2, offset=1, factor=3) == 7");
+
+        let f = wrap_pyfunction_bound!(scale, py).unwrap();
+        py_assert!(py, f, "f(2, factor=3, offset=1) == 7");
+        py_assert!(py, f, "f(value=2, offset=1, factor=3) == 7");
+        py_assert!(py, f, "f.__text_signature__ == '(value, **options)'");
+        py_expect_exception!(py, f, "f(2, 3, offset=1)", PyTypeError);
+        py_expect_exception!(py, f, "f(2, factor='a', offset=1)", PyTypeError);
+
+        let err = f
+            .call((2,), Some(&[("factor", 3)].into_py_dict_bound(py)))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "TypeError: scale() missing 1 required keyword argument: 'offset'"
+        );
+        let kwargs = [("factor", 3), ("offset", 1), ("ofset", 1)].into_py_dict_bound(py);
+        let err = f.call((2,), Some(&kwargs)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "TypeError: scale() got an unexpected keyword argument 'ofset'"
+        );
+    })
+}
+
+#[derive(FromPyObject)]
+#[pyo3(from_item_all)]
+struct FormatOptions {
+    width: usize,
+    #[pyo3(item("fill"))]
+    fill_char: Option<char>,
+    align: Option<String>,
+}
+
+#[pyfunction]
+fn pad(text: &str, #[pyo3(kwargs_struct)] options: FormatOptions) -> String {
+    let fill = options.fill_char.unwrap_or(' ');
+    let padding = options.width.saturating_sub(text.chars().count());
+    let padding: String = std::iter::repeat(fill).take(padding).collect();
+    match options.align.as_deref() {
+        Some("right") => padding + text,
+        _ => text.to_string() + &padding,
+    }
+}
+
+#[test]
+fn test_kwargs_struct_optional_fields() {
+    Python::with_gil(|py| {
+        let f = wrap_pyfunction_bound!(pad, py).unwrap();
+        py_assert!(py, f, "f('ab', width=4) == 'ab  '");
+        py_assert!(py, f, "f('ab', width=4, fill='.') == 'ab..'");
+        py_assert!(
+            py,
+            f,
+            "f('ab', width=4, fill=None, align='right') == '  ab'"
+        );
+        py_expect_exception!(py, f, "f('ab')", PyTypeError);
+        py_expect_exception!(py, f, "f('ab', width=4, fill_char='.')", PyTypeError);
+    })
+}
+
+fn check_positive(value: &i32) -> PyResult<()> {
+    if *value > 0 {
+        Ok(())
+    } else {
+        Err(pyo3::exceptions::PyValueError::new_err("must be positive"))
+    }
+}
+
+fn check_not_empty(value: &&str) -> PyResult<()> {
+    if value.is_empty() {
+        Err(pyo3::exceptions::PyTypeError::new_err("must not be empty"))
+    } else {
+        Ok(())
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (n, label = "x"))]
+fn repeat_label(
+    #[pyo3(validate = check_positive)] n: i32,
+    #[pyo3(validate = check_not_empty)] label: &str,
+) -> String {
+    label.repeat(n as usize)
+}
+
+#[test]
+fn test_validate_argument() {
+    Python::with_gil(|py| {
+        let f = wrap_pyfunction_bound!(repeat_label, py).unwrap();
+        py_assert!(py, f, "f(3) == 'xxx'");
+        py_assert!(py, f, "f(2, 'ab') == 'abab'");
+        py_expect_exception!(py, f, "f(0)", PyValueError);
+        py_expect_exception!(py, f, "f(-1, 'ab')", PyValueError);
+        py_expect_exception!(py, f, "f('a')", PyTypeError);
+
+        let err = f.call1((1, "")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "TypeError: argument 'label': must not be empty"
+        );
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (x, y, z = None))]
+fn strict_floats(x: f64, #[pyo3(strict)] y: f64, #[pyo3(strict)] z: Option<f32>) -> f64 {
+    x + y + f64::from(z.unwrap_or(0.0))
+}
+
+#[test]
+fn test_strict_float_argument() {
+    Python::with_gil(|py| {
+        let f = wrap_pyfunction_bound!(strict_floats, py).unwrap();
+        py_assert!(py, f, "f(1, 2.0) == 3.0");
+        py_assert!(py, f, "f(1.0, 2.0, 0.5) == 3.5");
+        py_assert!(py, f, "f(1.0, 2.0, None) == 3.0");
+        py_expect_exception!(py, f, "f(1.0, 2, 0.5)", PyTypeError);
+        py_expect_exception!(py, f, "f(1.0, 2.0, 1)", PyTypeError);
+
+        let err = f.call1((1.0, 2)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "TypeError: argument 'y': 'int' object cannot be converted to 'float'"
+        );
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (prefix, *args), min_args = 2, max_args = 3)]
+fn bounded_varargs(prefix: &str, args: &Bound<'_, types::PyTuple>) -> String {
+    format!("{}{}", prefix, args.len())
+}
+
+#[pyfunction]
+#[pyo3(signature = (*args, **kwargs), min_args = 1)]
+fn bounded_forwarded_args(
+    args: &Bound<'_, types::PyTuple>,
+    kwargs: Option<&Bound<'_, types::PyDict>>,
+) -> usize {
+    args.len() + kwargs.map_or(0, |kwargs| kwargs.len())
+}
+
+#[test]
+fn test_varargs_arity() {
+    Python::with_gil(|py| {
+        let f = wrap_pyfunction_bound!(bounded_varargs, py).unwrap();
+        py_assert!(py, f, "f('n', 1, 2) == 'n2'");
+        py_assert!(py, f, "f('n', 1, 2, 3) == 'n3'");
+
+        let err = f.call1(("n", 1)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "TypeError: bounded_varargs() takes at least 2 variadic positional arguments but 1 was given"
+        );
+        let err = f.call1(("n", 1, 2, 3, 4)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "TypeError: bounded_varargs() takes at most 3 variadic positional arguments but 4 were given"
+        );
+
+        let f = wrap_pyfunction_bound!(bounded_forwarded_args, py).unwrap();
+        py_assert!(py, f, "f(1, a=2) == 2");
+        py_expect_exception!(py, f, "f(a=2)", PyTypeError);
+    })
+}
+
+static RENDEZVOUS: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+/// Waits (for at most a few seconds) until `n` callers have arrived; this can only succeed if
+/// the callers run concurrently, i.e. without holding the GIL.
+#[pyfunction]
+#[pyo3(allow_threads)]
+fn rendezvous(n: usize) -> bool {
+    use std::sync::atomic::Ordering;
+    use std::time::{Duration, Instant};
+
+    RENDEZVOUS.fetch_add(1, Ordering::SeqCst);
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while RENDEZVOUS.load(Ordering::SeqCst) < n {
+        if Instant::now() > deadline {
+            return false;
+        }
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    true
+}
+
+#[pyfunction]
+#[pyo3(allow_threads, signature = (text, repeat = 2))]
+fn repeat_text(text: &str, repeat: usize) -> PyResult<String> {
+    if repeat == 0 {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "repeat must be positive",
+        ));
+    }
+    Ok(text.repeat(repeat))
+}
+
+#[test]
+fn test_allow_threads() {
+    Python::with_gil(|py| {
+        let f = wrap_pyfunction_bound!(repeat_text, py).unwrap();
+        py_assert!(py, f, "f('ab') == 'abab'");
+        py_assert!(py, f, "f('ab', repeat=3) == 'ababab'");
+        py_expect_exception!(py, f, "f('ab', 0)", PyValueError);
+        py_expect_exception!(py, f, "f(1)", PyTypeError);
+
+        let rendezvous = wrap_pyfunction_bound!(rendezvous, py).unwrap();
+        pyo3::py_run!(
+            py,
+            rendezvous,
+            r#"
+            import threading
+
+            results = []
+            def call(results=results, rendezvous=rendezvous):
+                results.append(rendezvous(2))
+            threads = [threading.Thread(target=call), threading.Thread(target=call)]
+            for thread in threads:
+                thread.start()
+            for thread in threads:
+                thread.join()
+            assert results == [True, True], results
+            "#
+        );
+    })
+}
+
+#[pyfunction]
+fn r#async(r#type: i32) -> i32 {
+    r#type * 2
+}
+
+#[pyfunction]
+#[pyo3(signature = (r#match = 1))]
+fn r#match(r#match: i32) -> i32 {
+    r#match
+}
+
+#[pyfunction]
+#[pyo3(name = "r#await")]
+fn raw_name_attribute() {}
+
+#[pyfunction]
+#[pyo3(name = "await")]
+fn keyword_name_attribute() {}
+
+#[test]
+fn test_raw_identifier_names() {
+    Python::with_gil(|py| {
+        let f = wrap_pyfunction_bound!(r#async, py).unwrap();
+        py_assert!(py, f, "f.__name__ == 'async'");
+        py_assert!(py, f, "f(type=2) == 4");
+        py_assert!(py, f, "f.__text_signature__ == '(type)'");
+
+        let f = wrap_pyfunction_bound!(r#match, py).unwrap();
+        py_assert!(py, f, "f.__name__ == 'match'");
+        py_assert!(py, f, "f() == 1");
+        py_assert!(py, f, "f(match=3) == 3");
+        py_assert!(py, f, "f.__text_signature__ == '(match=...)'");
+
+        let m = PyModule::new_bound(py, "raw_names").unwrap();
+        m.add_function(wrap_pyfunction!(r#async, &m).unwrap())
+            .unwrap();
+        py_assert!(py, m, "m.__dict__['async'](1) == 2");
+        py_assert!(py, m, "'r#async' not in m.__dict__");
+
+        let f = wrap_pyfunction_bound!(raw_name_attribute, py).unwrap();
+        py_assert!(py, f, "f.__name__ == 'await'");
+        let f = wrap_pyfunction_bound!(keyword_name_attribute, py).unwrap();
+        py_assert!(py, f, "f.__name__ == 'await'");
+    })
+}
+
+#[pyfunction]
+#[allow(clippy::type_complexity)]
+fn many_return_values() -> (
+    u8,
+    i32,
+    f64,
+    bool,
+    &'static str,
+    String,
+    Option<i32>,
+    Vec<u8>,
+    (i32, (String, bool)),
+    (),
+    char,
+    u64,
+) {
+    (
+        1,
+        -2,
+        3.5,
+        true,
+        "five",
+        "six".to_owned(),
+        None,
+        vec![8],
+        (9, ("ten".to_owned(), false)),
+        (),
+        'c',
+        12,
+    )
+}
+
+#[pyfunction]
+fn tuple_from_sequence(py: Python<'_>, values: Vec<i32>) -> Py<types::PyTuple> {
+    types::PyTuple::new_bound(py, values).unbind()
+}
+
+#[test]
+fn test_tuple_return_values() {
+    Python::with_gil(|py| {
+        let f = wrap_pyfunction_bound!(many_return_values, py).unwrap();
+        pyo3::py_run!(
+            py,
+            f,
+            r#"
+            result = f()
+            assert type(result) is tuple
+            assert result == (1, -2, 3.5, True, "five", "six", None, [8], (9, ("ten", False)), None, "c", 12)
+            a, b, *_, last = result
+            assert (a, b, last) == (1, -2, 12)
+            assert type(result[8]) is tuple and type(result[8][1]) is tuple
+            "#
+        );
+
+        let f = wrap_pyfunction_bound!(tuple_from_sequence, py).unwrap();
+        py_assert!(py, f, "f([1, 2, 3]) == (1, 2, 3)");
+        py_assert!(py, f, "type(f([])) is tuple");
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (
+    flag = True,
+    other = False,
+    rust_flag = true,
+    value = None,
+    marker = Python::with_gil(|py| py.Ellipsis()),
+))]
+fn singleton_defaults(
+    py: Python<'_>,
+    flag: bool,
+    other: bool,
+    rust_flag: bool,
+    value: Option<i32>,
+    marker: PyObject,
+) -> (bool, bool, bool, Option<i32>, bool) {
+    (flag, other, rust_flag, value, marker.is(&py.Ellipsis()))
+}
+
+#[test]
+fn test_singleton_defaults() {
+    Python::with_gil(|py| {
+        let f = wrap_pyfunction_bound!(singleton_defaults, py).unwrap();
+        py_assert!(py, f, "f() == (True, False, True, None, True)");
+        py_assert!(
+            py,
+            f,
+            "f(False, True, False, 1, None) == (False, True, False, 1, False)"
+        );
+        py_assert!(
+            py,
+            f,
+            "f.__text_signature__ == '(flag=True, other=False, rust_flag=True, value=None, marker=...)'"
+        );
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature_object, signature = (a, b = None, *, scale = 1.5, tags = Vec::new(), accent = "\u{e9}"))]
+fn annotated(
+    a: i64,
+    b: Option<&str>,
+    scale: f64,
+    tags: Vec<String>,
+    accent: &str,
+) -> PyResult<String> {
+    if a < 0 {
+        return Err(pyo3::exceptions::PyValueError::new_err("negative"));
+    }
+    Ok(format!("{} {:?} {} {:?} {}", a, b, scale, tags, accent))
+}
+
+#[test]
+fn test_signature_object() {
+    Python::with_gil(|py| {
+        let m = PyModule::new_bound(py, "signature_object").unwrap();
+        let f = wrap_pyfunction_bound!(annotated, &m).unwrap();
+        pyo3::py_run!(
+            py,
+            f,
+            r#"
+            import inspect, typing
+            sig = inspect.signature(f)
+            assert str(sig) == "(a: int, b: Optional[str] = None, *, scale: float = 1.5, tags: List[str] = [], accent: str = '\u00e9') -> str", str(sig)
+            assert sig.parameters['b'].annotation == typing.Optional[str]
+            assert sig.parameters['tags'].default == []
+            assert sig.parameters['accent'].default == "\u00e9"
+            assert f.__name__ == "annotated"
+            assert f.__module__ == "signature_object"
+            assert f(1, scale=2.0) == "1 None 2 [] \u00e9"
+            "#
+        );
+        py_expect_exception!(py, f, "f(-1)", PyValueError);
+
+        // without a module
+        let m = PyModule::new_bound(py, "no_module").unwrap();
+        m.add_wrapped(wrap_pyfunction_bound!(annotated)).unwrap();
+        let f = m.getattr("annotated").unwrap();
+        py_assert!(
+            py,
+            f,
+            "str(__import__('inspect').signature(f)).startswith('(a: int, b: Optional[str] = None')"
+        );
+        py_assert!(py, f, "f(2, scale=3.0, accent='e') == '2 None 3 [] e'");
+    })
+}
+
+fn describe_call(
+    a: i32,
+    b: i32,
+    args: &Bound<'_, types::PyTuple>,
+    c: i32,
+    d: i32,
+    kwargs: Option<&Bound<'_, types::PyDict>>,
+) -> String {
+    let kwargs = kwargs.map_or_else(|| "{}".to_owned(), |kwargs| kwargs.to_string());
+    format!("{} {} {} {} {} {}", a, b, args, c, d, kwargs)
+}
+
+#[pyfunction]
+#[pyo3(signature = (a, b = 1, *args, c, d = 2, **kwargs))]
+fn full_signature(
+    a: i32,
+    b: i32,
+    args: &Bound<'_, types::PyTuple>,
+    c: i32,
+    d: i32,
+    kwargs: Option<&Bound<'_, types::PyDict>>,
+) -> String {
+    describe_call(a, b, args, c, d, kwargs)
+}
+
+#[pyfunction]
+#[pyo3(signature = (a, /, b, *args, c = 3, **kwargs))]
+fn full_signature_positional_only(
+    a: i32,
+    b: i32,
+    args: &Bound<'_, types::PyTuple>,
+    c: i32,
+    kwargs: Option<&Bound<'_, types::PyDict>>,
+) -> String {
+    describe_call(a, b, args, c, 0, kwargs)
+}
+
+/// `#[new]` is called with a tuple and dict rather than the "fastcall" convention.
+#[pyclass]
+struct FullSignature(String);
+
+#[pymethods]
+impl FullSignature {
+    #[new]
+    #[pyo3(signature = (a, b = 1, *args, c, d = 2, **kwargs))]
+    fn new(
+        a: i32,
+        b: i32,
+        args: &Bound<'_, types::PyTuple>,
+        c: i32,
+        d: i32,
+        kwargs: Option<&Bound<'_, types::PyDict>>,
+    ) -> Self {
+        FullSignature(describe_call(a, b, args, c, d, kwargs))
+    }
+
+    fn __str__(&self) -> &str {
+        &self.0
+    }
+}
+
+#[test]
+fn test_full_signature_binds_like_python() {
+    Python::with_gil(|py| {
+        let f = wrap_pyfunction_bound!(full_signature, py).unwrap();
+        let new = py.get_type_bound::<FullSignature>();
+        let p = wrap_pyfunction_bound!(full_signature_positional_only, py).unwrap();
+        pyo3::py_run!(
+            py,
+            f new p,
+            r#"
+            def py_full(a, b=1, *args, c, d=2, **kwargs):
+                return f"{a} {b} {args} {c} {d} {kwargs}"
+
+            def py_positional_only(a, /, b, *args, c=3, **kwargs):
+                return f"{a} {b} {args} {c} 0 {kwargs}"
+
+            def outcome(function, call):
+                import re
+                try:
+                    return str(eval(f"function({call})"))
+                except TypeError as e:
+                    # only the function name differs between the Rust and Python versions
+                    return re.sub(r"^\S+\(\) ", "", str(e))
+
+            full_calls = [
+                "1, c=3",
+                "1, 2, 3, 4, c=5, d=6",
+                "a=1, b=2, c=3",
+                "1, c=3, e=4, args=5, kwargs=6",
+                "*(1, 2, 3), **{'c': 4, 'd': 5}",
+                # keywords matching a parameter already filled positionally, including via `*args`
+                "1, 2, 3, a=4, c=5",
+                "1, 2, 3, b=4, c=5",
+                "1, *(2, 3), b=4, c=5",
+                "1, 2, 3, c=4, d=5, a=6",
+                # missing arguments
+                "c=3",
+                "1",
+                "1, 2, 3",
+            ]
+            for function in (f, new):
+                for call in full_calls:
+                    assert outcome(function, call) == outcome(py_full, call), call
+
+            positional_only_calls = [
+                "1, 2",
+                "1, b=2",
+                "1, 2, a=3",
+                "1, 2, 3, a=4, c=5",
+                "a=1, b=2",
+                "1, 2, b=3",
+            ]
+            for call in positional_only_calls:
+                assert outcome(p, call) == outcome(py_positional_only, call), call
+            "#
+        );
+    })
+}
+
+#[pyfunction]
+fn squares() -> impl Iterator<Item = u64> + Send {
+    // infinite, so this can only work if items are produced lazily
+    (0u64..).map(|i| i * i)
+}
+
+#[pyfunction]
+fn checked_reciprocals(values: Vec<i64>) -> PyResult<impl Iterator<Item = PyResult<f64>> + Send> {
+    if values.is_empty() {
+        return Err(pyo3::exceptions::PyValueError::new_err("no values"));
+    }
+    Ok(values.into_iter().map(|value| {
+        if value == 0 {
+            Err(pyo3::exceptions::PyZeroDivisionError::new_err(
+                "division by zero",
+            ))
+        } else {
+            Ok(1.0 / value as f64)
+        }
+    }))
+}
+
+#[test]
+fn test_return_impl_iterator() {
+    Python::with_gil(|py| {
+        let squares = wrap_pyfunction_bound!(squares, py).unwrap();
+        let reciprocals = wrap_pyfunction_bound!(checked_reciprocals, py).unwrap();
+        pyo3::py_run!(
+            py,
+            squares reciprocals,
+            r#"
+            import itertools
+            it = squares()
+            assert iter(it) is it
+            assert list(itertools.islice(it, 5)) == [0, 1, 4, 9, 16]
+            assert next(it) == 25
+
+            it = reciprocals([1, 0, 2])
+            assert next(it) == 1.0
+            try:
+                next(it)
+            except ZeroDivisionError as e:
+                assert str(e) == "division by zero"
+            else:
+                assert False, "expected ZeroDivisionError"
+            assert list(it) == [0.5]
+
+            try:
+                reciprocals([])
+            except ValueError as e:
+                assert str(e) == "no values"
+            else:
+                assert False, "expected ValueError"
+            "#
+        );
+    })
+}
+
+#[pyfunction]
+#[pyo3(unpack, signature = (x, y = 0, *, scale = 1))]
+fn unpacked_point(x: i32, y: i32, scale: i32) -> (i32, i32) {
+    (x * scale, y * scale)
+}
+
+#[test]
+fn test_unpack() {
+    Python::with_gil(|py| {
+        let f = wrap_pyfunction_bound!(unpacked_point)(py).unwrap();
+        py_assert!(py, f, "f((1, 2)) == (1, 2)");
+        py_assert!(py, f, "f((3,)) == (3, 0)");
+        py_assert!(py, f, "f({'x': 1, 'y': 2, 'scale': 10}) == (10, 20)");
+        py_assert!(py, f, "f({'x': 1}) == (1, 0)");
+        py_assert!(py, f, "f.__text_signature__ == '(packed, /)'");
+
+        py_expect_exception!(
+            py,
+            f,
+            "f(1, 2)",
+            PyTypeError,
+            "unpacked_point() takes a single tuple or dict of arguments"
+        );
+        py_expect_exception!(
+            py,
+            f,
+            "f(packed=(1, 2))",
+            PyTypeError,
+            "unpacked_point() takes a single tuple or dict of arguments"
+        );
+        py_expect_exception!(
+            py,
+            f,
+            "f([1, 2])",
+            PyTypeError,
+            "unpacked_point() argument must be a tuple or dict, not 'list'"
+        );
+        py_expect_exception!(
+            py,
+            f,
+            "f({1: 2})",
+            PyTypeError,
+            "unpacked_point() keywords must be strings"
+        );
+        py_expect_exception!(
+            py,
+            f,
+            "f((1, 2, 3))",
+            PyTypeError,
+            "unpacked_point() takes from 1 to 2 positional arguments but 3 were given"
+        );
+        py_expect_exception!(
+            py,
+            f,
+            "f({'y': 2})",
+            PyTypeError,
+            "unpacked_point() missing 1 required positional argument: 'x'"
+        );
+    })
+}
+
+#[test]
+fn test_unpack_dict_modified_during_extraction() {
+    Python::with_gil(|py| {
+        let f = wrap_pyfunction_bound!(unpacked_point)(py).unwrap();
+        // the later arguments must stay alive when the dict is cleared while extracting `x`
+        pyo3::py_run!(
+            py,
+            f,
+            r#"
+            class Clearing:
+                def __init__(self, packed):
+                    self.packed = packed
+
+                def __index__(self):
+                    self.packed.clear()
+                    return 1
+
+            class Two:
+                def __index__(self):
+                    return 2
+
+            packed = {}
+            packed.update(x=Clearing(packed), y=Two(), scale=Two())
+            assert f(packed) == (2, 4)
+            assert packed == {}
+            "#
+        );
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (*args, **kwargs))]
+fn forward_call(
+    args: &Bound<'_, types::PyTuple>,
+    kwargs: Option<&Bound<'_, types::PyDict>>,
+) -> PyResult<PyObject> {
+    let (a, b, c) = pyo3::extract_args!(
+        args,
+        kwargs,
+        #[pyo3(signature = (a, /, b = None, *, c))]
+        fn handler(a: i32, b: Option<String>, c: bool)
+    )?;
+    Ok((a, b, c).into_py(args.py()))
+}
+
+#[pyfunction]
+#[pyo3(signature = (*args, **kwargs))]
+fn forward_variadic(
+    args: &Bound<'_, types::PyTuple>,
+    kwargs: Option<&Bound<'_, types::PyDict>>,
+) -> PyResult<PyObject> {
+    let (first, rest, options) = pyo3::extract_args!(
+        args,
+        kwargs,
+        #[pyo3(signature = (first, *rest, **options))]
+        fn variadic<'py>(
+            first: Bound<'py, PyAny>,
+            rest: Bound<'py, types::PyTuple>,
+            options: Option<Bound<'py, types::PyDict>>,
+        )
+    )?;
+    Ok((first, rest, options).into_py(args.py()))
+}
+
+#[test]
+fn test_extract_args() {
+    Python::with_gil(|py| {
+        let f = wrap_pyfunction_bound!(forward_call)(py).unwrap();
+        py_assert!(py, f, "f(1, c=True) == (1, None, True)");
+        py_assert!(py, f, "f(1, 'x', c=False) == (1, 'x', False)");
+        py_assert!(py, f, "f(1, b='y', c=False) == (1, 'y', False)");
+        py_expect_exception!(
+            py,
+            f,
+            "f(a=1, c=True)",
+            PyTypeError,
+            "handler() got some positional-only arguments passed as keyword arguments: 'a'"
+        );
+        py_expect_exception!(
+            py,
+            f,
+            "f(1, None, True)",
+            PyTypeError,
+            "handler() takes from 1 to 2 positional arguments but 3 were given"
+        );
+        py_expect_exception!(
+            py,
+            f,
+            "f(1)",
+            PyTypeError,
+            "handler() missing 1 required keyword-only argument: 'c'"
+        );
+        py_expect_exception!(
+            py,
+            f,
+            "f(1, 'x', b='y', c=True)",
+            PyTypeError,
+            "handler() got multiple values for argument 'b'"
+        );
+        py_expect_exception!(
+            py,
+            f,
+            "f(1, c=True, d=0)",
+            PyTypeError,
+            "handler() got an unexpected keyword argument 'd'"
+        );
+
+        let f = wrap_pyfunction_bound!(forward_variadic)(py).unwrap();
+        py_assert!(py, f, "f(1, 2, 3, x=4) == (1, (2, 3), {'x': 4})");
+        py_assert!(py, f, "f(1) == (1, (), None)");
+    })
+}
+
+#[pyfunction(signature = (*values))]
+fn sum_values(values: Vec<i32>) -> i32 {
+    values.iter().sum()
+}
+
+#[pyfunction(signature = (scale, *values))]
+fn scale_values(scale: i32, values: Vec<i32>) -> Vec<i32> {
+    values.into_iter().map(|value| value * scale).collect()
+}
+
+#[test]
+fn test_typed_varargs() {
+    Python::with_gil(|py| {
+        let f = wrap_pyfunction_bound!(sum_values)(py).unwrap();
+        py_assert!(py, f, "f(1, 2, 3) == 6");
+        py_assert!(py, f, "f() == 0");
+        py_expect_exception!(
+            py,
+            f,
+            "f(1, 'x')",
+            PyTypeError,
+            "argument 'values[1]' (positional argument 2): 'str' object cannot be interpreted as an integer"
+        );
+        py_expect_exception!(py, f, "f(1, 2**40)", PyOverflowError);
+
+        let f = wrap_pyfunction_bound!(scale_values)(py).unwrap();
+        py_assert!(py, f, "f(2, 1, 2, 3) == [2, 4, 6]");
+        py_expect_exception!(
+            py,
+            f,
+            "f(2, 1, None)",
+            PyTypeError,
+            "argument 'values[1]' (positional argument 3): 'NoneType' object cannot be interpreted as an integer"
+        );
+    })
+}
+
+pyo3::shared_signature!(span_signature = (start, end = None, *, inclusive = false));
+
+#[pyfunction]
+#[pyo3(signature = span_signature!())]
+fn span_len(start: i32, end: Option<i32>, inclusive: bool) -> i32 {
+    end.unwrap_or(start) - start + i32::from(inclusive)
+}
+
+#[pyfunction(signature = span_signature!())]
+fn span_repr(start: i32, end: Option<i32>, inclusive: bool) -> String {
+    format!("{} {:?} {}", start, end, inclusive)
+}
+
+macro_rules! default_scale {
+    () => {
+        2
+    };
+}
+
+/// `signature = name!()` in the function body is not a shared signature.
+#[pyfunction]
+fn scaled(value: i32) -> i32 {
+    let signature = default_scale!();
+    value * signature
+}
+
+#[test]
+fn test_shared_signature() {
+    Python::with_gil(|py| {
+        let f = wrap_pyfunction_bound!(span_len)(py).unwrap();
+        py_assert!(py, f, "f(1, 5) == 4");
+        py_assert!(py, f, "f(1, 5, inclusive=True) == 5");
+        py_assert!(py, f, "f(3) == 0");
+        py_expect_exception!(py, f, "f(1, 5, True)", PyTypeError);
+
+        let f = wrap_pyfunction_bound!(span_repr)(py).unwrap();
+        py_assert!(py, f, "f(2, inclusive=True) == '2 None true'");
+
+        let f = wrap_pyfunction_bound!(scaled)(py).unwrap();
+        py_assert!(py, f, "f(3) == 6");
+    });
+}
@@ -124,3 +124,64 @@ fn test_nested_iter_reset() {
         assert!(reader_ref.inner.is_empty());
     });
 }
+
+#[pyclass]
+struct Builder {
+    parts: Vec<String>,
+}
+
+#[pymethods]
+impl Builder {
+    #[new]
+    fn new() -> Self {
+        Builder { parts: Vec::new() }
+    }
+
+    fn push(mut slf: PyRefMut<'_, Self>, part: String) -> PyRefMut<'_, Self> {
+        slf.parts.push(part);
+        slf
+    }
+
+    fn push_checked(mut slf: PyRefMut<'_, Self>, part: String) -> PyResult<PyRefMut<'_, Self>> {
+        if part.is_empty() {
+            return Err(pyo3::exceptions::PyValueError::new_err("empty part"));
+        }
+        slf.parts.push(part);
+        Ok(slf)
+    }
+
+    fn noop(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn build(&self) -> String {
+        self.parts.join(" ")
+    }
+}
+
+#[test]
+fn test_method_chaining() {
+    Python::with_gil(|py| {
+        let builder = py.get_type_bound::<Builder>();
+        py_assert!(
+            py,
+            builder,
+            "builder().push('a').push_checked('b').noop().push('c').build() == 'a b c'"
+        );
+        pyo3::py_run!(
+            py,
+            builder,
+            r#"
+b = builder()
+assert b.push('x').noop() is b
+assert b.build() == 'x'
+"#
+        );
+        py_expect_exception!(
+            py,
+            builder,
+            "builder().push('a').push_checked('')",
+            PyValueError
+        );
+    });
+}
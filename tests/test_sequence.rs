@@ -356,3 +356,99 @@ fn sequence_length() {
         unsafe { ffi::PyErr_Clear() };
     })
 }
+
+#[pyclass(sequence)]
+struct Samples {
+    values: Vec<i64>,
+}
+
+#[pymethods]
+impl Samples {
+    fn __len__(&self) -> usize {
+        self.values.len()
+    }
+
+    fn __getitem__(&self, idx: usize) -> PyResult<i64> {
+        self.values
+            .get(idx)
+            .copied()
+            .ok_or_else(|| PyIndexError::new_err("Samples index out of range"))
+    }
+
+    fn __setitem__(&mut self, idx: usize, value: i64) -> PyResult<()> {
+        match self.values.get_mut(idx) {
+            Some(slot) => {
+                *slot = value;
+                Ok(())
+            }
+            None => Err(PyIndexError::new_err("Samples index out of range")),
+        }
+    }
+
+    fn __delitem__(&mut self, idx: usize) -> PyResult<()> {
+        if idx < self.values.len() {
+            self.values.remove(idx);
+            Ok(())
+        } else {
+            Err(PyIndexError::new_err("Samples index out of range"))
+        }
+    }
+}
+
+#[test]
+fn sequence_negative_indices() {
+    Python::with_gil(|py| {
+        let samples = Py::new(
+            py,
+            Samples {
+                values: vec![1, 2, 3],
+            },
+        )
+        .unwrap();
+
+        py_run!(
+            py,
+            samples,
+            r#"
+            import operator
+            assert samples[-1] == 3
+            assert operator.getitem(samples, -3) == 1
+            samples[-1] = 30
+            assert samples[2] == 30
+            del samples[-3]
+            assert len(samples) == 2
+            assert samples[0] == 2
+            "#
+        );
+        py_expect_exception!(py, samples, "samples[-3]", PyIndexError);
+        py_expect_exception!(py, samples, "samples[2]", PyIndexError);
+    });
+}
+
+#[test]
+fn sequence_c_api_indices() {
+    Python::with_gil(|py| {
+        let samples = Bound::new(
+            py,
+            Samples {
+                values: vec![1, 2, 3],
+            },
+        )
+        .unwrap()
+        .into_any();
+
+        assert_eq!(unsafe { ffi::PySequence_Check(samples.as_ptr()) }, 1);
+        let item = unsafe {
+            Bound::from_owned_ptr_or_err(py, ffi::PySequence_GetItem(samples.as_ptr(), -1))
+        }
+        .unwrap();
+        assert_eq!(item.extract::<i64>().unwrap(), 3);
+
+        // Python has already counted the index from the end, so it must not be counted again
+        let err = unsafe {
+            Bound::from_owned_ptr_or_err(py, ffi::PySequence_GetItem(samples.as_ptr(), -4))
+        }
+        .unwrap_err();
+        assert!(err.is_instance_of::<PyIndexError>(py));
+    });
+}
@@ -0,0 +1,126 @@
+#![cfg(feature = "experimental-stubs")]
+
+use pyo3::prelude::*;
+
+#[pyclass]
+struct StubPoint {
+    #[pyo3(get, set)]
+    x: f64,
+    #[pyo3(get, name = "label")]
+    name: String,
+}
+
+#[pymethods]
+impl StubPoint {
+    #[classattr]
+    const DIMENSIONS: usize = 2;
+
+    #[new]
+    #[pyo3(signature = (x, name = String::from("origin")))]
+    fn new(x: f64, name: String) -> Self {
+        Self { x, name }
+    }
+
+    fn scaled(&self, factor: f64) -> Vec<f64> {
+        vec![self.x * factor]
+    }
+
+    #[getter]
+    fn norm(&self) -> f64 {
+        self.x.abs()
+    }
+
+    #[staticmethod]
+    fn parse(text: &str) -> Option<i64> {
+        text.parse().ok()
+    }
+
+    #[classmethod]
+    fn default_name(_cls: &Bound<'_, pyo3::types::PyType>) -> &'static str {
+        "origin"
+    }
+}
+
+#[pyclass(name = "StubColor")]
+enum Color {
+    Red,
+    #[pyo3(name = "GREEN")]
+    Green,
+}
+
+#[pyfunction]
+#[pyo3(signature = (a, b = 1, *args, flag = false, **kwargs))]
+fn stub_function(
+    a: i32,
+    b: i32,
+    args: &Bound<'_, pyo3::types::PyTuple>,
+    flag: bool,
+    kwargs: Option<&Bound<'_, pyo3::types::PyDict>>,
+) {
+    let _ = (a, b, args, flag, kwargs);
+}
+
+mod inner {
+    use pyo3::prelude::*;
+
+    #[pyfunction]
+    pub fn inner_function(x: Option<bool>) -> bool {
+        x.unwrap_or_default()
+    }
+}
+
+#[pymodule]
+fn stubs_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<StubPoint>()?;
+    m.add_class::<Color>()?;
+    m.add_function(wrap_pyfunction!(stub_function, m)?)?;
+    m.add_function(wrap_pyfunction!(inner::inner_function, m)?)?;
+    Ok(())
+}
+
+#[test]
+fn test_stubs() {
+    assert_eq!(
+        pyo3::stub::stubs("test_stubs"),
+        "\
+import typing
+
+class StubColor:
+    GREEN: typing.ClassVar[StubColor]
+    Red: typing.ClassVar[StubColor]
+
+class StubPoint:
+    DIMENSIONS: typing.ClassVar[int]
+    def __new__(cls, x: float, name: str = ...) -> StubPoint: ...
+    @classmethod
+    def default_name(cls) -> str: ...
+    @property
+    def label(self) -> str: ...
+    @property
+    def norm(self) -> float: ...
+    @staticmethod
+    def parse(text: str) -> typing.Optional[int]: ...
+    def scaled(self, factor: float) -> typing.List[float]: ...
+    @property
+    def x(self) -> float: ...
+    @x.setter
+    def x(self, value: float) -> None: ...
+
+def inner_function(x: typing.Optional[bool] = None) -> bool: ...
+def stub_function(a: int, b: int = 1, *args, flag: bool = False, **kwargs) -> None: ...
+"
+    );
+}
+
+#[test]
+fn test_submodule_stubs() {
+    assert_eq!(
+        pyo3::stub::stubs("test_stubs::inner"),
+        "\
+import typing
+
+def inner_function(x: typing.Optional[bool] = None) -> bool: ...
+"
+    );
+    assert_eq!(pyo3::stub::stubs("test_stubs::other"), "import typing\n");
+}
@@ -213,4 +213,27 @@ struct FromItemAllConflictAttrWithArgs {
     field: String,
 }
 
+#[derive(FromPyObject)]
+#[pyo3(tag = "type")]
+enum TaggedEnumMissingTagValue {
+    #[pyo3(tag_value = "a")]
+    A(String),
+    B(String),
+}
+
+#[derive(FromPyObject)]
+#[pyo3(tag = "type")]
+enum TaggedEnumDuplicateTagValue {
+    #[pyo3(tag_value = "a")]
+    A(String),
+    #[pyo3(tag_value = "a")]
+    B(String),
+}
+
+#[derive(FromPyObject)]
+enum TagValueWithoutTag {
+    #[pyo3(tag_value = "a")]
+    A(String),
+}
+
 fn main() {}
@@ -0,0 +1,50 @@
+use pyo3::IntoPy;
+
+#[derive(IntoPy)]
+enum EmptyEnum {}
+
+#[derive(IntoPy)]
+union Union {
+    a: usize,
+}
+
+#[derive(IntoPy)]
+#[pyo3(transparent)]
+struct Transparent(usize);
+
+#[derive(IntoPy)]
+#[pyo3(from_item_all)]
+struct FromItemAll {
+    a: usize,
+}
+
+#[derive(IntoPy)]
+#[pyo3(annotation = "annotated")]
+struct Annotation(usize);
+
+#[derive(IntoPy)]
+#[pyo3(tag = "kind")]
+enum Tagged {
+    #[pyo3(tag_value = "a")]
+    A(usize),
+}
+
+#[derive(IntoPy)]
+enum VariantAttribute {
+    #[pyo3(transparent)]
+    A(usize),
+}
+
+#[derive(IntoPy)]
+struct FieldItem {
+    #[pyo3(item("key"))]
+    a: usize,
+}
+
+#[derive(IntoPy)]
+struct FieldAttribute {
+    #[pyo3(attribute("name"))]
+    a: usize,
+}
+
+fn main() {}
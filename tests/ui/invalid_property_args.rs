@@ -39,4 +39,23 @@ struct MultipleName(#[pyo3(name = "foo", name = "bar")] i32);
 #[pyclass]
 struct NameWithoutGetSet(#[pyo3(name = "value")] i32);
 
+#[pyclass]
+struct CacheWithoutGet {
+    #[pyo3(set, cache)]
+    value: i32,
+}
+
+#[pyclass]
+struct EqHashWithoutFrozen(#[pyo3(eq_hash)] i32);
+
+#[pyclass(frozen)]
+struct IterOnFrozen(#[pyo3(iter)] std::vec::IntoIter<i32>);
+
+#[pyclass]
+struct ReprWithoutClassRepr(#[pyo3(repr)] i32);
+
+#[pyclass(frozen, eq)]
+#[derive(PartialEq)]
+struct EqWithEqHash(#[pyo3(eq_hash)] i32);
+
 fn main() {}
@@ -30,4 +30,26 @@ struct InvalidArg {}
 #[pyclass(mapping, sequence)]
 struct CannotBeMappingAndSequence {}
 
+#[pyclass(eq, hash)]
+#[derive(PartialEq, Hash)]
+struct HashWithoutFrozen {}
+
+#[pyclass(frozen, hash)]
+#[derive(Hash)]
+struct HashWithoutEq {}
+
+#[pyclass(frozen, eq, hash, compare_with = f64)]
+#[derive(PartialEq, Hash)]
+struct HashWithCompareWith {}
+
+#[pyclass(str = "({}, {y})")]
+struct StrPositionalPlaceholder {
+    y: i32,
+}
+
+#[pyclass(str = "{x:>0$}")]
+struct StrPositionalWidth {
+    x: i32,
+}
+
 fn main() {}
@@ -21,10 +21,69 @@ enum NoUnitVariants {
     UnitVariant,
 }
 
-#[pyclass]
-enum NoTupleVariants {
-    StructVariant { field: i32 },
-    TupleVariant(i32),
+#[pyclass(str = "{0}")]
+enum StrFormatString {
+    A,
+    B,
+}
+
+#[pyclass(repr)]
+enum ReprFromFields {
+    A,
+    B,
+}
+
+#[pyclass(match_args)]
+enum MatchArgs {
+    A,
+    B,
+}
+
+#[pyclass(ord)]
+enum OrdFromRust {
+    A,
+    B,
+}
+
+#[pyclass(replace)]
+enum Replace {
+    A,
+    B,
+}
+
+#[pyclass(context_manager)]
+enum ContextManager {
+    A,
+    B,
+}
+
+#[pyclass(hash)]
+enum Hash {
+    A,
+    B,
+}
+
+#[pyclass(buffer)]
+enum Buffer {
+    A,
+    B,
+}
+
+#[pyclass(getnewargs)]
+enum Getnewargs {
+    A,
+    B,
+}
+
+#[pyclass(subscriptable)]
+enum Subscriptable {
+    A,
+    B,
+}
+#[pyclass(compare_with = i32)]
+enum CompareWith {
+    A,
+    B,
 }
 
 fn main() {}
@@ -0,0 +1,32 @@
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyTuple};
+
+#[pyclass(getnewargs)]
+struct KeywordOnly {
+    #[pyo3(get)]
+    start: i64,
+}
+
+#[pymethods]
+impl KeywordOnly {
+    #[new]
+    #[pyo3(signature = (*, start))]
+    fn new(start: i64) -> Self {
+        Self { start }
+    }
+}
+
+#[pyclass(getnewargs)]
+struct Varargs {}
+
+#[pymethods]
+impl Varargs {
+    #[new]
+    #[pyo3(signature = (*args, **kwargs))]
+    fn new(args: &Bound<'_, PyTuple>, kwargs: Option<&Bound<'_, PyDict>>) -> Self {
+        let _ = (args, kwargs);
+        Self {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+use pyo3::prelude::*;
+
+#[pyfunction]
+#[pyo3(allow_threads)]
+fn python_argument(_py: Python<'_>) {}
+
+#[pyfunction(pass_module)]
+#[pyo3(allow_threads)]
+fn module_argument(_module: &Bound<'_, PyModule>) {}
+
+#[pyclass]
+struct MyClass;
+
+#[pymethods]
+impl MyClass {
+    #[pyo3(allow_threads)]
+    fn method(&self) {}
+}
+
+fn main() {}
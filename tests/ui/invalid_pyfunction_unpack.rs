@@ -0,0 +1,12 @@
+use pyo3::prelude::*;
+
+#[pyclass]
+struct MyClass;
+
+#[pymethods]
+impl MyClass {
+    #[pyo3(unpack)]
+    fn method(&self, _a: i32, _b: i32) {}
+}
+
+fn main() {}
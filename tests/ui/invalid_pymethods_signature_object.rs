@@ -0,0 +1,24 @@
+use pyo3::prelude::*;
+
+#[pyclass]
+struct MyClass;
+
+#[pymethods]
+impl MyClass {
+    #[staticmethod]
+    #[pyo3(signature_object)]
+    fn static_method() {}
+}
+
+#[pyclass]
+struct OtherClass;
+
+#[pymethods]
+impl OtherClass {
+    #[pyo3(signature_object)]
+    fn __repr__(&self) -> String {
+        String::new()
+    }
+}
+
+fn main() {}
@@ -5,4 +5,10 @@ fn module(m: &Bound<'_, PyModule>) -> PyResult<()> {
     Ok(())
 }
 
+#[pymodule]
+#[pyo3(submodules(module), submodules(module))]
+fn duplicate_submodules(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    Ok(())
+}
+
 fn main(){}
@@ -1,23 +1,16 @@
 //! Testing https://github.com/PyO3/pyo3/issues/1106. A result type that
-//! *doesn't* implement `From<MyError> for PyErr` won't be automatically
-//! converted when using `#[pyfunction]`.
+//! implements neither `From<MyError> for PyErr` nor `Display` (for
+//! `pyo3::exceptions::register_mapper`) won't be automatically converted
+//! when using `#[pyfunction]`.
 use pyo3::prelude::*;
 
-use std::fmt;
-
-/// A basic error type for the tests. It's missing `From<MyError> for PyErr`,
-/// though, so it shouldn't work.
+/// A basic error type for the tests. It's missing `From<MyError> for PyErr`
+/// and `Display`, though, so it shouldn't work.
 #[derive(Debug)]
 struct MyError {
     pub descr: &'static str,
 }
 
-impl fmt::Display for MyError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "My error message: {}", self.descr)
-    }
-}
-
 #[pyfunction]
 fn should_not_work() -> Result<(), MyError> {
     Err(MyError {